@@ -35,10 +35,13 @@ pub struct CryptoCoin {
   pub public_header: String,
   pub public_key_hash: String,
   pub script_hash: String,
+  pub bech32_hrp: String,
   pub wallet_import_format: String,
   pub evm: String,
   pub ucid: String,
   pub cmc_top: String,
+  pub chain_id: String,
+  pub eip1191: String,
 }
 
 mod implementation {
@@ -111,6 +114,10 @@ mod implementation {
             .blurb("Script Hash")
             .flags(glib::ParamFlags::READWRITE)
             .build(),
+          glib::ParamSpecString::builder("bech32-hrp")
+            .blurb("Bech32 HRP")
+            .flags(glib::ParamFlags::READWRITE)
+            .build(),
           glib::ParamSpecString::builder("wallet-import-format")
             .blurb("Wallet Import Format")
             .flags(glib::ParamFlags::READWRITE)
@@ -127,6 +134,14 @@ mod implementation {
             .blurb("CMC Top")
             .flags(glib::ParamFlags::READWRITE)
             .build(),
+          glib::ParamSpecString::builder("chain-id")
+            .blurb("Chain ID")
+            .flags(glib::ParamFlags::READWRITE)
+            .build(),
+          glib::ParamSpecString::builder("eip1191")
+            .blurb("EIP-1191")
+            .flags(glib::ParamFlags::READWRITE)
+            .build(),
         ]
       })
     }
@@ -145,12 +160,15 @@ mod implementation {
           self.data.borrow_mut().public_key_hash = value.get().unwrap_or_default()
         }
         "script-hash" => self.data.borrow_mut().script_hash = value.get().unwrap_or_default(),
+        "bech32-hrp" => self.data.borrow_mut().bech32_hrp = value.get().unwrap_or_default(),
         "wallet-import-format" => {
           self.data.borrow_mut().wallet_import_format = value.get().unwrap_or_default()
         }
         "evm" => self.data.borrow_mut().evm = value.get().unwrap_or_default(),
         "ucid" => self.data.borrow_mut().ucid = value.get().unwrap_or_default(),
         "cmc-top" => self.data.borrow_mut().cmc_top = value.get().unwrap_or_default(),
+        "chain-id" => self.data.borrow_mut().chain_id = value.get().unwrap_or_default(),
+        "eip1191" => self.data.borrow_mut().eip1191 = value.get().unwrap_or_default(),
         _ => eprintln!("Unknown property"),
       }
     }
@@ -167,10 +185,13 @@ mod implementation {
         "public-header" => self.data.borrow_mut().public_header.to_value(),
         "public-key-hash" => self.data.borrow_mut().public_key_hash.to_value(),
         "script-hash" => self.data.borrow_mut().script_hash.to_value(),
+        "bech32-hrp" => self.data.borrow_mut().bech32_hrp.to_value(),
         "wallet-import-format" => self.data.borrow_mut().wallet_import_format.to_value(),
         "evm" => self.data.borrow_mut().evm.to_value(),
         "ucid" => self.data.borrow_mut().ucid.to_value(),
         "cmc-top" => self.data.borrow_mut().cmc_top.to_value(),
+        "chain-id" => self.data.borrow_mut().chain_id.to_value(),
+        "eip1191" => self.data.borrow_mut().eip1191.to_value(),
         _ => unimplemented!(),
       }
     }
@@ -194,10 +215,13 @@ impl CoinDatabase {
       .property("public-header", crypto_coin.public_header)
       .property("public-key-hash", crypto_coin.public_key_hash)
       .property("script-hash", crypto_coin.script_hash)
+      .property("bech32-hrp", crypto_coin.bech32_hrp)
       .property("wallet-import-format", crypto_coin.wallet_import_format)
       .property("evm", crypto_coin.evm)
       .property("ucid", crypto_coin.ucid)
-      .property("cmc-top", crypto_coin.cmc_top);
+      .property("cmc-top", crypto_coin.cmc_top)
+      .property("chain-id", crypto_coin.chain_id)
+      .property("eip1191", crypto_coin.eip1191);
 
     builder.build()
   }
@@ -256,10 +280,13 @@ pub fn create_coin_store() -> FunctionOutput<gtk::gio::ListStore> {
     let public_header = record[7].to_string();
     let public_key_hash = record[8].to_string();
     let script_hash = record[9].to_string();
-    let wallet_import_format = record[10].to_string();
-    let evm = record[11].to_string();
-    let ucid = record[12].to_string();
-    let cmc_top = record[13].to_string();
+    let bech32_hrp = record[10].to_string();
+    let wallet_import_format = record[11].to_string();
+    let evm = record[12].to_string();
+    let ucid = record[13].to_string();
+    let cmc_top = record[14].to_string();
+    let chain_id = record[15].to_string();
+    let eip1191 = record[16].to_string();
 
     let crypto_coin = CryptoCoin {
       status,
@@ -272,10 +299,13 @@ pub fn create_coin_store() -> FunctionOutput<gtk::gio::ListStore> {
       public_header,
       public_key_hash,
       script_hash,
+      bech32_hrp,
       wallet_import_format,
       evm,
       ucid,
       cmc_top,
+      chain_id,
+      eip1191,
     };
 
     let coin = CoinDatabase::new(crypto_coin);
@@ -383,6 +413,56 @@ pub fn create_sorter() -> FunctionOutput<gtk::CustomSorter> {
   Ok(gtk::CustomSorter::new(sorter))
 }
 
+pub fn find_coin_by_extended_key_header(
+  header: u32,
+  selected_coin_index: Option<u32>,
+) -> FunctionOutput<Option<(CoinDatabase, u32, u32, Option<u32>)>> {
+  d3bug(">>> find_coin_by_extended_key_header", "debug");
+  d3bug(&format!("header {header:#010x}"), "debug");
+
+  let parse_header =
+    |value: String| u32::from_str_radix(value.trim().trim_start_matches("0x"), 16).ok();
+
+  let coins = create_coin_database()?;
+  let slip132_header = crate::keys::get_slip132_extended_key_header(header);
+  let bip = slip132_header.and_then(|(_, _, _, bip)| bip);
+
+  let mut matching_coins: Vec<(CoinDatabase, u32, u32)> = coins
+    .iter()
+    .filter_map(|coin| {
+      let private_header = parse_header(coin.property::<String>("private-header"))?;
+      let public_header = parse_header(coin.property::<String>("public-header"))?;
+
+      (private_header == header || public_header == header)
+        .then(|| (coin.clone(), private_header, public_header))
+    })
+    .collect();
+
+  // Many coins share the xprv/xpub version bytes, so the selected coin wins over table order
+  if let Some(position) = matching_coins
+    .iter()
+    .position(|(coin, _, _)| Some(coin.property::<u32>("coin-index")) == selected_coin_index)
+  {
+    let (coin, private_header, public_header) = matching_coins.swap_remove(position);
+    return Ok(Some((coin, private_header, public_header, bip)));
+  }
+
+  if let Some((private_header, public_header, coin_index, bip)) = slip132_header
+    && let Some(coin) = coins
+      .into_iter()
+      .find(|coin| coin.property::<u32>("coin-index") == coin_index)
+  {
+    return Ok(Some((coin, private_header, public_header, bip)));
+  }
+
+  Ok(
+    matching_coins
+      .into_iter()
+      .next()
+      .map(|(coin, private_header, public_header)| (coin, private_header, public_header, bip)),
+  )
+}
+
 fn create_coin_database() -> FunctionOutput<Vec<CoinDatabase>> {
   let resource_path = std::path::Path::new("coin").join(COINLIST_FILE);
   let resource_path_str = resource_path.to_str().unwrap_or_default();
@@ -412,10 +492,13 @@ fn create_coin_database() -> FunctionOutput<Vec<CoinDatabase>> {
       let public_header: String = record.get(7).unwrap_or_default().to_string();
       let public_key_hash: String = record.get(8).unwrap_or_default().to_string();
       let script_hash: String = record.get(9).unwrap_or_default().to_string();
-      let wallet_import_format: String = record.get(10).unwrap_or_default().to_string();
-      let evm: String = record.get(11).unwrap_or_default().to_string();
-      let ucid: String = record.get(12).unwrap_or_default().to_string();
-      let cmc_top: String = record.get(13).unwrap_or_default().to_string();
+      let bech32_hrp: String = record.get(10).unwrap_or_default().to_string();
+      let wallet_import_format: String = record.get(11).unwrap_or_default().to_string();
+      let evm: String = record.get(12).unwrap_or_default().to_string();
+      let ucid: String = record.get(13).unwrap_or_default().to_string();
+      let cmc_top: String = record.get(14).unwrap_or_default().to_string();
+      let chain_id: String = record.get(15).unwrap_or_default().to_string();
+      let eip1191: String = record.get(16).unwrap_or_default().to_string();
 
       let crypto_coin = CryptoCoin {
         status,
@@ -428,10 +511,13 @@ fn create_coin_database() -> FunctionOutput<Vec<CoinDatabase>> {
         public_header,
         public_key_hash,
         script_hash,
+        bech32_hrp,
         wallet_import_format,
         evm,
         ucid,
         cmc_top,
+        chain_id,
+        eip1191,
       };
 
       CoinDatabase::new(crypto_coin)
@@ -7,25 +7,49 @@ use crate::{AppError, FunctionOutput, d3bug};
 use adw::prelude::*;
 use gtk4 as gtk;
 use libadwaita as adw;
-use num_bigint::BigUint;
+use qr2m_lib::{DerivationPath, SecretBytes, SecretString};
 use rand::Rng;
+use rayon::prelude::*;
 use sha2::{Digest, Sha256};
 use sha3::Keccak256;
 use std::{fs::File, io::Read};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
 
 pub type DerivationResult = Option<([u8; 32], [u8; 32], Vec<u8>)>;
 pub type AddressResult = Option<Address>;
 
+lazy_static::lazy_static! {
+    static ref SECP256K1_CONTEXT: secp256k1::Secp256k1<secp256k1::All> = {
+        let mut secp = secp256k1::Secp256k1::new();
+        secp.seeded_randomize(&rand::rng().random::<[u8; 32]>());
+        secp
+    };
+}
+
+pub fn secp256k1_context() -> &'static secp256k1::Secp256k1<secp256k1::All> {
+  &SECP256K1_CONTEXT
+}
+
 #[derive(Debug)]
 pub struct AddressHocusPokus {
   pub coin_index: u32,
   pub derivation_path: String,
-  pub master_private_key_bytes: Vec<u8>,
-  pub master_chain_code_bytes: Vec<u8>,
+  pub master_private_key_bytes: SecretBytes,
+  pub master_chain_code_bytes: SecretBytes,
+  pub master_public_key_bytes: Vec<u8>,
+  pub master_depth: u8,
+  pub master_child_number: u32,
   pub public_key_hash: String,
+  pub script_hash: String,
+  pub bech32_hrp: String,
   pub key_derivation: String,
   pub wallet_import_format: String,
   pub hash: String,
+  pub chain_id: Option<u64>,
+  pub eip1191: bool,
+  pub bip: u32,
+  pub compressed: bool,
   // pub seed: String,
 }
 
@@ -33,7 +57,9 @@ pub struct AddressHocusPokus {
 pub struct Address {
   pub address: String,
   pub public_key: String,
-  pub private_key: String,
+  pub private_key: SecretString,
+  pub private_view_key: Option<SecretString>,
+  pub mnemonic: Option<SecretString>,
 }
 
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
@@ -41,58 +67,341 @@ pub struct Address {
 #[derive(Debug)]
 pub enum CryptoPublicKey {
   Secp256k1(secp256k1::PublicKey),
-  #[cfg(feature = "dev")]
   Ed25519(ed25519_dalek::VerifyingKey),
 }
 
+#[derive(Clone)]
+pub struct ExtendedKey {
+  pub depth: u8,
+  pub parent_fingerprint: [u8; 4],
+  pub child_number: u32,
+  pub chain_code: [u8; 32],
+  pub private_key: Option<[u8; 32]>,
+  pub public_key: [u8; 33],
+}
+
+impl Drop for ExtendedKey {
+  fn drop(&mut self) {
+    self.private_key.zeroize();
+    self.chain_code.zeroize();
+  }
+}
+
+impl std::fmt::Debug for ExtendedKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ExtendedKey")
+      .field("depth", &self.depth)
+      .field("parent_fingerprint", &hex::encode(self.parent_fingerprint))
+      .field("child_number", &self.child_number)
+      .field("chain_code", &"[REDACTED]")
+      .field("private_key", &self.private_key.map(|_| "[REDACTED]"))
+      .field("public_key", &hex::encode(self.public_key))
+      .finish()
+  }
+}
+
+impl ExtendedKey {
+  pub fn new_master(private_key: &[u8], chain_code: &[u8]) -> FunctionOutput<Self> {
+    let private_key: [u8; 32] = private_key
+      .try_into()
+      .map_err(|_| AppError::Custom("master private key must be 32 bytes".into()))?;
+    let chain_code: [u8; 32] = chain_code
+      .try_into()
+      .map_err(|_| AppError::Custom("master chain code must be 32 bytes".into()))?;
+
+    let secp = secp256k1_context();
+    let secret_key = secp256k1::SecretKey::from_byte_array(private_key)
+      .map_err(|err| AppError::Custom(format!("Invalid master SecretKey: {err}")))?;
+    let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key).serialize();
+
+    Ok(Self {
+      depth: 0,
+      parent_fingerprint: [0u8; 4],
+      child_number: 0,
+      chain_code,
+      private_key: Some(private_key),
+      public_key,
+    })
+  }
+
+  pub fn from_parts(
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: &[u8],
+    private_key: Option<&[u8]>,
+    public_key: &[u8],
+  ) -> FunctionOutput<Self> {
+    let chain_code: [u8; 32] = chain_code
+      .try_into()
+      .map_err(|_| AppError::Custom("chain code must be 32 bytes".into()))?;
+    let public_key: [u8; 33] = public_key
+      .try_into()
+      .map_err(|_| AppError::Custom("public key must be 33 bytes".into()))?;
+    let private_key = match private_key {
+      Some(private_key) => Some(
+        <[u8; 32]>::try_from(private_key)
+          .map_err(|_| AppError::Custom("private key must be 32 bytes".into()))?,
+      ),
+      None => None,
+    };
+
+    Ok(Self {
+      depth,
+      parent_fingerprint,
+      child_number,
+      chain_code,
+      private_key,
+      public_key,
+    })
+  }
+
+  pub fn fingerprint(&self) -> [u8; 4] {
+    let hash = qr2m_lib::calculate_sha256_and_ripemd160_hash(&self.public_key);
+    let mut fingerprint = [0u8; 4];
+    fingerprint.copy_from_slice(&hash[..4]);
+    fingerprint
+  }
+
+  pub fn neuter(&self) -> Self {
+    Self {
+      private_key: None,
+      ..self.clone()
+    }
+  }
+
+  pub fn derive_child(&self, child: qr2m_lib::ChildNumber) -> FunctionOutput<Self> {
+    d3bug(">>> ExtendedKey::derive_child", "debug");
+
+    if self.depth == u8::MAX {
+      return Err(AppError::Custom(
+        "Maximum BIP32 derivation depth reached".to_string(),
+      ));
+    }
+
+    let (private_key, chain_code, public_key) = match self.private_key {
+      Some(private_key) => {
+        let derived = derive_child_key_secp256k1(&private_key, &self.chain_code, child)?
+          .ok_or_else(|| {
+            AppError::Custom("Problem with derivation result: value is None".into())
+          })?;
+
+        let mut public_key = [0u8; 33];
+        public_key.copy_from_slice(&derived.2);
+
+        (Some(derived.0), derived.1, public_key)
+      }
+      None => {
+        if child.is_hardened() {
+          return Err(AppError::Custom(
+            "Hardened child can not be derived from a public key".to_string(),
+          ));
+        }
+
+        let (chain_code, public_key) =
+          derive_public_child_key_secp256k1(&self.public_key, &self.chain_code, child.index())?;
+
+        (None, chain_code, public_key)
+      }
+    };
+
+    Ok(Self {
+      depth: self.depth + 1,
+      parent_fingerprint: self.fingerprint(),
+      child_number: child.to_raw(),
+      chain_code,
+      private_key,
+      public_key,
+    })
+  }
+
+  pub fn serialize_private(&self, header: u32) -> FunctionOutput<String> {
+    let private_key = self
+      .private_key
+      .ok_or_else(|| AppError::Custom("Extended key does not contain a private key".to_string()))?;
+
+    let mut key_data = [0u8; 33];
+    key_data[1..].copy_from_slice(&private_key);
+
+    Ok(self.serialize(header, &key_data))
+  }
+
+  pub fn serialize_public(&self, header: u32) -> String {
+    self.serialize(header, &self.public_key)
+  }
+
+  fn serialize(&self, header: u32, key_data: &[u8; 33]) -> String {
+    let mut extended_key = Vec::with_capacity(82);
+
+    extended_key.extend_from_slice(&header.to_be_bytes());
+    extended_key.push(self.depth);
+    extended_key.extend_from_slice(&self.parent_fingerprint);
+    extended_key.extend_from_slice(&self.child_number.to_be_bytes());
+    extended_key.extend_from_slice(&self.chain_code);
+    extended_key.extend_from_slice(key_data);
+
+    let checksum: [u8; 4] = qr2m_lib::calculate_checksum_for_master_keys(&extended_key);
+    extended_key.extend_from_slice(&checksum);
+
+    bs58::encode(extended_key).into_string()
+  }
+}
+
+pub fn decode_extended_key(encoded: &str) -> FunctionOutput<(u32, ExtendedKey)> {
+  d3bug(">>> decode_extended_key", "debug");
+
+  let decoded = bs58::decode(encoded.trim())
+    .into_vec()
+    .map_err(|err| AppError::Custom(format!("Invalid Base58 extended key: {err}")))?;
+
+  if decoded.len() != 82 {
+    return Err(AppError::Custom(format!(
+      "Invalid extended key length {:?}",
+      decoded.len()
+    )));
+  }
+
+  let (payload, checksum) = decoded.split_at(78);
+  if qr2m_lib::calculate_checksum_for_master_keys(payload) != checksum {
+    return Err(AppError::Custom(
+      "Invalid extended key checksum".to_string(),
+    ));
+  }
+
+  let header = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+  let depth = payload[4];
+
+  let mut parent_fingerprint = [0u8; 4];
+  parent_fingerprint.copy_from_slice(&payload[5..9]);
+
+  let child_number = u32::from_be_bytes([payload[9], payload[10], payload[11], payload[12]]);
+
+  let mut chain_code = [0u8; 32];
+  chain_code.copy_from_slice(&payload[13..45]);
+
+  let mut key_data = [0u8; 33];
+  key_data.copy_from_slice(&payload[45..78]);
+
+  let (private_key, public_key) = match key_data[0] {
+    0x00 => {
+      let mut private_key = [0u8; 32];
+      private_key.copy_from_slice(&key_data[1..]);
+
+      let secp = secp256k1_context();
+      let secret_key = secp256k1::SecretKey::from_byte_array(private_key)
+        .map_err(|err| AppError::Custom(format!("Invalid extended private key: {err}")))?;
+      let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key).serialize();
+
+      (Some(private_key), public_key)
+    }
+    0x02 | 0x03 => {
+      secp256k1::PublicKey::from_byte_array_compressed(key_data)
+        .map_err(|err| AppError::Custom(format!("Invalid extended public key: {err}")))?;
+
+      (None, key_data)
+    }
+    prefix => {
+      return Err(AppError::Custom(format!(
+        "Invalid extended key data prefix {prefix:#04x}"
+      )));
+    }
+  };
+
+  if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+    return Err(AppError::Custom(
+      "Master extended key with non-zero parent fingerprint or child number".to_string(),
+    ));
+  }
+
+  Ok((
+    header,
+    ExtendedKey {
+      depth,
+      parent_fingerprint,
+      child_number,
+      chain_code,
+      private_key,
+      public_key,
+    },
+  ))
+}
+
+pub fn parse_extended_key_header(header: Option<&str>, default: &str) -> FunctionOutput<u32> {
+  let header = header.filter(|s| !s.is_empty()).unwrap_or(default);
+
+  u32::from_str_radix(header.trim_start_matches("0x"), 16)
+    .map_err(|err| AppError::Custom(format!("Invalid extended key header {header:?}: {err}")))
+}
+
+// SLIP-132 version bytes: (private header, public header, coin index, BIP purpose)
+const SLIP132_EXTENDED_KEY_HEADERS: [(u32, u32, u32, Option<u32>); 6] = [
+  (0x0488ADE4, 0x0488B21E, 0, None),
+  (0x049D7878, 0x049D7CB2, 0, Some(49)),
+  (0x04B2430C, 0x04B24746, 0, Some(84)),
+  (0x04358394, 0x043587CF, 1, None),
+  (0x044A4E28, 0x044A5262, 1, Some(49)),
+  (0x045F18BC, 0x045F1CF6, 1, Some(84)),
+];
+
+pub fn get_slip132_extended_key_header(header: u32) -> Option<(u32, u32, u32, Option<u32>)> {
+  SLIP132_EXTENDED_KEY_HEADERS
+    .into_iter()
+    .find(|(private_header, public_header, _, _)| {
+      *private_header == header || *public_header == header
+    })
+}
+
 pub fn derive_child_key_secp256k1(
   parent_key: &[u8],
   parent_chain_code: &[u8],
-  index: u32,
-  hardened: bool,
+  child: qr2m_lib::ChildNumber,
 ) -> FunctionOutput<DerivationResult> {
   d3bug(">>> derive_child_key_secp256k1", "debug");
-  d3bug(&format!("parent_key {parent_key:?}"), "debug");
-  d3bug(&format!("parent_chain_code {parent_chain_code:?}"), "debug");
-  d3bug(&format!("index {index:?}"), "debug");
-  d3bug(&format!("hardened {hardened:?}"), "debug");
+  d3bug(&format!("child {child}"), "debug");
 
-  if index & 0x80000000 != 0 && !hardened {
-    return Err(AppError::Custom(format!("Problem with index {index:?}")));
-  }
+  let array: [u8; 32] = parent_key
+    .try_into()
+    .map_err(|_| AppError::Custom("parent_key must be 32 bytes".into()))?;
 
-  let secp = secp256k1::Secp256k1::new();
-  let mut data = Vec::with_capacity(37);
+  let parent_secret_key = secp256k1::SecretKey::from_byte_array(array)
+    .map_err(|err| AppError::Custom(format!("Invalid SecretKey: {err}")))?;
 
-  if hardened {
-    data.push(0x00);
-    data.extend_from_slice(parent_key);
-  } else {
-    let array: [u8; 32] = parent_key
-      .try_into()
-      .map_err(|_| AppError::Custom("parent_key must be 32 bytes".into()))?;
+  let (child_secret_key, child_chain_code_bytes, child_pubkey) =
+    derive_private_child_secp256k1(&parent_secret_key, None, parent_chain_code, child)?;
 
-    let parent_secret_key = secp256k1::SecretKey::from_byte_array(array)
-      .map_err(|err| AppError::Custom(format!("Invalid SecretKey: {err}")))?;
+  Ok(Some((
+    child_secret_key.secret_bytes(),
+    child_chain_code_bytes,
+    child_pubkey.serialize().to_vec(),
+  )))
+}
 
-    let parent_pubkey = secp256k1::PublicKey::from_secret_key(&secp, &parent_secret_key);
-    data.extend_from_slice(&parent_pubkey.serialize()[..]);
-  }
+fn derive_private_child_secp256k1(
+  parent_secret_key: &secp256k1::SecretKey,
+  parent_public_key: Option<&secp256k1::PublicKey>,
+  parent_chain_code: &[u8],
+  child: qr2m_lib::ChildNumber,
+) -> FunctionOutput<(secp256k1::SecretKey, [u8; 32], secp256k1::PublicKey)> {
+  let secp = secp256k1_context();
+  let mut data = [0u8; 37];
 
-  let index_bytes = if hardened {
-    let index = index + crate::WALLET_MAX_ADDRESSES + 1;
-    index.to_be_bytes()
+  if child.is_hardened() {
+    data[1..33].copy_from_slice(&parent_secret_key.secret_bytes());
   } else {
-    index.to_be_bytes()
-  };
-
-  data.extend_from_slice(&index_bytes);
+    // Callers walking a path already hold the parent public key, so only compute it when missing
+    let parent_pubkey = match parent_public_key {
+      Some(public_key) => *public_key,
+      None => secp256k1::PublicKey::from_secret_key(secp, parent_secret_key),
+    };
+    data[..33].copy_from_slice(&parent_pubkey.serialize());
+  }
 
-  d3bug(&format!("data_for_hmac_sha512 {data:?}"), "debug");
+  data[33..].copy_from_slice(&child.to_raw().to_be_bytes());
 
   let result = qr2m_lib::calculate_hmac_sha512_hash(parent_chain_code, &data);
+  data.zeroize();
 
-  let child_private_key_bytes: [u8; 32] = result[..32]
+  let mut child_private_key_bytes: [u8; 32] = result[..32]
     .try_into()
     .map_err(|_| AppError::Custom("Slice with incorrect length for private key".to_string()))?;
 
@@ -100,46 +409,103 @@ pub fn derive_child_key_secp256k1(
     .try_into()
     .map_err(|_| AppError::Custom("Slice with incorrect length for chain code".to_string()))?;
 
-  let child_key_int = BigUint::from_bytes_be(&child_private_key_bytes);
-  let parent_key_int = BigUint::from_bytes_be(parent_key);
-  let curve_order = BigUint::from_bytes_be(&secp256k1::constants::CURVE_ORDER);
-  let combined_int = (parent_key_int + child_key_int) % &curve_order;
-  let combined_bytes = combined_int.to_bytes_be();
-  let combined_bytes_padded = {
-    let mut padded = [0u8; 32];
-    let offset = 32 - combined_bytes.len();
-    padded[offset..].copy_from_slice(&combined_bytes);
-    padded
-  };
-  // let array: [u8; 32] = combined_bytes_padded
-  //   .try_into()
-  //   .map_err(|_| AppError::Custom("combined_bytes_padded must be 32 bytes".into()))?;
+  let tweak = secp256k1::Scalar::from_be_bytes(child_private_key_bytes)
+    .map_err(|err| AppError::Custom(format!("Invalid child tweak: {err}")))?;
 
-  let child_secret_key = secp256k1::SecretKey::from_byte_array(combined_bytes_padded)
+  let child_secret_key = parent_secret_key
+    .add_tweak(&tweak)
     .map_err(|err| AppError::Custom(format!("Invalid child_secret_key: {err}")))?;
 
-  let child_secret_key_bytes = child_secret_key.secret_bytes();
-  let child_pubkey = secp256k1::PublicKey::from_secret_key(&secp, &child_secret_key);
-  let child_public_key_bytes = child_pubkey.serialize().to_vec();
+  let child_pubkey = secp256k1::PublicKey::from_secret_key(secp, &child_secret_key);
+
+  d3bug(&format!("child_public_key_bytes {child_pubkey:?}"), "debug");
+
+  child_private_key_bytes.zeroize();
+
+  Ok((child_secret_key, child_chain_code_bytes, child_pubkey))
+}
+
+pub fn derive_public_child_key_secp256k1(
+  parent_public_key: &[u8],
+  parent_chain_code: &[u8],
+  index: u32,
+) -> FunctionOutput<([u8; 32], [u8; 33])> {
+  d3bug(">>> derive_public_child_key_secp256k1", "debug");
+  d3bug(&format!("parent_public_key {parent_public_key:?}"), "debug");
+  d3bug(&format!("index {index:?}"), "debug");
+
+  if index & 0x80000000 != 0 {
+    return Err(AppError::Custom(format!(
+      "Hardened index {index:?} can not be derived from a public key"
+    )));
+  }
+
+  let parent_public_key: [u8; 33] = parent_public_key
+    .try_into()
+    .map_err(|_| AppError::Custom("parent_public_key must be 33 bytes".into()))?;
+
+  let parent_pubkey = secp256k1::PublicKey::from_byte_array_compressed(parent_public_key)
+    .map_err(|err| AppError::Custom(format!("Invalid PublicKey: {err}")))?;
+
+  let mut data = Vec::with_capacity(37);
+  data.extend_from_slice(&parent_public_key);
+  data.extend_from_slice(&index.to_be_bytes());
+
+  let result = qr2m_lib::calculate_hmac_sha512_hash(parent_chain_code, &data);
+
+  let tweak_bytes: [u8; 32] = result[..32]
+    .try_into()
+    .map_err(|_| AppError::Custom("Slice with incorrect length for tweak".to_string()))?;
+
+  let child_chain_code_bytes: [u8; 32] = result[32..]
+    .try_into()
+    .map_err(|_| AppError::Custom("Slice with incorrect length for chain code".to_string()))?;
+
+  let tweak = secp256k1::Scalar::from_be_bytes(tweak_bytes)
+    .map_err(|err| AppError::Custom(format!("Invalid child tweak: {err}")))?;
+
+  let secp = secp256k1_context();
+  let child_pubkey = parent_pubkey
+    .add_exp_tweak(secp, &tweak)
+    .map_err(|err| AppError::Custom(format!("Invalid child public key: {err}")))?;
+
+  let child_public_key_bytes = child_pubkey.serialize();
 
-  d3bug(
-    &format!("child_private_key_bytes {child_private_key_bytes:?}"),
-    "debug",
-  );
-  d3bug(
-    &format!("child_chain_code_bytes {child_chain_code_bytes:?}"),
-    "debug",
-  );
   d3bug(
     &format!("child_public_key_bytes {child_public_key_bytes:?}"),
     "debug",
   );
 
-  Ok(Some((
-    child_secret_key_bytes,
-    child_chain_code_bytes,
-    child_public_key_bytes,
-  )))
+  Ok((child_chain_code_bytes, child_public_key_bytes))
+}
+
+pub fn derive_public_from_path_secp256k1(
+  master_public_key: &[u8],
+  master_chain_code: &[u8],
+  path: &DerivationPath,
+) -> FunctionOutput<([u8; 32], [u8; 33])> {
+  d3bug(">>> derive_public_from_path_secp256k1", "debug");
+  d3bug(&format!("path {path}"), "debug");
+
+  let mut public_key: [u8; 33] = master_public_key
+    .try_into()
+    .map_err(|_| AppError::Custom("master_public_key must be 33 bytes".into()))?;
+  let mut chain_code: [u8; 32] = master_chain_code
+    .try_into()
+    .map_err(|_| AppError::Custom("master_chain_code must be 32 bytes".into()))?;
+
+  for child in path.children() {
+    if child.is_hardened() {
+      return Err(AppError::Custom(format!(
+        "Hardened path part {child} can not be derived from a public key"
+      )));
+    }
+
+    (chain_code, public_key) =
+      derive_public_child_key_secp256k1(&public_key, &chain_code, child.index())?;
+  }
+
+  Ok((chain_code, public_key))
 }
 
 pub fn create_private_key_for_address(
@@ -201,12 +567,8 @@ pub fn create_private_key_for_address(
         Err(AppError::Custom("Private key must be provided".to_string()))
       }
     }
-    "sha256+ripemd160" => match private_key {
-      Some(key) => {
-        let private_key_hex = hex::encode(key.secret_bytes());
-        d3bug(&format!("private_key_hex {private_key_hex:?}"), "debug");
-        Ok(private_key_hex)
-      }
+    "sha256+ripemd160" | "blake2b" => match private_key {
+      Some(key) => Ok(hex::encode(key.secret_bytes())),
       None => Err(AppError::Custom("Private key must be provided".to_string())),
     },
     _ => Err(AppError::Custom(format!("Unsupported hash method: {hash}"))),
@@ -216,86 +578,851 @@ pub fn create_private_key_for_address(
 pub fn derive_from_path_secp256k1(
   master_key: &[u8],
   master_chain_code: &[u8],
-  path: &str,
+  master_public_key: &[u8],
+  path: &DerivationPath,
 ) -> FunctionOutput<DerivationResult> {
   d3bug(">>> derive_from_path_secp256k1", "debug");
-  d3bug(&format!("master_key {master_key:?}"), "debug");
-  d3bug(&format!("master_chain_code {master_chain_code:?}"), "debug");
-  d3bug(&format!("path {path:?}"), "debug");
+  d3bug(&format!("path {path}"), "debug");
+
+  let array: [u8; 32] = master_key
+    .try_into()
+    .map_err(|_| AppError::Custom("master_key must be 32 bytes".into()))?;
+
+  let mut secret_key = secp256k1::SecretKey::from_byte_array(array)
+    .map_err(|err| AppError::Custom(format!("Invalid secret_key: {err}")))?;
+
+  let mut chain_code: [u8; 32] = master_chain_code.try_into().map_err(|_| {
+    AppError::Custom(format!(
+      "Invalid chain code length {:?}",
+      master_chain_code.len()
+    ))
+  })?;
+
+  let mut public_key = secp256k1::PublicKey::from_secret_key(secp256k1_context(), &secret_key);
+
+  if !master_public_key.is_empty() && master_public_key != public_key.serialize() {
+    return Err(AppError::Custom(
+      "Master public key does not match master private key".to_string(),
+    ));
+  }
+
+  for child in path.children() {
+    d3bug(&format!("child {child}"), "debug");
+
+    let derived =
+      derive_private_child_secp256k1(&secret_key, Some(&public_key), &chain_code, *child)
+        .map_err(|err| AppError::Custom(format!("Problem with deriving child keys: {err:?}")))?;
+
+    secret_key = derived.0;
+    chain_code = derived.1;
+    public_key = derived.2;
+  }
+
+  Ok(Some((
+    secret_key.secret_bytes(),
+    chain_code,
+    public_key.serialize().to_vec(),
+  )))
+}
+
+pub fn derive_extended_key_from_path(
+  root: &ExtendedKey,
+  path: &DerivationPath,
+) -> FunctionOutput<ExtendedKey> {
+  d3bug(">>> derive_extended_key_from_path", "debug");
+  d3bug(&format!("path {path}"), "debug");
+
+  let mut node = root.clone();
+
+  let relative_path = path
+    .relative_to(root.depth, root.child_number)
+    .map_err(AppError::Custom)?;
+
+  for child in relative_path.children() {
+    node = node.derive_child(*child)?;
+  }
+
+  Ok(node)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+// DERIVATION NODE
+
+#[derive(Debug, Clone)]
+pub struct DerivationNode {
+  pub private_key: SecretBytes,
+  pub chain_code: SecretBytes,
+  pub public_key: Vec<u8>,
+  pub depth: u8,
+  pub child_number: u32,
+}
+
+impl DerivationNode {
+  pub fn new(
+    private_key: impl Into<SecretBytes>,
+    chain_code: impl Into<SecretBytes>,
+    public_key: Vec<u8>,
+    depth: u8,
+    child_number: u32,
+  ) -> Self {
+    Self {
+      private_key: private_key.into(),
+      chain_code: chain_code.into(),
+      public_key,
+      depth,
+      child_number,
+    }
+  }
+
+  // Batch generation derives m/purpose'/coin'/account'/change once through this node,
+  // after which every address only has to compute its own last child
+  pub fn derive(&self, key_derivation: &str, path: &DerivationPath) -> FunctionOutput<Self> {
+    d3bug(">>> DerivationNode::derive", "debug");
+    d3bug(&format!("path {path}"), "debug");
+
+    let relative_path = path
+      .relative_to(self.depth, self.child_number)
+      .map_err(AppError::Custom)?;
+    let depth = self
+      .depth
+      .checked_add(relative_path.depth() as u8)
+      .ok_or_else(|| AppError::Custom(format!("Derivation path {path} is too deep")))?;
+    let child_number = relative_path
+      .children()
+      .last()
+      .map_or(self.child_number, |child| child.to_raw());
+
+    let (private_key, chain_code, public_key) = match key_derivation {
+      "secp256k1" | "secp256k1-monero" if self.private_key.is_empty() => {
+        let (chain_code, public_key) =
+          derive_public_from_path_secp256k1(&self.public_key, &self.chain_code, &relative_path)?;
+
+        (
+          SecretBytes::default(),
+          SecretBytes::from(chain_code),
+          public_key.to_vec(),
+        )
+      }
+      "secp256k1" | "secp256k1-monero" => derive_from_path_secp256k1(
+        &self.private_key,
+        &self.chain_code,
+        &self.public_key,
+        &relative_path,
+      )?
+      .map(|(private_key, chain_code, public_key)| {
+        (
+          SecretBytes::from(private_key),
+          SecretBytes::from(chain_code),
+          public_key,
+        )
+      })
+      .ok_or_else(|| AppError::Custom(format!("Can not derive node at {path}")))?,
+      "ed25519" | "ed25519-blake2b" => {
+        derive_from_path_ed25519(&self.private_key, &self.chain_code, &relative_path)?
+          .map(|(private_key, chain_code, public_key)| {
+            (
+              SecretBytes::from(private_key),
+              SecretBytes::from(chain_code),
+              public_key,
+            )
+          })
+          .ok_or_else(|| AppError::Custom(format!("Can not derive node at {path}")))?
+      }
+      // Icarus payment and stake keys only share the account prefix, so those stay on the root
+      _ => return Ok(self.clone()),
+    };
+
+    Ok(Self {
+      private_key,
+      chain_code,
+      public_key,
+      depth,
+      child_number,
+    })
+  }
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+// SLIP-10 ED25519
+
+pub fn derive_master_key_ed25519(
+  seed_bytes: &[u8],
+) -> FunctionOutput<([u8; 32], [u8; 32], Vec<u8>)> {
+  d3bug(">>> derive_master_key_ed25519", "debug");
+
+  let hmac_result = qr2m_lib::calculate_hmac_sha512_hash(b"ed25519 seed", seed_bytes);
+
+  if hmac_result.len() != 64 {
+    return Err(AppError::Custom(
+      "calculate_hmac_sha512_hash len is not 64".to_string(),
+    ));
+  }
+
+  let mut private_key = [0u8; 32];
+  let mut chain_code = [0u8; 32];
+  private_key.copy_from_slice(&hmac_result[..32]);
+  chain_code.copy_from_slice(&hmac_result[32..]);
+
+  let public_key = ed25519_dalek::SigningKey::from_bytes(&private_key)
+    .verifying_key()
+    .to_bytes()
+    .to_vec();
 
-  let mut private_key = master_key.to_vec();
-  let mut chain_code = master_chain_code.to_vec();
-  let mut public_key = Vec::new();
+  Ok((private_key, chain_code, public_key))
+}
+
+pub fn derive_child_key_ed25519(
+  parent_key: &[u8],
+  parent_chain_code: &[u8],
+  child: qr2m_lib::ChildNumber,
+) -> FunctionOutput<DerivationResult> {
+  d3bug(">>> derive_child_key_ed25519", "debug");
+  d3bug(&format!("child {child}"), "debug");
+
+  if parent_key.len() != 32 || parent_chain_code.len() != 32 {
+    return Err(AppError::Custom(
+      "Invalid parent key or parent chain code length".to_string(),
+    ));
+  }
+
+  if !child.is_hardened() {
+    return Err(AppError::Custom(
+      "SLIP-10 Ed25519 supports only hardened derivation".to_string(),
+    ));
+  }
+
+  let mut data = Vec::with_capacity(37);
+  data.push(0u8);
+  data.extend_from_slice(parent_key);
+  data.extend_from_slice(&child.to_raw().to_be_bytes());
+
+  let hmac_result = qr2m_lib::calculate_hmac_sha512_hash(parent_chain_code, &data);
+  data.zeroize();
+
+  if hmac_result.len() != 64 {
+    return Err(AppError::Custom(
+      "calculate_hmac_sha512_hash len is not 64".to_string(),
+    ));
+  }
+
+  let mut child_private_key_bytes = [0u8; 32];
+  let mut child_chain_code_bytes = [0u8; 32];
+  child_private_key_bytes.copy_from_slice(&hmac_result[..32]);
+  child_chain_code_bytes.copy_from_slice(&hmac_result[32..]);
+
+  let child_public_key = ed25519_dalek::SigningKey::from_bytes(&child_private_key_bytes)
+    .verifying_key()
+    .to_bytes()
+    .to_vec();
+
+  Ok(Some((
+    child_private_key_bytes,
+    child_chain_code_bytes,
+    child_public_key,
+  )))
+}
+
+pub fn derive_from_path_ed25519(
+  master_key: &[u8],
+  master_chain_code: &[u8],
+  path: &DerivationPath,
+) -> FunctionOutput<DerivationResult> {
+  d3bug(">>> derive_from_path_ed25519", "debug");
+  d3bug(&format!("path {path}"), "debug");
+
+  // SLIP-10 defines only hardened Ed25519 children, unhardened input is an error, not a typo to fix
+  if let Some(child) = path.children().iter().find(|child| !child.is_hardened()) {
+    return Err(AppError::Custom(format!(
+      "SLIP-10 Ed25519 supports only hardened derivation, segment {child} of {path} is not hardened"
+    )));
+  }
+
+  let mut private_key: [u8; 32] = master_key.try_into().map_err(|_| {
+    AppError::Custom(format!(
+      "Master key must be 32 bytes, got {}",
+      master_key.len()
+    ))
+  })?;
+  let mut chain_code: [u8; 32] = master_chain_code.try_into().map_err(|_| {
+    AppError::Custom(format!(
+      "Master chain code must be 32 bytes, got {}",
+      master_chain_code.len()
+    ))
+  })?;
+  let mut public_key = ed25519_dalek::SigningKey::from_bytes(&private_key)
+    .verifying_key()
+    .to_bytes()
+    .to_vec();
+
+  for child in path.children() {
+    let derived = derive_child_key_ed25519(&private_key, &chain_code, *child)?
+      .ok_or_else(|| AppError::Custom(format!("Failed to derive child key for index: {child}")))?;
+
+    private_key = derived.0;
+    chain_code = derived.1;
+    public_key = derived.2;
+  }
+
+  Ok(Some((private_key, chain_code, public_key)))
+}
 
-  for part in path.split('/') {
-    if part == "m" {
-      continue;
+pub fn generate_master_keys_ed25519(seed: &str) -> FunctionOutput<(String, String)> {
+  #[cfg(debug_assertions)]
+  println!(
+    "[+] {}",
+    &t!("log.generate_master_keys_ed25519").to_string()
+  );
+
+  let seed_bytes = hex::decode(seed)
+    .map_err(|err| AppError::Custom(format!("{}: {err}", t!("error.seed.decode"))))?;
+  let (private_key, chain_code, public_key) = derive_master_key_ed25519(&seed_bytes)?;
+
+  let master_private_key_encoded = bs58::encode(&private_key).into_string();
+  let master_public_key_encoded = bs58::encode(&public_key).into_string();
+
+  #[cfg(debug_assertions)]
+  {
+    println!(" - Master private key: {master_private_key_encoded:?}");
+    println!(" - Master public key: {master_public_key_encoded:?}");
+    println!(" - Master chain code: {chain_code:?}");
+  }
+
+  let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
+  wallet_settings.master_private_key = Some(master_private_key_encoded.clone().into());
+  wallet_settings.master_public_key = Some(master_public_key_encoded.clone());
+  wallet_settings.master_private_key_bytes = Some(private_key.into());
+  wallet_settings.master_chain_code_bytes = Some(chain_code.into());
+  wallet_settings.master_public_key_bytes = Some(public_key);
+  wallet_settings.master_depth = Some(0);
+  wallet_settings.master_parent_fingerprint = Some([0u8; 4]);
+  wallet_settings.master_child_number = Some(0);
+
+  Ok((master_private_key_encoded, master_public_key_encoded))
+}
+
+pub fn generate_ed25519_address(public_key: &CryptoPublicKey) -> FunctionOutput<String> {
+  let public_key_bytes = match public_key {
+    CryptoPublicKey::Ed25519(key) => key.to_bytes(),
+    _ => {
+      return Err(AppError::Custom(
+        "Ed25519 address requires an ed25519 public key".to_string(),
+      ));
+    }
+  };
+
+  Ok(bs58::encode(public_key_bytes).into_string())
+}
+
+pub fn derive_public_key_ed25519_blake2b(private_key: &[u8; 32]) -> [u8; 32] {
+  let hash = qr2m_lib::calculate_blake2b_hash(private_key, 64);
+  let mut scalar_bytes = [0u8; 32];
+  scalar_bytes.copy_from_slice(&hash[..32]);
+
+  curve25519_dalek::EdwardsPoint::mul_base_clamped(scalar_bytes)
+    .compress()
+    .to_bytes()
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+// BLAKE2B
+
+const FILECOIN_BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const NANO_BASE32_ALPHABET: &[u8; 32] = b"13456789abcdefghijkmnopqrstuwxyz";
+
+pub fn generate_address_filecoin(public_key: &CryptoPublicKey) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_filecoin", "debug");
+
+  let public_key_bytes = match public_key {
+    CryptoPublicKey::Secp256k1(key) => key.serialize_uncompressed(),
+    _ => {
+      return Err(AppError::Custom(
+        "Filecoin address requires a secp256k1 public key".to_string(),
+      ));
     }
+  };
+
+  let payload = qr2m_lib::calculate_blake2b_hash(&public_key_bytes, 20);
+
+  let mut checksum_input = Vec::with_capacity(1 + payload.len());
+  checksum_input.push(0x01);
+  checksum_input.extend_from_slice(&payload);
+  let checksum = qr2m_lib::calculate_blake2b_hash(&checksum_input, 4);
+
+  let mut address_bytes = payload;
+  address_bytes.extend_from_slice(&checksum);
 
-    let hardened = part.ends_with("'");
-    let index: u32 = match part.trim_end_matches("'").parse() {
-      Ok(index) => {
-        d3bug(&format!("index {index:?}"), "debug");
+  Ok(format!(
+    "f1{}",
+    qr2m_lib::encode_base32(&address_bytes, FILECOIN_BASE32_ALPHABET, 0)
+  ))
+}
+
+pub fn generate_address_nano(public_key: &CryptoPublicKey) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_nano", "debug");
+
+  let public_key_bytes = match public_key {
+    CryptoPublicKey::Ed25519(key) => key.to_bytes(),
+    _ => {
+      return Err(AppError::Custom(
+        "Nano address requires an ed25519 public key".to_string(),
+      ));
+    }
+  };
+
+  let mut checksum = qr2m_lib::calculate_blake2b_hash(&public_key_bytes, 5);
+  checksum.reverse();
+
+  Ok(format!(
+    "nano_{}{}",
+    qr2m_lib::encode_base32(&public_key_bytes, NANO_BASE32_ALPHABET, 4),
+    qr2m_lib::encode_base32(&checksum, NANO_BASE32_ALPHABET, 0)
+  ))
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+// CARDANO ICARUS
+
+#[derive(Clone)]
+pub struct IcarusKey {
+  pub private_key: [u8; 64],
+  pub chain_code: [u8; 32],
+  pub public_key: [u8; 32],
+}
+
+impl Drop for IcarusKey {
+  fn drop(&mut self) {
+    self.private_key.zeroize();
+    self.chain_code.zeroize();
+  }
+}
+
+impl std::fmt::Debug for IcarusKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("IcarusKey")
+      .field("private_key", &"[REDACTED]")
+      .field("chain_code", &"[REDACTED]")
+      .field("public_key", &hex::encode(self.public_key))
+      .finish()
+  }
+}
+
+impl IcarusKey {
+  pub fn new_master(entropy: &[u8], passphrase: &str) -> FunctionOutput<Self> {
+    d3bug(">>> IcarusKey::new_master", "debug");
+
+    let mut master_key = [0u8; 96];
+    ring::pbkdf2::derive(
+      ring::pbkdf2::PBKDF2_HMAC_SHA512,
+      std::num::NonZeroU32::new(4096).unwrap(),
+      entropy,
+      passphrase.as_bytes(),
+      &mut master_key,
+    );
+
+    master_key[0] &= 0b1111_1000;
+    master_key[31] &= 0b0001_1111;
+    master_key[31] |= 0b0100_0000;
+
+    Self::from_parts(&master_key[..64], &master_key[64..])
+  }
+
+  pub fn from_parts(private_key: &[u8], chain_code: &[u8]) -> FunctionOutput<Self> {
+    let private_key: [u8; 64] = private_key.try_into().map_err(|_| {
+      AppError::Custom(format!(
+        "Icarus private key must be 64 bytes, got {}",
+        private_key.len()
+      ))
+    })?;
+    let chain_code: [u8; 32] = chain_code.try_into().map_err(|_| {
+      AppError::Custom(format!(
+        "Icarus chain code must be 32 bytes, got {}",
+        chain_code.len()
+      ))
+    })?;
+
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&private_key[..32]);
+
+    let public_key = curve25519_dalek::EdwardsPoint::mul_base(
+      &curve25519_dalek::Scalar::from_bytes_mod_order(scalar_bytes),
+    )
+    .compress()
+    .to_bytes();
+
+    Ok(Self {
+      private_key,
+      chain_code,
+      public_key,
+    })
+  }
+
+  pub fn derive_child(&self, child: qr2m_lib::ChildNumber) -> FunctionOutput<Self> {
+    d3bug(">>> IcarusKey::derive_child", "debug");
+    d3bug(&format!("child {child}"), "debug");
+
+    let index_bytes = child.to_raw().to_le_bytes();
+
+    let mut key_data = Vec::with_capacity(69);
+    let mut chain_data = Vec::with_capacity(69);
+
+    if child.is_hardened() {
+      key_data.push(0x00);
+      key_data.extend_from_slice(&self.private_key);
+      chain_data.push(0x01);
+      chain_data.extend_from_slice(&self.private_key);
+    } else {
+      key_data.push(0x02);
+      key_data.extend_from_slice(&self.public_key);
+      chain_data.push(0x03);
+      chain_data.extend_from_slice(&self.public_key);
+    }
+
+    key_data.extend_from_slice(&index_bytes);
+    chain_data.extend_from_slice(&index_bytes);
+
+    let z = qr2m_lib::calculate_hmac_sha512_hash(&self.chain_code, &key_data);
+    let child_chain_code = qr2m_lib::calculate_hmac_sha512_hash(&self.chain_code, &chain_data);
+
+    let mut child_private_key = [0u8; 64];
+
+    // kL = 8 * zL[0..28] + parent kL
+    let mut carry = 0u16;
+    for i in 0..32 {
+      let z_low = if i < 28 { z[i] } else { 0 };
+      let z_previous = if i > 0 && i <= 28 { z[i - 1] } else { 0 };
+      let z_times_eight = (z_low << 3) | (z_previous >> 5);
+      let sum = self.private_key[i] as u16 + z_times_eight as u16 + carry;
+      child_private_key[i] = sum as u8;
+      carry = sum >> 8;
+    }
+
+    // kR = zR + parent kR (mod 2^256)
+    let mut carry = 0u16;
+    for i in 0..32 {
+      let sum = self.private_key[32 + i] as u16 + z[32 + i] as u16 + carry;
+      child_private_key[32 + i] = sum as u8;
+      carry = sum >> 8;
+    }
+
+    Self::from_parts(&child_private_key, &child_chain_code[32..])
+  }
+
+  pub fn encode_private(&self, hrp: &str) -> FunctionOutput<String> {
+    let mut data = Vec::with_capacity(96);
+    data.extend_from_slice(&self.private_key);
+    data.extend_from_slice(&self.chain_code);
+
+    encode_cardano_bech32(hrp, &data)
+  }
+
+  pub fn encode_public(&self, hrp: &str) -> FunctionOutput<String> {
+    let mut data = Vec::with_capacity(64);
+    data.extend_from_slice(&self.public_key);
+    data.extend_from_slice(&self.chain_code);
+
+    encode_cardano_bech32(hrp, &data)
+  }
+}
+
+fn encode_cardano_bech32(hrp: &str, data: &[u8]) -> FunctionOutput<String> {
+  let hrp = bech32::Hrp::parse(hrp)
+    .map_err(|err| AppError::Custom(format!("Invalid bech32 HRP {hrp}: {err}")))?;
+
+  bech32::encode::<bech32::Bech32>(hrp, data)
+    .map_err(|err| AppError::Custom(format!("Bech32 encoding failed: {err}")))
+}
+
+pub fn derive_from_path_icarus(
+  root: &IcarusKey,
+  path: &DerivationPath,
+) -> FunctionOutput<IcarusKey> {
+  d3bug(">>> derive_from_path_icarus", "debug");
+  d3bug(&format!("path {path}"), "debug");
+
+  let mut node = root.clone();
+
+  for child in path.children() {
+    node = node.derive_child(*child)?;
+  }
+
+  Ok(node)
+}
+
+pub fn get_cardano_stake_path(path: &DerivationPath) -> FunctionOutput<DerivationPath> {
+  let children = path.children();
+
+  if children.len() != 5 {
+    return Err(AppError::Custom(format!(
+      "Cardano path must be m/purpose'/coin'/account'/role/index, got {path}"
+    )));
+  }
+
+  let mut stake_children = children[..3].to_vec();
+  stake_children.push(qr2m_lib::ChildNumber::from_raw(2));
+  stake_children.push(qr2m_lib::ChildNumber::from_raw(0));
+
+  Ok(DerivationPath::from_children(stake_children))
+}
+
+pub fn generate_address_cardano_base(
+  payment_public_key: &[u8],
+  stake_public_key: &[u8],
+) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_cardano_base", "debug");
+
+  // Header 0x01: base address (payment key hash, stake key hash) on mainnet
+  let mut address_bytes = Vec::with_capacity(57);
+  address_bytes.push(0x01);
+  address_bytes.extend_from_slice(&qr2m_lib::calculate_blake2b_hash(payment_public_key, 28));
+  address_bytes.extend_from_slice(&qr2m_lib::calculate_blake2b_hash(stake_public_key, 28));
+
+  encode_cardano_bech32("addr", &address_bytes)
+}
+
+pub fn generate_master_keys_icarus(
+  full_entropy: &str,
+  passphrase: &str,
+) -> FunctionOutput<(String, String)> {
+  #[cfg(debug_assertions)]
+  println!("[+] {}", &t!("log.generate_master_keys_icarus").to_string());
+
+  if !qr2m_lib::is_valid_entropy(full_entropy) {
+    return Err(AppError::Custom(t!("error.entropy.invalid").to_string()));
+  }
+
+  let entropy_length = full_entropy.len() * 32 / 33;
+  let entropy_bytes = qr2m_lib::convert_string_to_binary(&full_entropy[..entropy_length]);
+  let master_node = IcarusKey::new_master(&entropy_bytes, passphrase)?;
+
+  let master_private_key_encoded = master_node.encode_private("root_xsk")?;
+  let master_public_key_encoded = master_node.encode_public("root_xvk")?;
+
+  #[cfg(debug_assertions)]
+  {
+    println!(" - Master private key: {master_private_key_encoded:?}");
+    println!(" - Master public key: {master_public_key_encoded:?}");
+  }
+
+  let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
+  wallet_settings.master_private_key = Some(master_private_key_encoded.clone().into());
+  wallet_settings.master_public_key = Some(master_public_key_encoded.clone());
+  wallet_settings.master_private_key_bytes = Some(master_node.private_key.as_slice().into());
+  wallet_settings.master_chain_code_bytes = Some(master_node.chain_code.as_slice().into());
+  wallet_settings.master_public_key_bytes = Some(master_node.public_key.to_vec());
+  wallet_settings.master_depth = Some(0);
+  wallet_settings.master_parent_fingerprint = Some([0u8; 4]);
+  wallet_settings.master_child_number = Some(0);
+
+  Ok((master_private_key_encoded, master_public_key_encoded))
+}
+
+fn generate_address_icarus(ingredients: &AddressHocusPokus) -> FunctionOutput<AddressResult> {
+  d3bug(">>> generate_address_icarus", "debug");
+
+  let root = IcarusKey::from_parts(
+    &ingredients.master_private_key_bytes,
+    &ingredients.master_chain_code_bytes,
+  )?;
+
+  let derivation_path = parse_derivation_path(&ingredients.derivation_path)?;
+  let payment_node = derive_from_path_icarus(&root, &derivation_path)?;
+  let stake_node = derive_from_path_icarus(&root, &get_cardano_stake_path(&derivation_path)?)?;
+
+  let address = generate_address_cardano_base(&payment_node.public_key, &stake_node.public_key)?;
+
+  Ok(Some(Address {
+    address,
+    public_key: hex::encode(payment_node.public_key),
+    private_key: payment_node.encode_private("addr_xsk")?.into(),
+    private_view_key: None,
+    mnemonic: None,
+  }))
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+// MONERO
+
+const MONERO_WORDLIST_LENGTH: usize = 1626;
+const MONERO_WORDLIST_PREFIX_LENGTH: usize = 3;
+
+#[derive(Clone)]
+pub struct MoneroKeys {
+  pub private_spend_key: [u8; 32],
+  pub private_view_key: [u8; 32],
+  pub public_spend_key: [u8; 32],
+  pub public_view_key: [u8; 32],
+}
+
+impl Drop for MoneroKeys {
+  fn drop(&mut self) {
+    self.private_spend_key.zeroize();
+    self.private_view_key.zeroize();
+  }
+}
+
+impl std::fmt::Debug for MoneroKeys {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("MoneroKeys")
+      .field("private_spend_key", &"[REDACTED]")
+      .field("private_view_key", &"[REDACTED]")
+      .field("public_spend_key", &hex::encode(self.public_spend_key))
+      .field("public_view_key", &hex::encode(self.public_view_key))
+      .finish()
+  }
+}
+
+impl MoneroKeys {
+  pub fn from_private_spend_key(private_spend_key: &[u8; 32]) -> Self {
+    d3bug(">>> MoneroKeys::from_private_spend_key", "debug");
+
+    let private_spend_key = curve25519_dalek::Scalar::from_bytes_mod_order(*private_spend_key);
+    let private_view_key = calculate_monero_scalar(private_spend_key.as_bytes());
+
+    Self {
+      private_spend_key: private_spend_key.to_bytes(),
+      private_view_key: private_view_key.to_bytes(),
+      public_spend_key: curve25519_dalek::EdwardsPoint::mul_base(&private_spend_key)
+        .compress()
+        .to_bytes(),
+      public_view_key: curve25519_dalek::EdwardsPoint::mul_base(&private_view_key)
+        .compress()
+        .to_bytes(),
+    }
+  }
+
+  // Same reduction hardware wallets use: spend key = sc_reduce32(keccak256(BIP32 private key))
+  pub fn from_secp256k1_private_key(private_key: &[u8; 32]) -> Self {
+    Self::from_private_spend_key(&calculate_monero_scalar(private_key).to_bytes())
+  }
+
+  pub fn primary_address(&self, network_byte: u8) -> String {
+    let mut address_bytes = Vec::with_capacity(69);
+    address_bytes.push(network_byte);
+    address_bytes.extend_from_slice(&self.public_spend_key);
+    address_bytes.extend_from_slice(&self.public_view_key);
+
+    let checksum = Keccak256::digest(&address_bytes);
+    address_bytes.extend_from_slice(&checksum[..4]);
+
+    qr2m_lib::encode_monero_base58(&address_bytes)
+  }
+}
+
+fn calculate_monero_scalar(data: &[u8]) -> curve25519_dalek::Scalar {
+  curve25519_dalek::Scalar::from_bytes_mod_order(Keccak256::digest(data).into())
+}
+
+pub fn get_monero_checksum_word<'a>(words: &[&'a str]) -> FunctionOutput<&'a str> {
+  if words.is_empty() {
+    return Err(AppError::Custom("Monero mnemonic has no words".to_string()));
+  }
+
+  let prefixes: String = words
+    .iter()
+    .map(|word| {
+      word
+        .chars()
+        .take(MONERO_WORDLIST_PREFIX_LENGTH)
+        .collect::<String>()
+    })
+    .collect();
+  let index = crc32fast::hash(prefixes.as_bytes()) as usize % words.len();
+
+  Ok(words[index])
+}
+
+pub fn encode_monero_mnemonic(
+  private_spend_key: &[u8; 32],
+  wordlist: &[&str],
+) -> FunctionOutput<String> {
+  d3bug(">>> encode_monero_mnemonic", "debug");
+
+  if wordlist.len() != MONERO_WORDLIST_LENGTH {
+    return Err(AppError::Custom(format!(
+      "Monero wordlist must contain {MONERO_WORDLIST_LENGTH} words, got {}",
+      wordlist.len()
+    )));
+  }
 
-        index
-      }
-      Err(err) => {
-        return Err(AppError::Custom(format!(
-          "Error: Unable to parse index from path part: {err}"
-        )));
-      }
-    };
+  let list_length = MONERO_WORDLIST_LENGTH as u64;
+  let mut words = Vec::with_capacity(25);
 
-    let derived = match derive_child_key_secp256k1(&private_key, &chain_code, index, hardened) {
-      Ok(Some(value)) => value,
-      Ok(None) => {
-        return Err(AppError::Custom(
-          "Problem with derivation result: value is None".to_string(),
-        ));
-      }
-      Err(err) => {
-        return Err(AppError::Custom(format!(
-          "Problem with deriving child keys: {err:?}"
-        )));
-      }
-    };
+  for chunk in private_spend_key.chunks(4) {
+    let value = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as u64;
+    let first = value % list_length;
+    let second = (value / list_length + first) % list_length;
+    let third = (value / list_length / list_length + second) % list_length;
 
-    private_key = derived.0.to_vec();
-    chain_code = derived.1.to_vec();
-    public_key = derived.2;
+    words.push(wordlist[first as usize]);
+    words.push(wordlist[second as usize]);
+    words.push(wordlist[third as usize]);
   }
 
-  let array: [u8; 32] = private_key
-    .try_into()
-    .map_err(|_| AppError::Custom("private_key must be 32 bytes".into()))?;
+  words.push(get_monero_checksum_word(&words)?);
 
-  let secret_key = secp256k1::SecretKey::from_byte_array(array)
-    .map_err(|err| AppError::Custom(format!("Invalid secret_key: {err}")))?;
+  Ok(words.join(" "))
+}
 
-  if chain_code.len() != 32 {
-    return Err(AppError::Custom(format!(
-      "Invalid chain code length {:?}",
-      chain_code.len()
-    )));
-  }
+pub fn generate_monero_mnemonic(private_spend_key: &[u8; 32]) -> FunctionOutput<String> {
+  let wordlist_path = std::path::Path::new("wordlists").join("monero_english.txt");
+  let wordlist = qr2m_lib::get_text_from_resources(wordlist_path.to_str().unwrap());
+  let wordlist: Vec<&str> = wordlist.lines().collect();
 
-  let mut chain_code_array = [0u8; 32];
-  chain_code_array.copy_from_slice(&chain_code);
+  encode_monero_mnemonic(private_spend_key, &wordlist)
+}
 
-  let mut public_key_array = [0u8; 33];
-  public_key_array.copy_from_slice(&public_key);
+fn generate_address_monero(ingredients: &AddressHocusPokus) -> FunctionOutput<AddressResult> {
+  d3bug(">>> generate_address_monero", "debug");
+
+  let network_byte = hex::decode(ingredients.public_key_hash.trim_start_matches("0x"))
+    .ok()
+    .and_then(|bytes| bytes.first().copied())
+    .ok_or_else(|| {
+      AppError::Custom(format!(
+        "Invalid Monero network byte: {}",
+        ingredients.public_key_hash
+      ))
+    })?;
+
+  let (private_key, _chain_code, _public_key) = derive_from_path_secp256k1(
+    &ingredients.master_private_key_bytes,
+    &ingredients.master_chain_code_bytes,
+    &ingredients.master_public_key_bytes,
+    &get_relative_derivation_path(ingredients)?,
+  )?
+  .ok_or_else(|| {
+    AppError::Custom(format!(
+      "Key derivation returned no result for path: {}",
+      ingredients.derivation_path
+    ))
+  })?;
 
-  Ok(Some((
-    secret_key.secret_bytes(),
-    chain_code_array,
-    public_key_array.to_vec(),
-  )))
+  let monero_keys = MoneroKeys::from_secp256k1_private_key(&private_key);
+  let mnemonic = generate_monero_mnemonic(&monero_keys.private_spend_key)?;
+
+  Ok(Some(Address {
+    address: monero_keys.primary_address(network_byte),
+    public_key: hex::encode(monero_keys.public_spend_key),
+    private_key: hex::encode(monero_keys.private_spend_key).into(),
+    private_view_key: Some(hex::encode(monero_keys.private_view_key).into()),
+    mnemonic: Some(mnemonic.into()),
+  }))
 }
 
-fn get_public_key(public_key: &CryptoPublicKey) -> FunctionOutput<Vec<u8>> {
+fn get_public_key(public_key: &CryptoPublicKey, compressed: bool) -> FunctionOutput<Vec<u8>> {
   let public_key_bytes = match public_key {
-    CryptoPublicKey::Secp256k1(key) => key.serialize().to_vec(),
-    #[cfg(feature = "dev")]
+    CryptoPublicKey::Secp256k1(key) => {
+      if compressed {
+        key.serialize().to_vec()
+      } else {
+        key.serialize_uncompressed().to_vec()
+      }
+    }
     CryptoPublicKey::Ed25519(key) => key.to_bytes().to_vec(),
   };
 
@@ -305,10 +1432,11 @@ fn get_public_key(public_key: &CryptoPublicKey) -> FunctionOutput<Vec<u8>> {
 pub fn generate_address_sha256(
   public_key: &CryptoPublicKey,
   public_key_hash: &[u8],
+  compressed: bool,
 ) -> FunctionOutput<String> {
   d3bug(">>> generate_address_sha256", "debug");
 
-  let public_key_bytes = match get_public_key(public_key) {
+  let public_key_bytes = match get_public_key(public_key, compressed) {
     Ok(key) => key,
     Err(err) => return Err(AppError::Custom(format!("Can not get public key: {err:?}"))),
   };
@@ -344,13 +1472,13 @@ pub fn generate_address_keccak256(
   public_key: &CryptoPublicKey,
   public_key_hash: &[u8],
   coin_index: u32,
+  eip1191_chain_id: Option<u64>,
 ) -> FunctionOutput<String> {
   #[cfg(debug_assertions)]
   println!("[+] {}", &t!("log.generate_address_keccak256").to_string());
 
   let public_key_bytes = match public_key {
     CryptoPublicKey::Secp256k1(key) => key.serialize_uncompressed().to_vec(),
-    #[cfg(feature = "dev")]
     CryptoPublicKey::Ed25519(key) => key.to_bytes().to_vec(),
   };
 
@@ -359,7 +1487,6 @@ pub fn generate_address_keccak256(
 
   let public_key_slice = match public_key {
     CryptoPublicKey::Secp256k1(_) => &public_key_bytes[1..],
-    #[cfg(feature = "dev")]
     CryptoPublicKey::Ed25519(_) => &public_key_bytes[..],
   };
 
@@ -391,9 +1518,7 @@ pub fn generate_address_keccak256(
 
       bs58::encode(full_payload).into_string()
     }
-    _ => {
-      format!("0x{}", hex::encode(address_bytes))
-    }
+    _ => encode_eip55_address(address_bytes, eip1191_chain_id),
   };
 
   #[cfg(debug_assertions)]
@@ -402,10 +1527,78 @@ pub fn generate_address_keccak256(
   Ok(address)
 }
 
+// ECDB chain ID only enters the checksum for chains that adopted EIP-1191
+pub fn get_eip1191_chain_id(ingredients: &AddressHocusPokus) -> Option<u64> {
+  ingredients.chain_id.filter(|_| ingredients.eip1191)
+}
+
+pub fn encode_eip55_address(address_bytes: &[u8], chain_id: Option<u64>) -> String {
+  d3bug(">>> encode_eip55_address", "debug");
+
+  let lowercase_address = hex::encode(address_bytes);
+  let checksum_input = match chain_id {
+    Some(chain_id) => format!("{chain_id}0x{lowercase_address}"),
+    None => lowercase_address.clone(),
+  };
+
+  let checksum_hash = Keccak256::digest(checksum_input.as_bytes());
+  let mut checksum_address = String::with_capacity(2 + lowercase_address.len());
+  checksum_address.push_str("0x");
+
+  for (i, character) in lowercase_address.chars().enumerate() {
+    let nibble = (checksum_hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+
+    if character.is_ascii_alphabetic() && nibble >= 8 {
+      checksum_address.push(character.to_ascii_uppercase());
+    } else {
+      checksum_address.push(character);
+    }
+  }
+
+  checksum_address
+}
+
+pub fn validate_eip55_address(address: &str, chain_id: Option<u64>) -> FunctionOutput<bool> {
+  d3bug(">>> validate_eip55_address", "debug");
+  d3bug(&format!("address {address:?}"), "debug");
+
+  let hex_address = address
+    .strip_prefix("0x")
+    .or_else(|| address.strip_prefix("0X"))
+    .ok_or_else(|| AppError::Custom("Error: EVM address must start with 0x".to_string()))?;
+
+  if hex_address.len() != 40 || !hex_address.chars().all(|c| c.is_ascii_hexdigit()) {
+    return Err(AppError::Custom(
+      "Error: EVM address must contain 40 hex characters".to_string(),
+    ));
+  }
+
+  let address_bytes = hex::decode(hex_address)
+    .map_err(|err| AppError::Custom(format!("Error: Unable to decode EVM address: {err}")))?;
+
+  let is_lowercase = hex_address == hex_address.to_ascii_lowercase();
+  let is_uppercase = hex_address == hex_address.to_ascii_uppercase();
+
+  if is_lowercase || is_uppercase {
+    return Ok(false);
+  }
+
+  let checksum_address = encode_eip55_address(&address_bytes, chain_id);
+
+  if checksum_address[2..] != *hex_address {
+    return Err(AppError::Custom(
+      "Error: EVM address checksum mismatch".to_string(),
+    ));
+  }
+
+  Ok(true)
+}
+
 pub fn generate_sha256_ripemd160_address(
   coin_index: u32,
   public_key: &CryptoPublicKey,
   public_key_hash: &[u8],
+  compressed: bool,
 ) -> FunctionOutput<String> {
   #[cfg(debug_assertions)]
   println!(
@@ -413,7 +1606,7 @@ pub fn generate_sha256_ripemd160_address(
     &t!("log.generate_sha256_ripemd160_address").to_string()
   );
 
-  let public_key_bytes = match get_public_key(public_key) {
+  let public_key_bytes = match get_public_key(public_key, compressed) {
     Ok(key) => key,
     Err(err) => return Err(AppError::Custom(format!("Can not get public key: {err:?}"))),
   };
@@ -448,6 +1641,134 @@ pub fn generate_sha256_ripemd160_address(
   Ok(encoded_address)
 }
 
+pub fn generate_address_p2sh_p2wpkh(
+  public_key: &CryptoPublicKey,
+  script_hash: &[u8],
+) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_p2sh_p2wpkh", "debug");
+
+  let public_key_bytes = match public_key {
+    CryptoPublicKey::Secp256k1(key) => key.serialize().to_vec(),
+    _ => {
+      return Err(AppError::Custom(
+        "P2SH-P2WPKH address requires a secp256k1 public key".to_string(),
+      ));
+    }
+  };
+
+  let public_key_hash160 = qr2m_lib::calculate_sha256_and_ripemd160_hash(&public_key_bytes);
+
+  let mut redeem_script = Vec::with_capacity(22);
+  redeem_script.push(0x00); // OP_0
+  redeem_script.push(0x14); // push 20 bytes
+  redeem_script.extend_from_slice(&public_key_hash160);
+
+  d3bug(&format!("redeem_script {redeem_script:?}"), "debug");
+
+  let redeem_script_hash160 = qr2m_lib::calculate_sha256_and_ripemd160_hash(&redeem_script);
+
+  let mut payload = Vec::with_capacity(script_hash.len() + redeem_script_hash160.len() + 4);
+  payload.extend_from_slice(script_hash);
+  payload.extend_from_slice(&redeem_script_hash160);
+
+  let checksum = qr2m_lib::calculate_double_sha256_hash(&payload);
+  payload.extend_from_slice(&checksum[0..4]);
+
+  Ok(bs58::encode(payload).into_string())
+}
+
+pub fn calculate_taproot_output_key(
+  internal_key: &secp256k1::XOnlyPublicKey,
+) -> FunctionOutput<secp256k1::XOnlyPublicKey> {
+  d3bug(">>> calculate_taproot_output_key", "debug");
+
+  let tweak_hash = qr2m_lib::calculate_tagged_hash("TapTweak", &internal_key.serialize());
+  let tweak_bytes: [u8; 32] = tweak_hash
+    .try_into()
+    .map_err(|_| AppError::Custom("TapTweak hash must be 32 bytes".into()))?;
+
+  let tweak = secp256k1::Scalar::from_be_bytes(tweak_bytes)
+    .map_err(|err| AppError::Custom(format!("Invalid TapTweak scalar: {err}")))?;
+
+  let secp = secp256k1_context();
+  let (output_key, _parity) = internal_key
+    .add_tweak(secp, &tweak)
+    .map_err(|err| AppError::Custom(format!("Can not tweak internal key: {err}")))?;
+
+  d3bug(&format!("output_key {output_key:?}"), "debug");
+
+  Ok(output_key)
+}
+
+pub fn generate_address_p2tr(public_key: &CryptoPublicKey, hrp: &str) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_p2tr", "debug");
+
+  let internal_key = match public_key {
+    CryptoPublicKey::Secp256k1(key) => key.x_only_public_key().0,
+    _ => {
+      return Err(AppError::Custom(
+        "P2TR address requires a secp256k1 public key".to_string(),
+      ));
+    }
+  };
+
+  let output_key = calculate_taproot_output_key(&internal_key)?;
+
+  let hrp = bech32::Hrp::parse(hrp)
+    .map_err(|err| AppError::Custom(format!("Invalid bech32 prefix {hrp:?}: {err}")))?;
+
+  bech32::segwit::encode(hrp, bech32::segwit::VERSION_1, &output_key.serialize())
+    .map_err(|err| AppError::Custom(format!("Can not encode bech32m address: {err}")))
+}
+
+pub fn generate_address_p2wpkh(public_key: &CryptoPublicKey, hrp: &str) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_p2wpkh", "debug");
+
+  let public_key_bytes = match public_key {
+    CryptoPublicKey::Secp256k1(key) => key.serialize().to_vec(),
+    _ => {
+      return Err(AppError::Custom(
+        "P2WPKH address requires a secp256k1 public key".to_string(),
+      ));
+    }
+  };
+
+  let witness_program = qr2m_lib::calculate_sha256_and_ripemd160_hash(&public_key_bytes);
+
+  d3bug(&format!("witness_program {witness_program:?}"), "debug");
+
+  let hrp = bech32::Hrp::parse(hrp)
+    .map_err(|err| AppError::Custom(format!("Invalid bech32 prefix {hrp:?}: {err}")))?;
+
+  bech32::segwit::encode(hrp, bech32::segwit::VERSION_0, &witness_program)
+    .map_err(|err| AppError::Custom(format!("Can not encode bech32 address: {err}")))
+}
+
+pub fn generate_address_bech32_hash160(
+  public_key: &CryptoPublicKey,
+  hrp: &str,
+) -> FunctionOutput<String> {
+  d3bug(">>> generate_address_bech32_hash160", "debug");
+
+  let public_key_bytes = match public_key {
+    CryptoPublicKey::Secp256k1(key) => key.serialize().to_vec(),
+    _ => {
+      return Err(AppError::Custom(
+        "Bech32 HASH160 address requires a secp256k1 public key".to_string(),
+      ));
+    }
+  };
+
+  // Cosmos SDK account address: plain bech32 of HASH160, no witness version
+  let hash160 = qr2m_lib::calculate_sha256_and_ripemd160_hash(&public_key_bytes);
+
+  let hrp = bech32::Hrp::parse(hrp)
+    .map_err(|err| AppError::Custom(format!("Invalid bech32 prefix {hrp:?}: {err}")))?;
+
+  bech32::encode::<bech32::Bech32>(hrp, &hash160)
+    .map_err(|err| AppError::Custom(format!("Can not encode bech32 address: {err}")))
+}
+
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
 
 pub fn generate_entropy(source: &str, entropy_length: Option<u64>) -> FunctionOutput<String> {
@@ -478,7 +1799,7 @@ pub fn generate_entropy(source: &str, entropy_length: Option<u64>) -> FunctionOu
       println!(" - RNG Entropy: {rng_entropy_string:?}");
 
       let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
-      wallet_settings.entropy_string = Some(rng_entropy_string.clone());
+      wallet_settings.entropy_string = Some(rng_entropy_string.clone().into());
 
       Ok(rng_entropy_string)
     }
@@ -566,7 +1887,7 @@ pub fn generate_entropy(source: &str, entropy_length: Option<u64>) -> FunctionOu
               };
 
               let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
-              wallet_settings.entropy_string = Some(file_entropy_string.clone());
+              wallet_settings.entropy_string = Some(file_entropy_string.clone().into());
 
               if let Err(err) = tx.send(file_entropy_string) {
                 eprintln!("{}", &t!("error.mpsc.send", value = err));
@@ -616,6 +1937,18 @@ pub fn generate_mnemonic_words(
     println!(" - Entropy: {final_entropy_binary:?}");
   }
 
+  let mnemonic_words_as_string = encode_mnemonic_words(final_entropy_binary, dictionary)?;
+
+  let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
+  wallet_settings.mnemonic_words = Some(mnemonic_words_as_string.clone().into());
+
+  Ok(mnemonic_words_as_string)
+}
+
+pub fn encode_mnemonic_words(
+  final_entropy_binary: &str,
+  dictionary: Option<&str>,
+) -> FunctionOutput<String> {
   let chunks: Vec<String> = final_entropy_binary
     .chars()
     .collect::<Vec<char>>()
@@ -628,29 +1961,14 @@ pub fn generate_mnemonic_words(
     .map(|chunk| u32::from_str_radix(chunk, 2).unwrap())
     .collect();
 
-  let dictionary_file = match dictionary.unwrap_or_default() {
-    "Czech" => "czech.txt",
-    "French" => "french.txt",
-    "Italian" => "italian.txt",
-    "Portuguese" => "portuguese.txt",
-    "Spanish" => "spanish.txt",
-    "Chinese simplified" => "chinese_simplified.txt",
-    "Chinese traditional" => "chinese_traditional.txt",
-    "Japanese" => "japanese.txt",
-    "Korean" => "korean.txt",
-    _ => "english.txt",
-  };
-
-  let wordlist_path = std::path::Path::new("wordlists").join(dictionary_file);
-  let wordlist = qr2m_lib::get_text_from_resources(wordlist_path.to_str().unwrap());
+  let wordlist = get_bip39_wordlist(dictionary);
 
   let bad_word = t!("error.wordlist.word").to_string();
-  let mnemonic_words_vector: Vec<&str> = wordlist.lines().collect();
   let mnemonic_words_vector: Vec<&str> = mnemonic_decimal
     .iter()
     .map(|&decimal| {
-      if (decimal as usize) < mnemonic_words_vector.len() {
-        mnemonic_words_vector[decimal as usize]
+      if (decimal as usize) < wordlist.len() {
+        wordlist[decimal as usize].as_str()
       } else {
         &bad_word
       }
@@ -666,17 +1984,300 @@ pub fn generate_mnemonic_words(
     println!(" - Mnemonic words: {mnemonic_words_vector:?}");
   }
 
-  let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
-  wallet_settings.mnemonic_words = Some(mnemonic_words_as_string.clone());
-
   Ok(mnemonic_words_as_string)
 }
 
+const BIP39_WORDLIST_LENGTH: usize = 2048;
+const BIP39_DICTIONARIES: [(&str, &str); 10] = [
+  ("English", "english.txt"),
+  ("Czech", "czech.txt"),
+  ("French", "french.txt"),
+  ("Italian", "italian.txt"),
+  ("Portuguese", "portuguese.txt"),
+  ("Spanish", "spanish.txt"),
+  ("Chinese simplified", "chinese_simplified.txt"),
+  ("Chinese traditional", "chinese_traditional.txt"),
+  ("Japanese", "japanese.txt"),
+  ("Korean", "korean.txt"),
+];
+
+pub fn get_bip39_wordlist(dictionary: Option<&str>) -> Vec<String> {
+  let dictionary_file = BIP39_DICTIONARIES
+    .iter()
+    .find(|(name, _)| Some(*name) == dictionary)
+    .map(|(_, file)| *file)
+    .unwrap_or("english.txt");
+
+  let wordlist_path = std::path::Path::new("wordlists").join(dictionary_file);
+  let wordlist = qr2m_lib::get_text_from_resources(wordlist_path.to_str().unwrap());
+
+  wordlist
+    .lines()
+    .map(|word| word.trim().to_string())
+    .collect()
+}
+
+// BIP39 compares words in NFKD form, some wordlists ship precomposed and some decomposed
+fn normalize_mnemonic_text(text: &str) -> String {
+  text.nfkd().collect()
+}
+
+fn get_normalized_bip39_wordlist(dictionary: &str) -> Vec<String> {
+  get_bip39_wordlist(Some(dictionary))
+    .iter()
+    .map(|word| normalize_mnemonic_text(word))
+    .collect()
+}
+
+fn get_checked_bip39_wordlist(dictionary: &str) -> FunctionOutput<Vec<String>> {
+  let wordlist = get_normalized_bip39_wordlist(dictionary);
+
+  if wordlist.len() != BIP39_WORDLIST_LENGTH {
+    return Err(AppError::Custom(format!(
+      "Invalid {dictionary} wordlist length: {}",
+      wordlist.len()
+    )));
+  }
+
+  Ok(wordlist)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MnemonicIssue {
+  InvalidWordCount(usize),
+  UnknownWord {
+    position: usize,
+    word: String,
+    suggestions: Vec<String>,
+  },
+  InvalidChecksum,
+}
+
+pub struct MnemonicValidation {
+  pub dictionary: &'static str,
+  pub entropy: Option<SecretString>,
+  pub issues: Vec<MnemonicIssue>,
+}
+
+impl MnemonicValidation {
+  pub fn is_valid(&self) -> bool {
+    self.issues.is_empty()
+  }
+}
+
+fn get_mnemonic_dictionary(words: &[String], dictionary: Option<&str>) -> &'static str {
+  if let Some((name, _)) = BIP39_DICTIONARIES
+    .iter()
+    .find(|(name, _)| Some(*name) == dictionary)
+  {
+    return name;
+  }
+
+  // Auto-detect: pick the wordlist containing most of the words, English wins ties
+  let mut best_match = (BIP39_DICTIONARIES[0].0, 0);
+
+  for (name, _) in BIP39_DICTIONARIES {
+    let wordlist = get_normalized_bip39_wordlist(name);
+    let matches = words.iter().filter(|word| wordlist.contains(word)).count();
+
+    if matches > best_match.1 {
+      best_match = (name, matches);
+    }
+  }
+
+  best_match.0
+}
+
+fn calculate_levenshtein_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+  for (i, a_char) in a.chars().enumerate() {
+    let mut current = vec![i + 1; b.len() + 1];
+
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = previous[j] + usize::from(a_char != *b_char);
+      current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+    }
+
+    previous = current;
+  }
+
+  previous[b.len()]
+}
+
+fn get_nearest_mnemonic_words(word: &str, wordlist: &[String]) -> Vec<String> {
+  let word_length = word.chars().count();
+  let max_distance = 2.min(word_length.saturating_sub(1));
+
+  // BIP39 words are unique by their first four letters
+  let prefix: String = word.chars().take(4).collect();
+  let mut suggestions: Vec<String> = if prefix.chars().count() == 4 {
+    wordlist
+      .iter()
+      .filter(|candidate| candidate.starts_with(&prefix))
+      .cloned()
+      .collect()
+  } else {
+    Vec::new()
+  };
+
+  let mut candidates: Vec<(usize, &String)> = wordlist
+    .iter()
+    .map(|candidate| (calculate_levenshtein_distance(word, candidate), candidate))
+    .filter(|(distance, _)| *distance <= max_distance)
+    .collect();
+  candidates.sort_by_key(|(distance, _)| *distance);
+
+  for (_, candidate) in candidates {
+    if !suggestions.contains(candidate) {
+      suggestions.push(candidate.clone());
+    }
+  }
+
+  suggestions.truncate(5);
+  suggestions
+}
+
+fn get_mnemonic_word_indices(
+  words: &[String],
+  wordlist: &[String],
+) -> Result<Vec<usize>, Vec<MnemonicIssue>> {
+  let mut indices = Vec::new();
+  let mut issues = Vec::new();
+
+  for (position, word) in words.iter().enumerate() {
+    match wordlist.iter().position(|candidate| candidate == word) {
+      Some(index) => indices.push(index),
+      None => issues.push(MnemonicIssue::UnknownWord {
+        position: position + 1,
+        word: word.clone(),
+        suggestions: get_nearest_mnemonic_words(word, wordlist),
+      }),
+    }
+  }
+
+  if issues.is_empty() {
+    Ok(indices)
+  } else {
+    Err(issues)
+  }
+}
+
+fn split_mnemonic_words(mnemonic: &str) -> Vec<String> {
+  mnemonic
+    .split_whitespace()
+    .map(|word| normalize_mnemonic_text(&word.to_lowercase()))
+    .collect()
+}
+
+pub fn validate_mnemonic_words(
+  mnemonic: &str,
+  dictionary: Option<&str>,
+) -> FunctionOutput<MnemonicValidation> {
+  d3bug(">>> validate_mnemonic_words", "debug");
+
+  let words = split_mnemonic_words(mnemonic);
+  let dictionary = get_mnemonic_dictionary(&words, dictionary);
+  let wordlist = get_checked_bip39_wordlist(dictionary)?;
+
+  let mut issues = Vec::new();
+
+  if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+    issues.push(MnemonicIssue::InvalidWordCount(words.len()));
+  }
+
+  let mut entropy = None;
+
+  match get_mnemonic_word_indices(&words, &wordlist) {
+    Ok(indices) if issues.is_empty() => {
+      let entropy_binary: String = indices
+        .iter()
+        .map(|index| format!("{index:011b}"))
+        .collect();
+
+      if qr2m_lib::is_valid_entropy(&entropy_binary) {
+        entropy = Some(SecretString::from(entropy_binary));
+      } else {
+        issues.push(MnemonicIssue::InvalidChecksum);
+      }
+    }
+    Ok(_) => {}
+    Err(word_issues) => issues.extend(word_issues),
+  }
+
+  #[cfg(debug_assertions)]
+  {
+    println!(" - Dictionary: {dictionary}");
+    println!(" - Issues: {issues:?}");
+  }
+
+  Ok(MnemonicValidation {
+    dictionary,
+    entropy,
+    issues,
+  })
+}
+
+pub fn find_last_mnemonic_words(
+  mnemonic: &str,
+  dictionary: Option<&str>,
+) -> FunctionOutput<Vec<String>> {
+  d3bug(">>> find_last_mnemonic_words", "debug");
+
+  let words = split_mnemonic_words(mnemonic);
+
+  if !matches!(words.len(), 11 | 14 | 17 | 20 | 23) {
+    return Err(AppError::Custom(format!(
+      "Invalid number of words: {} (expected 11, 14, 17, 20 or 23)",
+      words.len()
+    )));
+  }
+
+  let dictionary = get_mnemonic_dictionary(&words, dictionary);
+  let wordlist = get_checked_bip39_wordlist(dictionary)?;
+
+  let indices = get_mnemonic_word_indices(&words, &wordlist).map_err(|issues| {
+    let unknown_words: Vec<String> = issues
+      .iter()
+      .filter_map(|issue| match issue {
+        MnemonicIssue::UnknownWord { word, .. } => Some(word.clone()),
+        _ => None,
+      })
+      .collect();
+    AppError::Custom(format!(
+      "Unknown mnemonic words: {}",
+      unknown_words.join(", ")
+    ))
+  })?;
+
+  let entropy_prefix: String = indices
+    .iter()
+    .map(|index| format!("{index:011b}"))
+    .collect();
+  let checksum_length = (words.len() + 1) / 3;
+  let free_bits = 11 - checksum_length;
+
+  let last_words = (0..1usize << free_bits)
+    .map(|bits| {
+      let entropy = format!("{entropy_prefix}{bits:0free_bits$b}");
+      let checksum = qr2m_lib::calculate_checksum_for_entropy(&entropy);
+      let index =
+        (bits << checksum_length) | usize::from_str_radix(&checksum, 2).unwrap_or_default();
+      wordlist[index].clone()
+    })
+    .collect();
+
+  Ok(last_words)
+}
+
 pub fn generate_seed_from_mnemonic(mnemonic: &str, passphrase: &str) -> FunctionOutput<[u8; 64]> {
   #[cfg(debug_assertions)]
   println!("[+] {}", &t!("log.generate_seed_from_mnemonic").to_string());
 
-  let salt = format!("mnemonic{passphrase}");
+  let mut salt = String::from("mnemonic");
+  salt.extend(passphrase.nfkd());
+  let mut mnemonic = normalize_mnemonic_text(mnemonic);
   let mut seed = [0u8; 64];
   ring::pbkdf2::derive(
     ring::pbkdf2::PBKDF2_HMAC_SHA512,
@@ -685,6 +2286,8 @@ pub fn generate_seed_from_mnemonic(mnemonic: &str, passphrase: &str) -> Function
     mnemonic.as_bytes(),
     &mut seed,
   );
+  salt.zeroize();
+  mnemonic.zeroize();
 
   Ok(seed)
 }
@@ -745,64 +2348,20 @@ pub fn generate_master_keys_secp256k1(
     println!(" - Public header: {public_header:?}");
   }
 
-  let private_header = private_header
-    .filter(|s| !s.is_empty())
-    .unwrap_or("0x0488ADE4");
-  let public_header = public_header
-    .filter(|s| !s.is_empty())
-    .unwrap_or("0x0488B21E");
-
-  let private_header = u32::from_str_radix(private_header.trim_start_matches("0x"), 16)
+  let private_header = parse_extended_key_header(private_header, "0x0488ADE4")
     .expect(&t!("error.master.parse.header", value = "private_header"));
-  let public_header = u32::from_str_radix(public_header.trim_start_matches("0x"), 16)
+  let public_header = parse_extended_key_header(public_header, "0x0488B21E")
     .expect(&t!("error.master.parse.header", value = "public_header"));
 
-  let seed_bytes = hex::decode(seed).expect(&t!("error.seed.decode"));
+  let mut seed_bytes = hex::decode(seed).expect(&t!("error.seed.decode"));
   let message = "Bitcoin seed";
   let hmac_result = qr2m_lib::calculate_hmac_sha512_hash(message.as_bytes(), &seed_bytes);
   let (master_private_key_bytes, master_chain_code_bytes) = hmac_result.split_at(32);
-  let mut master_private_key = Vec::new();
-
-  master_private_key.extend_from_slice(&u32::to_be_bytes(private_header));
-  master_private_key.push(0x00);
-  master_private_key.extend([0x00; 4].iter());
-  master_private_key.extend([0x00; 4].iter());
-  master_private_key.extend_from_slice(master_chain_code_bytes);
-  master_private_key.push(0x00);
-  master_private_key.extend_from_slice(master_private_key_bytes);
-
-  let checksum: [u8; 4] = qr2m_lib::calculate_checksum_for_master_keys(&master_private_key);
-
-  master_private_key.extend_from_slice(&checksum);
-
-  let master_private_key_encoded = bs58::encode(&master_private_key).into_string();
-  let secp = secp256k1::Secp256k1::new();
-
-  let array: [u8; 32] = master_private_key_bytes
-    .try_into()
-    .map_err(|_| AppError::Custom("master_private_key_bytes must be 32 bytes".into()))?;
-
-  let master_secret_key = secp256k1::SecretKey::from_byte_array(array)
-    .map_err(|err| AppError::Custom(format!("Invalid master_secret_key: {err:?}")))?;
-
-  // let master_secret_key =
-  //   secp256k1::SecretKey::from_slice(master_private_key_bytes).expect(&t!("error.master.create"));
-  let master_public_key_bytes =
-    secp256k1::PublicKey::from_secret_key(&secp, &master_secret_key).serialize();
-  let mut master_public_key = Vec::new();
-
-  master_public_key.extend_from_slice(&u32::to_be_bytes(public_header));
-  master_public_key.push(0x00);
-  master_public_key.extend([0x00; 4].iter());
-  master_public_key.extend([0x00; 4].iter());
-  master_public_key.extend_from_slice(master_chain_code_bytes);
-  master_public_key.extend_from_slice(&master_public_key_bytes);
-
-  let checksum: [u8; 4] = qr2m_lib::calculate_checksum_for_master_keys(&master_public_key);
-
-  master_public_key.extend_from_slice(&checksum);
 
-  let master_public_key_encoded = bs58::encode(&master_public_key).into_string();
+  let master_node = ExtendedKey::new_master(master_private_key_bytes, master_chain_code_bytes)?;
+  let master_private_key_encoded = master_node.serialize_private(private_header)?;
+  let master_public_key_encoded = master_node.serialize_public(public_header);
+  let master_public_key_bytes = master_node.public_key;
 
   #[cfg(debug_assertions)]
   {
@@ -813,17 +2372,21 @@ pub fn generate_master_keys_secp256k1(
     println!(" - Master key private bytes: {master_private_key_bytes:?}");
     println!(" - Master key chain code: {master_chain_code_bytes:?}");
     println!(" - Master private key: {master_private_key_encoded:?}");
-    println!(" - Master secret key {master_secret_key:?}");
     println!(" - Master public key {master_public_key_bytes:?}");
     println!(" - Master public key: {master_public_key_encoded:?}");
   }
 
   let mut wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
-  wallet_settings.master_private_key = Some(master_private_key_encoded.clone());
+  wallet_settings.master_private_key = Some(master_private_key_encoded.clone().into());
   wallet_settings.master_public_key = Some(master_public_key_encoded.clone());
-  wallet_settings.master_private_key_bytes = Some(master_private_key_bytes.to_vec());
-  wallet_settings.master_chain_code_bytes = Some(master_chain_code_bytes.to_vec());
+  wallet_settings.master_private_key_bytes = Some(master_private_key_bytes.into());
+  wallet_settings.master_chain_code_bytes = Some(master_chain_code_bytes.into());
   wallet_settings.master_public_key_bytes = Some(master_public_key_bytes.to_vec());
+  wallet_settings.master_depth = Some(0);
+  wallet_settings.master_parent_fingerprint = Some([0u8; 4]);
+  wallet_settings.master_child_number = Some(0);
+
+  seed_bytes.zeroize();
 
   Ok((master_private_key_encoded, master_public_key_encoded))
 }
@@ -1006,6 +2569,599 @@ pub fn generate_master_keys_secp256k1(
 
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
 
+// MNEMONIC RECOVERY
+
+const MNEMONIC_RECOVERY_MAX_POSITIONS: usize = 2;
+const MNEMONIC_RECOVERY_PROGRESS_STEP: usize = 4096;
+
+pub fn recover_mnemonic_words(
+  mnemonic: &str,
+  dictionary: Option<&str>,
+  passphrase: &str,
+  coin: &AddressHocusPokus,
+  target_address: &str,
+  cancel_flag: &std::sync::Mutex<bool>,
+  progress: &(dyn Fn(f64) + Sync),
+) -> FunctionOutput<Option<SecretString>> {
+  d3bug(">>> recover_mnemonic_words", "debug");
+
+  let words = split_mnemonic_words(mnemonic);
+
+  if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+    return Err(AppError::Custom(format!(
+      "Invalid number of words: {} (expected 12, 15, 18, 21 or 24)",
+      words.len()
+    )));
+  }
+
+  if !matches!(
+    coin.key_derivation.as_str(),
+    "secp256k1" | "secp256k1-monero" | "ed25519" | "ed25519-blake2b" | "ed25519-bip32"
+  ) {
+    return Err(AppError::Custom(format!(
+      "Unsupported key derivation: {}",
+      coin.key_derivation
+    )));
+  }
+
+  parse_derivation_path(&coin.derivation_path)?;
+
+  // Unknown words are written as "?" and suspect words get a trailing "?",
+  // anything missing from the wordlist is searched as well
+  let known_words: Vec<String> = words
+    .iter()
+    .map(|word| word.trim_end_matches('?').to_string())
+    .collect();
+  let dictionary = get_mnemonic_dictionary(&known_words, dictionary);
+  let wordlist = get_checked_bip39_wordlist(dictionary)?;
+
+  let mut indices = Vec::with_capacity(words.len());
+  let mut positions = Vec::new();
+
+  for (position, word) in words.iter().enumerate() {
+    match wordlist.iter().position(|candidate| candidate == word) {
+      Some(index) => indices.push(index),
+      None => {
+        positions.push(position);
+        indices.push(0);
+      }
+    }
+  }
+
+  if positions.is_empty() {
+    return Err(AppError::Custom(
+      "No unknown or suspect words, mark them with ?".to_string(),
+    ));
+  }
+
+  if positions.len() > MNEMONIC_RECOVERY_MAX_POSITIONS {
+    return Err(AppError::Custom(format!(
+      "Too many unknown or suspect words: {} (maximum {MNEMONIC_RECOVERY_MAX_POSITIONS})",
+      positions.len()
+    )));
+  }
+
+  let total_candidates = BIP39_WORDLIST_LENGTH.pow(positions.len() as u32);
+  let processed_candidates = std::sync::atomic::AtomicUsize::new(0);
+  let target_address = target_address.trim();
+
+  // A pasted EVM address with a broken checksum would only fail after the whole search
+  let is_evm_target = coin.hash == "keccak256" && coin.coin_index != 195;
+  if is_evm_target {
+    validate_eip55_address(target_address, get_eip1191_chain_id(coin))?;
+  }
+
+  // Base58 is case sensitive, only bech32 and the already checked EVM hex may differ in case
+  let ignore_case = is_evm_target || bech32::decode(target_address).is_ok();
+
+  #[cfg(debug_assertions)]
+  {
+    println!(" - Dictionary: {dictionary}");
+    println!(" - Search positions: {positions:?}");
+    println!(" - Candidates: {total_candidates}");
+  }
+
+  let result = (0..total_candidates)
+    .into_par_iter()
+    .find_map_any(|combination| {
+      let processed = processed_candidates.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+      if processed.is_multiple_of(MNEMONIC_RECOVERY_PROGRESS_STEP) {
+        progress(processed as f64 / total_candidates as f64);
+      }
+
+      let mut candidate_indices = indices.clone();
+      let mut remaining = combination;
+
+      for &position in &positions {
+        candidate_indices[position] = remaining % BIP39_WORDLIST_LENGTH;
+        remaining /= BIP39_WORDLIST_LENGTH;
+      }
+
+      let full_entropy = SecretString::from(
+        candidate_indices
+          .iter()
+          .map(|index| format!("{index:011b}"))
+          .collect::<String>(),
+      );
+
+      if !qr2m_lib::is_valid_entropy(&full_entropy) {
+        return None;
+      }
+
+      if *cancel_flag.lock().unwrap() {
+        return Some(Ok(None));
+      }
+
+      let candidate = SecretString::from(
+        candidate_indices
+          .iter()
+          .map(|&index| wordlist[index].as_str())
+          .collect::<Vec<&str>>()
+          .join(" "),
+      );
+
+      match generate_recovery_address(&candidate, &full_entropy, passphrase, coin) {
+        Ok(address)
+          if address == target_address
+            || (ignore_case && address.eq_ignore_ascii_case(target_address)) =>
+        {
+          Some(Ok(Some(candidate)))
+        }
+        Ok(_) => None,
+        Err(err) => Some(Err(err)),
+      }
+    });
+
+  progress(1.0);
+
+  result.transpose().map(Option::flatten)
+}
+
+fn derive_master_keys_secp256k1(
+  seed: &[u8],
+) -> FunctionOutput<(SecretBytes, SecretBytes, Vec<u8>)> {
+  let hmac_result = qr2m_lib::calculate_hmac_sha512_hash(b"Bitcoin seed", seed);
+  let (private_key, chain_code) = hmac_result.split_at(32);
+  let master_node = ExtendedKey::new_master(private_key, chain_code)?;
+
+  Ok((
+    private_key.into(),
+    chain_code.into(),
+    master_node.public_key.to_vec(),
+  ))
+}
+
+fn generate_recovery_address(
+  mnemonic: &str,
+  full_entropy: &str,
+  passphrase: &str,
+  coin: &AddressHocusPokus,
+) -> FunctionOutput<String> {
+  let mut seed = generate_seed_from_mnemonic(mnemonic, passphrase)?;
+
+  let master_keys: FunctionOutput<(SecretBytes, SecretBytes, Vec<u8>)> =
+    match coin.key_derivation.as_str() {
+      "ed25519" | "ed25519-blake2b" => {
+        derive_master_key_ed25519(&seed).map(|(private_key, chain_code, public_key)| {
+          (private_key.into(), chain_code.into(), public_key)
+        })
+      }
+      "ed25519-bip32" => {
+        let entropy_length = full_entropy.len() * 32 / 33;
+        let entropy_bytes = qr2m_lib::convert_string_to_binary(&full_entropy[..entropy_length]);
+
+        IcarusKey::new_master(&entropy_bytes, passphrase).map(|master_node| {
+          (
+            master_node.private_key.as_slice().into(),
+            master_node.chain_code.as_slice().into(),
+            master_node.public_key.to_vec(),
+          )
+        })
+      }
+      _ => derive_master_keys_secp256k1(&seed),
+    };
+
+  seed.zeroize();
+
+  let (master_private_key_bytes, master_chain_code_bytes, master_public_key_bytes) = master_keys?;
+
+  let address = generate_address(AddressHocusPokus {
+    coin_index: coin.coin_index,
+    derivation_path: coin.derivation_path.clone(),
+    master_private_key_bytes,
+    master_chain_code_bytes,
+    master_public_key_bytes,
+    master_depth: 0,
+    master_child_number: 0,
+    public_key_hash: coin.public_key_hash.clone(),
+    script_hash: coin.script_hash.clone(),
+    bech32_hrp: coin.bech32_hrp.clone(),
+    key_derivation: coin.key_derivation.clone(),
+    wallet_import_format: coin.wallet_import_format.clone(),
+    hash: coin.hash.clone(),
+    chain_id: coin.chain_id,
+    eip1191: coin.eip1191,
+    bip: coin.bip,
+    compressed: coin.compressed,
+  })?
+  .ok_or_else(|| AppError::Custom("Address generation returned no result".to_string()))?;
+
+  Ok(address.address)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+// ELECTRUM
+
+const ELECTRUM_SEED_VERSION_KEY: &[u8] = b"Seed version";
+const ELECTRUM_SEED_SALT: &str = "electrum";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElectrumSeedType {
+  Standard,
+  Segwit,
+  TwoFactor,
+  TwoFactorSegwit,
+}
+
+impl ElectrumSeedType {
+  pub fn name(&self) -> &'static str {
+    match self {
+      ElectrumSeedType::Standard => "standard",
+      ElectrumSeedType::Segwit => "segwit",
+      ElectrumSeedType::TwoFactor => "2fa",
+      ElectrumSeedType::TwoFactorSegwit => "2fa_segwit",
+    }
+  }
+}
+
+// Code point ranges Electrum treats as CJK when joining words
+const ELECTRUM_CJK_INTERVALS: [(u32, u32); 29] = [
+  (0x4E00, 0x9FFF),
+  (0x3400, 0x4DBF),
+  (0x20000, 0x2A6DF),
+  (0x2A700, 0x2B73F),
+  (0x2B740, 0x2B81F),
+  (0xF900, 0xFAFF),
+  (0x2F800, 0x2FA1D),
+  (0x3190, 0x319F),
+  (0x2E80, 0x2EFF),
+  (0x2F00, 0x2FDF),
+  (0x31C0, 0x31EF),
+  (0x2FF0, 0x2FFF),
+  (0xE0100, 0xE01EF),
+  (0x3100, 0x312F),
+  (0x31A0, 0x31BF),
+  (0xFF00, 0xFFEF),
+  (0x3040, 0x309F),
+  (0x30A0, 0x30FF),
+  (0x31F0, 0x31FF),
+  (0x1B000, 0x1B0FF),
+  (0xAC00, 0xD7AF),
+  (0x1100, 0x11FF),
+  (0xA960, 0xA97F),
+  (0xD7B0, 0xD7FF),
+  (0x3130, 0x318F),
+  (0xA4D0, 0xA4FF),
+  (0x16F00, 0x16F9F),
+  (0xA000, 0xA48F),
+  (0xA490, 0xA4CF),
+];
+
+fn is_electrum_cjk_character(character: char) -> bool {
+  ELECTRUM_CJK_INTERVALS
+    .iter()
+    .any(|(start, end)| (*start..=*end).contains(&(character as u32)))
+}
+
+// Same steps as Electrum's normalize_text: NFKD, lowercase, strip accents, collapse
+// whitespace and drop the spaces between CJK characters
+fn normalize_electrum_text(text: &str) -> String {
+  let mut stripped: String = text
+    .nfkd()
+    .collect::<String>()
+    .to_lowercase()
+    .chars()
+    .filter(|character| unicode_normalization::char::canonical_combining_class(*character) == 0)
+    .collect();
+
+  let mut normalized = String::with_capacity(stripped.len());
+
+  for word in stripped.split_whitespace() {
+    let joins_cjk = normalized
+      .chars()
+      .last()
+      .is_some_and(is_electrum_cjk_character)
+      && word.chars().next().is_some_and(is_electrum_cjk_character);
+
+    if !normalized.is_empty() && !joins_cjk {
+      normalized.push(' ');
+    }
+
+    normalized.push_str(word);
+  }
+
+  stripped.zeroize();
+  normalized
+}
+
+pub fn get_electrum_seed_type(mnemonic: &str) -> Option<ElectrumSeedType> {
+  let mnemonic = SecretString::from(normalize_electrum_text(mnemonic));
+
+  if mnemonic.is_empty() {
+    return None;
+  }
+
+  let version = hex::encode(&*qr2m_lib::calculate_hmac_sha512_hash(
+    ELECTRUM_SEED_VERSION_KEY,
+    mnemonic.as_bytes(),
+  ));
+
+  [
+    ("01", ElectrumSeedType::Standard),
+    ("100", ElectrumSeedType::Segwit),
+    ("101", ElectrumSeedType::TwoFactor),
+    ("102", ElectrumSeedType::TwoFactorSegwit),
+  ]
+  .into_iter()
+  .find(|(prefix, _)| version.starts_with(prefix))
+  .map(|(_, seed_type)| seed_type)
+}
+
+pub fn generate_seed_from_electrum_mnemonic(
+  mnemonic: &str,
+  passphrase: &str,
+) -> FunctionOutput<[u8; 64]> {
+  d3bug(">>> generate_seed_from_electrum_mnemonic", "debug");
+
+  if get_electrum_seed_type(mnemonic).is_none() {
+    return Err(AppError::Custom(
+      "Not an Electrum v2 seed (unknown seed version)".to_string(),
+    ));
+  }
+
+  let mnemonic = SecretString::from(normalize_electrum_text(mnemonic));
+  let mut salt = format!(
+    "{ELECTRUM_SEED_SALT}{}",
+    normalize_electrum_text(passphrase)
+  );
+  let mut seed = [0u8; 64];
+  ring::pbkdf2::derive(
+    ring::pbkdf2::PBKDF2_HMAC_SHA512,
+    std::num::NonZeroU32::new(2048).unwrap(),
+    salt.as_bytes(),
+    mnemonic.as_bytes(),
+    &mut seed,
+  );
+  salt.zeroize();
+
+  Ok(seed)
+}
+
+pub fn get_electrum_derivation_path(
+  seed_type: ElectrumSeedType,
+  change: bool,
+  index: u32,
+) -> FunctionOutput<String> {
+  let chain = u32::from(change);
+
+  match seed_type {
+    ElectrumSeedType::Standard => Ok(format!("m/{chain}/{index}")),
+    ElectrumSeedType::Segwit => Ok(format!("m/0'/{chain}/{index}")),
+    _ => Err(AppError::Custom(format!(
+      "Electrum {} seeds are 2-of-3 multisig wallets and are not supported",
+      seed_type.name()
+    ))),
+  }
+}
+
+pub fn generate_electrum_addresses(
+  mnemonic: &str,
+  passphrase: &str,
+  change: bool,
+  start_index: u32,
+  count: u32,
+) -> FunctionOutput<Vec<(String, Address)>> {
+  d3bug(">>> generate_electrum_addresses", "debug");
+
+  let seed_type = get_electrum_seed_type(mnemonic).ok_or_else(|| {
+    AppError::Custom("Not an Electrum v2 seed (unknown seed version)".to_string())
+  })?;
+
+  let mut seed = generate_seed_from_electrum_mnemonic(mnemonic, passphrase)?;
+  let master_keys = derive_master_keys_secp256k1(&seed);
+  seed.zeroize();
+
+  let (master_private_key_bytes, master_chain_code_bytes, master_public_key_bytes) = master_keys?;
+  let bip = match seed_type {
+    ElectrumSeedType::Segwit => 84,
+    _ => 44,
+  };
+
+  let mut addresses = Vec::with_capacity(count as usize);
+
+  for index in start_index..start_index.saturating_add(count) {
+    let derivation_path = get_electrum_derivation_path(seed_type, change, index)?;
+
+    let address = generate_address(AddressHocusPokus {
+      coin_index: 0,
+      derivation_path: derivation_path.clone(),
+      master_private_key_bytes: master_private_key_bytes.clone(),
+      master_chain_code_bytes: master_chain_code_bytes.clone(),
+      master_public_key_bytes: master_public_key_bytes.clone(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: "0x00".to_string(),
+      script_hash: "0x05".to_string(),
+      bech32_hrp: "bc".to_string(),
+      key_derivation: "secp256k1".to_string(),
+      wallet_import_format: "0x80".to_string(),
+      hash: "sha256".to_string(),
+      chain_id: None,
+      eip1191: false,
+      bip,
+      compressed: true,
+    })?
+    .ok_or_else(|| AppError::Custom("Address generation returned no result".to_string()))?;
+
+    addresses.push((derivation_path, address));
+  }
+
+  Ok(addresses)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// BIP85
+
+const BIP85_HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+const BIP85_PURPOSE: u32 = 83696968;
+const BIP85_APPLICATION_BIP39: u32 = 39;
+const BIP85_APPLICATION_WIF: u32 = 2;
+const BIP85_APPLICATION_HEX: u32 = 128169;
+const BIP85_APPLICATION_PASSWORD: u32 = 707764;
+
+fn get_bip85_path(application: u32, parameters: &[u32]) -> FunctionOutput<DerivationPath> {
+  [BIP85_PURPOSE, application]
+    .iter()
+    .chain(parameters)
+    .map(|&index| qr2m_lib::ChildNumber::new(index, true).map_err(AppError::Custom))
+    .collect::<FunctionOutput<Vec<_>>>()
+    .map(DerivationPath::from_children)
+}
+
+pub fn derive_bip85_entropy(
+  master_private_key: &[u8],
+  master_chain_code: &[u8],
+  path: &DerivationPath,
+) -> FunctionOutput<SecretBytes> {
+  d3bug(">>> derive_bip85_entropy", "debug");
+  d3bug(&format!("path {path}"), "debug");
+
+  let (mut private_key, mut chain_code, _public_key) =
+    derive_from_path_secp256k1(master_private_key, master_chain_code, &[], path)?
+      .ok_or_else(|| AppError::Custom(format!("Can not derive BIP85 key at {path}")))?;
+  chain_code.zeroize();
+
+  let entropy = qr2m_lib::calculate_hmac_sha512_hash(BIP85_HMAC_KEY, &private_key);
+  private_key.zeroize();
+
+  Ok(entropy)
+}
+
+pub fn get_bip85_language_code(dictionary: &str) -> FunctionOutput<u32> {
+  match dictionary {
+    "English" => Ok(0),
+    "Japanese" => Ok(1),
+    "Korean" => Ok(2),
+    "Spanish" => Ok(3),
+    "Chinese simplified" => Ok(4),
+    "Chinese traditional" => Ok(5),
+    "French" => Ok(6),
+    "Italian" => Ok(7),
+    "Czech" => Ok(8),
+    _ => Err(AppError::Custom(format!(
+      "Unsupported BIP85 language {dictionary}"
+    ))),
+  }
+}
+
+pub fn derive_bip85_mnemonic(
+  master_private_key: &[u8],
+  master_chain_code: &[u8],
+  dictionary: &str,
+  word_count: u32,
+  index: u32,
+) -> FunctionOutput<SecretString> {
+  d3bug(">>> derive_bip85_mnemonic", "debug");
+
+  if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+    return Err(AppError::Custom(format!(
+      "Unsupported BIP85 word count {word_count}"
+    )));
+  }
+
+  let language_code = get_bip85_language_code(dictionary)?;
+  let path = get_bip85_path(BIP85_APPLICATION_BIP39, &[language_code, word_count, index])?;
+  let entropy = derive_bip85_entropy(master_private_key, master_chain_code, &path)?;
+
+  let mut entropy_binary = SecretString::new(
+    entropy[..(word_count * 4 / 3) as usize]
+      .iter()
+      .map(|byte| format!("{byte:08b}"))
+      .collect(),
+  );
+  let checksum = qr2m_lib::calculate_checksum_for_entropy(&entropy_binary);
+  entropy_binary = format!("{entropy_binary}{checksum}").into();
+
+  Ok(encode_mnemonic_words(&entropy_binary, Some(dictionary))?.into())
+}
+
+pub fn derive_bip85_hex(
+  master_private_key: &[u8],
+  master_chain_code: &[u8],
+  length: u32,
+  index: u32,
+) -> FunctionOutput<SecretString> {
+  d3bug(">>> derive_bip85_hex", "debug");
+
+  if !(16..=64).contains(&length) {
+    return Err(AppError::Custom(format!(
+      "BIP85 HEX length must be between 16 and 64 bytes, got {length}"
+    )));
+  }
+
+  let path = get_bip85_path(BIP85_APPLICATION_HEX, &[length, index])?;
+  let entropy = derive_bip85_entropy(master_private_key, master_chain_code, &path)?;
+
+  Ok(hex::encode(&entropy[..length as usize]).into())
+}
+
+pub fn derive_bip85_wif(
+  master_private_key: &[u8],
+  master_chain_code: &[u8],
+  index: u32,
+) -> FunctionOutput<SecretString> {
+  d3bug(">>> derive_bip85_wif", "debug");
+
+  let path = get_bip85_path(BIP85_APPLICATION_WIF, &[index])?;
+  let entropy = derive_bip85_entropy(master_private_key, master_chain_code, &path)?;
+
+  let mut private_key = [0u8; 32];
+  private_key.copy_from_slice(&entropy[..32]);
+  let secret_key = secp256k1::SecretKey::from_byte_array(private_key)
+    .map_err(|err| AppError::Custom(format!("Invalid BIP85 SecretKey: {err}")));
+  private_key.zeroize();
+
+  Ok(
+    create_private_key_for_address(Some(&secret_key?), Some(true), Some("0x80"), "sha256", 0)?
+      .into(),
+  )
+}
+
+pub fn derive_bip85_password(
+  master_private_key: &[u8],
+  master_chain_code: &[u8],
+  length: u32,
+  index: u32,
+) -> FunctionOutput<SecretString> {
+  d3bug(">>> derive_bip85_password", "debug");
+
+  if !(20..=86).contains(&length) {
+    return Err(AppError::Custom(format!(
+      "BIP85 password length must be between 20 and 86 characters, got {length}"
+    )));
+  }
+
+  let path = get_bip85_path(BIP85_APPLICATION_PASSWORD, &[length, index])?;
+  let entropy = derive_bip85_entropy(master_private_key, master_chain_code, &path)?;
+  let mut password = qr2m_lib::encode_base64(&entropy);
+  password.truncate(length as usize);
+
+  Ok(password.into())
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
 pub fn convert_seed_to_mnemonic(seed: &[u8]) -> FunctionOutput<String> {
   #[cfg(debug_assertions)]
   println!("[+] {}", &t!("log.convert_seed_to_mnemonic").to_string());
@@ -1021,10 +3177,30 @@ pub fn convert_seed_to_mnemonic(seed: &[u8]) -> FunctionOutput<String> {
 
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
 
+pub fn parse_derivation_path(path: &str) -> FunctionOutput<DerivationPath> {
+  path.parse::<DerivationPath>().map_err(AppError::Custom)
+}
+
+pub fn get_relative_derivation_path(
+  ingredients: &AddressHocusPokus,
+) -> FunctionOutput<DerivationPath> {
+  parse_derivation_path(&ingredients.derivation_path)?
+    .relative_to(ingredients.master_depth, ingredients.master_child_number)
+    .map_err(AppError::Custom)
+}
+
 pub fn generate_address(ingredients: AddressHocusPokus) -> FunctionOutput<AddressResult> {
   d3bug(">>> generate_address", "debug");
   d3bug(&format!("ingredients {ingredients:?}"), "debug");
 
+  if ingredients.key_derivation == "ed25519-bip32" {
+    return generate_address_icarus(&ingredients);
+  }
+
+  if ingredients.key_derivation == "secp256k1-monero" {
+    return generate_address_monero(&ingredients);
+  }
+
   let public_key_hash_vec = if ingredients.key_derivation != "ed25519" {
     let trimmed = ingredients.public_key_hash.trim_start_matches("0x");
     hex::decode(trimmed)
@@ -1033,38 +3209,65 @@ pub fn generate_address(ingredients: AddressHocusPokus) -> FunctionOutput<Addres
     Vec::new()
   };
 
-  let derived_child_keys = derive_child_keys(&ingredients)?;
-  let derived_child_keys = derived_child_keys.ok_or_else(|| {
-    AppError::Custom(format!(
-      "Key derivation returned no result for path: {}",
-      ingredients.derivation_path
-    ))
-  })?;
+  let script_hash_vec = if ingredients.bip == 49 {
+    let trimmed = ingredients.script_hash.trim_start_matches("0x");
+    hex::decode(trimmed).map_err(|err| AppError::Custom(format!("Invalid script_hash: {err}")))?
+  } else {
+    Vec::new()
+  };
+
+  let (public_key, private_key_bytes) = if ingredients.master_private_key_bytes.is_empty() {
+    (derive_watch_only_public_key(&ingredients)?, None)
+  } else {
+    let derived_child_keys = derive_child_keys(&ingredients)?;
+    let derived_child_keys = derived_child_keys.ok_or_else(|| {
+      AppError::Custom(format!(
+        "Key derivation returned no result for path: {}",
+        ingredients.derivation_path
+      ))
+    })?;
+
+    (
+      generate_public_key(&ingredients, &derived_child_keys)?,
+      Some(derived_child_keys.0),
+    )
+  };
 
-  let public_key = generate_public_key(&ingredients, &derived_child_keys)?;
   let public_key_encoded = encode_public_key(&ingredients, &public_key)?;
-  let address = generate_address_internal(&ingredients, &public_key, &public_key_hash_vec)?;
-  let priv_key_wif = encode_private_key(&ingredients, &derived_child_keys.0)?;
+  let address = generate_address_internal(
+    &ingredients,
+    &public_key,
+    &public_key_hash_vec,
+    &script_hash_vec,
+  )?;
+  let priv_key_wif = match private_key_bytes {
+    Some(private_key_bytes) => encode_private_key(&ingredients, &private_key_bytes)?,
+    None => String::new(),
+  };
 
   Ok(Some(Address {
     address,
     public_key: public_key_encoded,
-    private_key: priv_key_wif,
+    private_key: priv_key_wif.into(),
+    private_view_key: None,
+    mnemonic: None,
   }))
 }
 
 fn derive_child_keys(ingredients: &AddressHocusPokus) -> FunctionOutput<DerivationResult> {
+  let relative_path = get_relative_derivation_path(ingredients)?;
+
   match ingredients.key_derivation.as_str() {
     "secp256k1" => derive_from_path_secp256k1(
       &ingredients.master_private_key_bytes,
       &ingredients.master_chain_code_bytes,
-      &ingredients.derivation_path,
+      &ingredients.master_public_key_bytes,
+      &relative_path,
     ),
-    #[cfg(feature = "dev")]
-    "ed25519" => crate::dev::derive_from_path_ed25519(
+    "ed25519" | "ed25519-blake2b" => derive_from_path_ed25519(
       &ingredients.master_private_key_bytes,
       &ingredients.master_chain_code_bytes,
-      &ingredients.derivation_path,
+      &relative_path,
     ),
     _ => Err(AppError::Custom(format!(
       "Unsupported key derivation method: {}",
@@ -1073,26 +3276,62 @@ fn derive_child_keys(ingredients: &AddressHocusPokus) -> FunctionOutput<Derivati
   }
 }
 
+fn derive_watch_only_public_key(
+  ingredients: &AddressHocusPokus,
+) -> FunctionOutput<CryptoPublicKey> {
+  match ingredients.key_derivation.as_str() {
+    "secp256k1" => {
+      let (_chain_code, public_key) = derive_public_from_path_secp256k1(
+        &ingredients.master_public_key_bytes,
+        &ingredients.master_chain_code_bytes,
+        &get_relative_derivation_path(ingredients)?,
+      )?;
+
+      let secp_pub_key = secp256k1::PublicKey::from_byte_array_compressed(public_key)
+        .map_err(|err| AppError::Custom(format!("Invalid PublicKey: {err}")))?;
+
+      Ok(CryptoPublicKey::Secp256k1(secp_pub_key))
+    }
+    _ => Err(AppError::Custom(format!(
+      "Watch-only derivation is not supported for {}",
+      ingredients.key_derivation
+    ))),
+  }
+}
+
 fn generate_public_key(
   ingredients: &AddressHocusPokus,
   derived_child_keys: &([u8; 32], [u8; 32], Vec<u8>),
 ) -> FunctionOutput<CryptoPublicKey> {
   match ingredients.key_derivation.as_str() {
     "secp256k1" => {
-      let secp = secp256k1::Secp256k1::new();
+      // The derivation already computed the child public key, decompressing it is cheaper than
+      // another scalar multiplication
+      if let Ok(public_key) = <[u8; 33]>::try_from(derived_child_keys.2.as_slice())
+        && let Ok(secp_pub_key) = secp256k1::PublicKey::from_byte_array_compressed(public_key)
+      {
+        return Ok(CryptoPublicKey::Secp256k1(secp_pub_key));
+      }
+
       let secret_key = secp256k1::SecretKey::from_byte_array(derived_child_keys.0)
         .map_err(|err| AppError::Custom(format!("Invalid SecretKey: {err}")))?;
-      let secp_pub_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+      let secp_pub_key = secp256k1::PublicKey::from_secret_key(secp256k1_context(), &secret_key);
 
       Ok(CryptoPublicKey::Secp256k1(secp_pub_key))
     }
-    #[cfg(feature = "dev")]
     "ed25519" => {
       let sign_key = ed25519_dalek::SigningKey::from_bytes(&derived_child_keys.0);
       let pub_key = sign_key.verifying_key();
 
       Ok(CryptoPublicKey::Ed25519(pub_key))
     }
+    "ed25519-blake2b" => {
+      let pub_key_bytes = derive_public_key_ed25519_blake2b(&derived_child_keys.0);
+      let pub_key = ed25519_dalek::VerifyingKey::from_bytes(&pub_key_bytes)
+        .map_err(|err| AppError::Custom(format!("Invalid ed25519 public key: {err}")))?;
+
+      Ok(CryptoPublicKey::Ed25519(pub_key))
+    }
     _ => Err(AppError::Custom(format!(
       "Unsupported key derivation method: {}",
       ingredients.key_derivation
@@ -1106,27 +3345,41 @@ fn encode_public_key(
 ) -> FunctionOutput<String> {
   match ingredients.hash.as_str() {
     "sha256" | "sha256+ripemd160" => match public_key {
-      CryptoPublicKey::Secp256k1(pk) => Ok(hex::encode(pk.serialize())),
-      #[cfg(feature = "dev")]
+      CryptoPublicKey::Secp256k1(pk) => {
+        if ingredients.bip == 86 {
+          Ok(hex::encode(pk.x_only_public_key().0.serialize()))
+        } else if ingredients.compressed {
+          Ok(hex::encode(pk.serialize()))
+        } else {
+          Ok(hex::encode(pk.serialize_uncompressed()))
+        }
+      }
       _ => Ok(String::new()),
     },
     "keccak256" => match public_key {
       CryptoPublicKey::Secp256k1(pk) => {
-        let serialized = pk.serialize();
+        let serialized = if ingredients.compressed {
+          pk.serialize().to_vec()
+        } else {
+          pk.serialize_uncompressed().to_vec()
+        };
+
         if ingredients.coin_index == 195 {
           Ok(hex::encode(serialized))
         } else {
           Ok(format!("0x{}", hex::encode(serialized)))
         }
       }
-      #[cfg(feature = "dev")]
       _ => Ok(String::new()),
     },
-    #[cfg(feature = "dev")]
     "ed25519" => match public_key {
       CryptoPublicKey::Ed25519(pk) => Ok(bs58::encode(pk.to_bytes()).into_string()),
       _ => Ok(String::new()),
     },
+    "blake2b" => match public_key {
+      CryptoPublicKey::Secp256k1(pk) => Ok(hex::encode(pk.serialize_uncompressed())),
+      CryptoPublicKey::Ed25519(pk) => Ok(hex::encode_upper(pk.to_bytes())),
+    },
     _ => Err(AppError::Custom(format!(
       "Unsupported hash method: {}",
       ingredients.hash
@@ -1138,17 +3391,76 @@ fn generate_address_internal(
   ingredients: &AddressHocusPokus,
   public_key: &CryptoPublicKey,
   public_key_hash_vec: &[u8],
+  script_hash_vec: &[u8],
 ) -> FunctionOutput<String> {
+  if !ingredients.compressed && matches!(ingredients.bip, 49 | 84 | 86) {
+    return Err(AppError::Custom(format!(
+      "BIP {} addresses require a compressed public key",
+      ingredients.bip
+    )));
+  }
+
   match ingredients.hash.as_str() {
-    "sha256" => generate_address_sha256(public_key, public_key_hash_vec),
-    "keccak256" => {
-      generate_address_keccak256(public_key, public_key_hash_vec, ingredients.coin_index)
-    }
+    "sha256" => match ingredients.bip {
+      49 => {
+        if script_hash_vec.is_empty() {
+          return Err(AppError::Custom(format!(
+            "Coin {} has no script hash version",
+            ingredients.coin_index
+          )));
+        }
+
+        generate_address_p2sh_p2wpkh(public_key, script_hash_vec)
+      }
+      84 => {
+        if ingredients.bech32_hrp.is_empty() {
+          return Err(AppError::Custom(format!(
+            "Coin {} does not support native SegWit addresses",
+            ingredients.coin_index
+          )));
+        }
+
+        generate_address_p2wpkh(public_key, &ingredients.bech32_hrp)
+      }
+      86 => {
+        if ingredients.bech32_hrp.is_empty() {
+          return Err(AppError::Custom(format!(
+            "Coin {} does not support Taproot addresses",
+            ingredients.coin_index
+          )));
+        }
+
+        generate_address_p2tr(public_key, &ingredients.bech32_hrp)
+      }
+      _ => generate_address_sha256(public_key, public_key_hash_vec, ingredients.compressed),
+    },
+    "keccak256" => generate_address_keccak256(
+      public_key,
+      public_key_hash_vec,
+      ingredients.coin_index,
+      get_eip1191_chain_id(ingredients),
+    ),
     "sha256+ripemd160" => {
-      generate_sha256_ripemd160_address(ingredients.coin_index, public_key, public_key_hash_vec)
+      if ingredients.bech32_hrp.is_empty() {
+        generate_sha256_ripemd160_address(
+          ingredients.coin_index,
+          public_key,
+          public_key_hash_vec,
+          ingredients.compressed,
+        )
+      } else {
+        generate_address_bech32_hash160(public_key, &ingredients.bech32_hrp)
+      }
     }
-    #[cfg(feature = "dev")]
-    "ed25519" => crate::dev::generate_ed25519_address(public_key),
+    "ed25519" => generate_ed25519_address(public_key),
+    "blake2b" => match ingredients.coin_index {
+      165 => generate_address_nano(public_key),
+      461 => generate_address_filecoin(public_key),
+      _ => Err(AppError::Custom(format!(
+        "No blake2b address encoder for coin {}",
+        ingredients.coin_index
+      ))),
+    },
     _ => Err(AppError::Custom(format!(
       "Unsupported hash method: {}",
       ingredients.hash
@@ -1162,13 +3474,15 @@ fn encode_private_key(
 ) -> FunctionOutput<String> {
   if ingredients.key_derivation == "ed25519" {
     Ok(bs58::encode(private_key_bytes).into_string())
+  } else if ingredients.key_derivation == "ed25519-blake2b" {
+    Ok(hex::encode_upper(private_key_bytes))
   } else {
     let secret_key = secp256k1::SecretKey::from_byte_array(*private_key_bytes)
       .map_err(|err| AppError::Custom(format!("Invalid SecretKey: {err}")))?;
 
     create_private_key_for_address(
       Some(&secret_key),
-      Some(true), // compressed
+      Some(ingredients.compressed),
       Some(&ingredients.wallet_import_format),
       &ingredients.hash,
       ingredients.coin_index,
@@ -8,6 +8,7 @@ use gtk4 as gtk;
 use include_dir::{Dir, include_dir};
 use sha2::{Digest, Sha256, Sha512};
 use std::io::{self, Write};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 const APP_DEFAULT_BUTTON_HEIGHT: u8 = 24;
 const APP_DEFAULT_BUTTON_WIDTH: u8 = 24;
@@ -70,44 +71,178 @@ pub fn calculate_sha256_and_ripemd160_hash(input: &[u8]) -> Vec<u8> {
   ripemd.finalize().to_vec()
 }
 
-pub fn calculate_hmac_sha512_hash(key: &[u8], data: &[u8]) -> Vec<u8> {
+pub fn calculate_tagged_hash(tag: &str, data: &[u8]) -> Vec<u8> {
+  let tag_hash = Sha256::digest(tag.as_bytes());
+
+  let mut hasher = Sha256::new();
+  hasher.update(tag_hash);
+  hasher.update(tag_hash);
+  hasher.update(data);
+
+  hasher.finalize().to_vec()
+}
+
+pub fn calculate_blake2b_hash(data: &[u8], output_size: usize) -> Vec<u8> {
+  use blake2::digest::{Update, VariableOutput};
+
+  let mut hasher = match blake2::Blake2bVar::new(output_size) {
+    Ok(hasher) => hasher,
+    Err(_) => return Vec::new(),
+  };
+  hasher.update(data);
+
+  let mut output = vec![0u8; output_size];
+  match hasher.finalize_variable(&mut output) {
+    Ok(_) => output,
+    Err(_) => Vec::new(),
+  }
+}
+
+pub fn encode_base32(data: &[u8], alphabet: &[u8; 32], leading_zero_bits: usize) -> String {
+  let total_bits = leading_zero_bits + data.len() * 8;
+  let mut encoded = String::with_capacity(total_bits.div_ceil(5));
+
+  let get_bit = |position: usize| -> u8 {
+    if position < leading_zero_bits || position >= total_bits {
+      0
+    } else {
+      let position = position - leading_zero_bits;
+      (data[position / 8] >> (7 - position % 8)) & 1
+    }
+  };
+
+  for group_start in (0..total_bits).step_by(5) {
+    let value = (0..5).fold(0u8, |value, i| (value << 1) | get_bit(group_start + i));
+    encoded.push(alphabet[value as usize] as char);
+  }
+
+  encoded
+}
+
+pub fn encode_monero_base58(data: &[u8]) -> String {
+  const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+  const ENCODED_BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
+  let mut encoded = String::with_capacity(data.len().div_ceil(8) * 11);
+
+  for block in data.chunks(8) {
+    let mut value = block
+      .iter()
+      .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+    let mut block_encoded = vec![ALPHABET[0]; ENCODED_BLOCK_SIZES[block.len()]];
+
+    for position in (0..block_encoded.len()).rev() {
+      block_encoded[position] = ALPHABET[(value % 58) as usize];
+      value /= 58;
+    }
+
+    encoded.extend(block_encoded.iter().map(|byte| *byte as char));
+  }
+
+  encoded
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn encode_base64(data: &[u8]) -> String {
+  let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+  for chunk in data.chunks(3) {
+    let value = chunk.iter().enumerate().fold(0u32, |value, (i, byte)| {
+      value | (*byte as u32) << (16 - 8 * i)
+    });
+
+    for i in 0..4 {
+      if i <= chunk.len() {
+        encoded.push(BASE64_ALPHABET[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+      } else {
+        encoded.push('=');
+      }
+    }
+  }
+
+  encoded
+}
+
+pub fn decode_base64(encoded: &str) -> Result<Vec<u8>, String> {
+  let encoded: Vec<u8> = encoded
+    .bytes()
+    .filter(|byte| !byte.is_ascii_whitespace())
+    .collect();
+
+  if !encoded.len().is_multiple_of(4) {
+    return Err(format!("Invalid base64 length {}", encoded.len()));
+  }
+
+  let mut decoded = Vec::with_capacity(encoded.len() / 4 * 3);
+
+  for (chunk_index, chunk) in encoded.chunks(4).enumerate() {
+    let padding = chunk.iter().rev().take_while(|byte| **byte == b'=').count();
+
+    if padding > 2 || (padding > 0 && chunk_index != encoded.len() / 4 - 1) {
+      return Err("Invalid base64 padding".to_string());
+    }
+
+    let mut value = 0u32;
+
+    for byte in &chunk[..4 - padding] {
+      let index = BASE64_ALPHABET
+        .iter()
+        .position(|symbol| symbol == byte)
+        .ok_or_else(|| format!("Invalid base64 character {:?}", *byte as char))?;
+      value = (value << 6) | index as u32;
+    }
+
+    value <<= 6 * padding as u32;
+
+    decoded.extend_from_slice(&value.to_be_bytes()[1..4 - padding]);
+  }
+
+  Ok(decoded)
+}
+
+pub fn calculate_hmac_sha512_hash(key: &[u8], data: &[u8]) -> SecretBytes {
   const BLOCK_SIZE: usize = 128;
   const HASH_SIZE: usize = 64;
 
-  let padded_key = if key.len() > BLOCK_SIZE {
-    let mut hasher = Sha512::new();
-    hasher.update(key);
-    let mut hashed_key = vec![0u8; HASH_SIZE];
-    hashed_key.copy_from_slice(&hasher.finalize());
-    hashed_key.resize(BLOCK_SIZE, 0x00);
-    hashed_key
+  let mut padded_key = [0u8; BLOCK_SIZE];
+
+  if key.len() > BLOCK_SIZE {
+    let mut hashed_key = Sha512::digest(key);
+    padded_key[..HASH_SIZE].copy_from_slice(&hashed_key);
+    hashed_key.as_mut_slice().zeroize();
   } else {
-    let mut padded_key = vec![0x00; BLOCK_SIZE];
     padded_key[..key.len()].copy_from_slice(key);
-    padded_key
-  };
-
-  assert_eq!(padded_key.len(), BLOCK_SIZE, "Padded key length mismatch");
+  }
 
-  let mut inner_pad = vec![0x36; BLOCK_SIZE];
-  let mut outer_pad = vec![0x5c; BLOCK_SIZE];
+  let mut inner_pad = [0x36; BLOCK_SIZE];
+  let mut outer_pad = [0x5c; BLOCK_SIZE];
   for (i, &b) in padded_key.iter().enumerate() {
     inner_pad[i] ^= b;
     outer_pad[i] ^= b;
   }
 
   let mut hasher = Sha512::new();
-  hasher.update(&inner_pad);
+  hasher.update(inner_pad);
   hasher.update(data);
-  let inner_hash = hasher.finalize();
+  let mut inner_hash = hasher.finalize();
   let mut hasher = Sha512::new();
-  hasher.update(&outer_pad);
+  hasher.update(outer_pad);
   hasher.update(inner_hash);
-  let final_hash = hasher.finalize().to_vec();
+  let mut final_hash = hasher.finalize();
+
+  let result = SecretBytes::from(final_hash.as_slice());
 
-  assert_eq!(final_hash.len(), HASH_SIZE, "Final hash length mismatch");
+  padded_key.zeroize();
+  inner_pad.zeroize();
+  outer_pad.zeroize();
+  inner_hash.as_mut_slice().zeroize();
+  final_hash.as_mut_slice().zeroize();
 
-  final_hash
+  assert_eq!(result.len(), HASH_SIZE, "Final hash length mismatch");
+
+  result
 }
 
 pub fn calculate_checksum_for_master_keys(data: &[u8]) -> [u8; 4] {
@@ -177,6 +312,305 @@ pub fn is_valid_seed(seed: &str) -> bool {
 
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
 
+pub const BIP32_HARDENED_OFFSET: u32 = 0x8000_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChildNumber {
+  index: u32,
+  hardened: bool,
+}
+
+impl ChildNumber {
+  pub fn new(index: u32, hardened: bool) -> Result<Self, String> {
+    if index >= BIP32_HARDENED_OFFSET {
+      return Err(format!(
+        "Child index {index} is out of range, must be below {BIP32_HARDENED_OFFSET}"
+      ));
+    }
+
+    Ok(Self { index, hardened })
+  }
+
+  pub fn from_raw(value: u32) -> Self {
+    Self {
+      index: value & !BIP32_HARDENED_OFFSET,
+      hardened: value & BIP32_HARDENED_OFFSET != 0,
+    }
+  }
+
+  pub fn index(&self) -> u32 {
+    self.index
+  }
+
+  pub fn is_hardened(&self) -> bool {
+    self.hardened
+  }
+
+  pub fn to_raw(&self) -> u32 {
+    if self.hardened {
+      self.index | BIP32_HARDENED_OFFSET
+    } else {
+      self.index
+    }
+  }
+}
+
+impl std::fmt::Display for ChildNumber {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.hardened {
+      write!(f, "{}'", self.index)
+    } else {
+      write!(f, "{}", self.index)
+    }
+  }
+}
+
+impl std::str::FromStr for ChildNumber {
+  type Err = String;
+
+  fn from_str(segment: &str) -> Result<Self, Self::Err> {
+    let (number, hardened) = match segment.strip_suffix(['\'', 'h', 'H']) {
+      Some(number) => (number, true),
+      None => (segment, false),
+    };
+
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+      return Err(format!("Invalid path segment {segment:?}"));
+    }
+
+    let value: u32 = number
+      .parse()
+      .map_err(|err| format!("Invalid path segment {segment:?}: {err}"))?;
+
+    Self::new(value, hardened)
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath {
+  children: Vec<ChildNumber>,
+}
+
+impl DerivationPath {
+  pub fn master() -> Self {
+    Self::default()
+  }
+
+  pub fn from_children(children: Vec<ChildNumber>) -> Self {
+    Self { children }
+  }
+
+  pub fn children(&self) -> &[ChildNumber] {
+    &self.children
+  }
+
+  pub fn depth(&self) -> usize {
+    self.children.len()
+  }
+
+  pub fn is_master(&self) -> bool {
+    self.children.is_empty()
+  }
+
+  pub fn child(&self, child: ChildNumber) -> Self {
+    let mut children = self.children.clone();
+    children.push(child);
+
+    Self { children }
+  }
+
+  pub fn parent(&self) -> Self {
+    let depth = self.children.len().saturating_sub(1);
+
+    Self {
+      children: self.children[..depth].to_vec(),
+    }
+  }
+
+  // Path below a node that already sits at depth, e.g. an imported account key.
+  // The stripped prefix has to end in the child number stored in that node.
+  pub fn relative_to(&self, depth: u8, child_number: u32) -> Result<Self, String> {
+    let depth = depth as usize;
+
+    if depth > self.children.len() {
+      return Err(format!(
+        "Derivation path {self} is shallower than the key depth {depth}"
+      ));
+    }
+
+    if depth > 0 && self.children[depth - 1].to_raw() != child_number {
+      return Err(format!(
+        "Derivation path {self} does not lead to the key at depth {depth} with child number {}",
+        ChildNumber::from_raw(child_number)
+      ));
+    }
+
+    Ok(Self {
+      children: self.children[depth..].to_vec(),
+    })
+  }
+}
+
+impl std::fmt::Display for DerivationPath {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "m")?;
+
+    for child in &self.children {
+      write!(f, "/{child}")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl std::str::FromStr for DerivationPath {
+  type Err = String;
+
+  fn from_str(path: &str) -> Result<Self, Self::Err> {
+    let path = path.trim();
+
+    let segments = match path {
+      "m" | "M" => return Ok(Self::master()),
+      _ => path
+        .strip_prefix("m/")
+        .or_else(|| path.strip_prefix("M/"))
+        .ok_or_else(|| format!("Derivation path {path:?} must start with 'm/'"))?,
+    };
+
+    let children = segments
+      .split('/')
+      .map(|segment| segment.parse::<ChildNumber>())
+      .collect::<Result<Vec<ChildNumber>, String>>()
+      .map_err(|err| format!("Invalid derivation path {path:?}: {err}"))?;
+
+    Ok(Self { children })
+  }
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+  pub fn new(bytes: Vec<u8>) -> Self {
+    Self(bytes)
+  }
+
+  pub fn as_slice(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl std::ops::Deref for SecretBytes {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+  fn from(bytes: Vec<u8>) -> Self {
+    Self(bytes)
+  }
+}
+
+impl From<&[u8]> for SecretBytes {
+  fn from(bytes: &[u8]) -> Self {
+    Self(bytes.to_vec())
+  }
+}
+
+impl<const N: usize> From<[u8; N]> for SecretBytes {
+  fn from(mut bytes: [u8; N]) -> Self {
+    let secret = Self(bytes.to_vec());
+    bytes.zeroize();
+    secret
+  }
+}
+
+impl std::fmt::Debug for SecretBytes {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SecretBytes([REDACTED; {}])", self.0.len())
+  }
+}
+
+impl Zeroize for SecretBytes {
+  fn zeroize(&mut self) {
+    self.0.zeroize();
+  }
+}
+
+impl Drop for SecretBytes {
+  fn drop(&mut self) {
+    self.zeroize();
+  }
+}
+
+impl ZeroizeOnDrop for SecretBytes {}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+  pub fn new(text: String) -> Self {
+    Self(text)
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl std::ops::Deref for SecretString {
+  type Target = str;
+
+  fn deref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl From<String> for SecretString {
+  fn from(text: String) -> Self {
+    Self(text)
+  }
+}
+
+impl From<&str> for SecretString {
+  fn from(text: &str) -> Self {
+    Self(text.to_string())
+  }
+}
+
+impl std::fmt::Display for SecretString {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl std::fmt::Debug for SecretString {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SecretString([REDACTED; {}])", self.0.len())
+  }
+}
+
+impl Zeroize for SecretString {
+  fn zeroize(&mut self) {
+    self.0.zeroize();
+  }
+}
+
+impl Drop for SecretString {
+  fn drop(&mut self) {
+    self.zeroize();
+  }
+}
+
+impl ZeroizeOnDrop for SecretString {}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
 pub fn get_text_from_resources(file_name: &str) -> String {
   match RES_DIR.get_file(file_name) {
     Some(file) => match std::str::from_utf8(file.contents()) {
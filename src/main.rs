@@ -36,6 +36,8 @@ mod dev;
 mod keys;
 mod os;
 mod sec;
+mod sign;
+mod slip39;
 mod test_vectors;
 
 #[macro_use]
@@ -63,12 +65,10 @@ const VALID_ENTROPY_LENGTHS: [u32; 5] = [128, 160, 192, 224, 256];
 const VALID_BIP_DERIVATIONS: &[&str] = &[
   "32",
   "44",
-  // #[cfg(feature = "dev")]
-  // "49",
-  // #[cfg(feature = "dev")]
-  // "84",
-  // #[cfg(feature = "dev")]
-  // "86",
+  "49",
+  "84",
+  "86",
+  "1852",
   #[cfg(feature = "dev")]
   "Custom",
 ];
@@ -1198,22 +1198,32 @@ impl AppSettings {
 
 #[derive(Clone)]
 struct WalletSettings {
-  entropy_string: Option<String>,
+  entropy_string: Option<qr2m_lib::SecretString>,
   entropy_checksum: Option<String>,
-  mnemonic_words: Option<String>,
-  mnemonic_passphrase: Option<String>,
-  seed: Option<String>,
-  master_private_key: Option<String>,
+  mnemonic_words: Option<qr2m_lib::SecretString>,
+  mnemonic_passphrase: Option<qr2m_lib::SecretString>,
+  seed: Option<qr2m_lib::SecretString>,
+  master_private_key: Option<qr2m_lib::SecretString>,
   master_public_key: Option<String>,
-  master_private_key_bytes: Option<Vec<u8>>,
-  master_chain_code_bytes: Option<Vec<u8>>,
+  master_private_key_bytes: Option<qr2m_lib::SecretBytes>,
+  master_chain_code_bytes: Option<qr2m_lib::SecretBytes>,
   master_public_key_bytes: Option<Vec<u8>>,
+  master_depth: Option<u8>,
+  master_parent_fingerprint: Option<[u8; 4]>,
+  master_child_number: Option<u32>,
+  private_header: Option<String>,
+  public_header: Option<String>,
   coin_index: Option<u32>,
   coin_name: Option<String>,
   wallet_import_format: Option<String>,
   public_key_hash: Option<String>,
+  script_hash: Option<String>,
+  bech32_hrp: Option<String>,
   key_derivation: Option<String>,
   hash: Option<String>,
+  chain_id: Option<u64>,
+  eip1191: Option<bool>,
+  evm: Option<bool>,
 }
 
 impl WalletSettings {
@@ -1229,12 +1239,22 @@ impl WalletSettings {
       master_private_key_bytes: None,
       master_chain_code_bytes: None,
       master_public_key_bytes: None,
+      master_depth: None,
+      master_parent_fingerprint: None,
+      master_child_number: None,
+      private_header: None,
+      public_header: None,
       coin_index: None,
       coin_name: None,
       wallet_import_format: None,
       public_key_hash: None,
+      script_hash: None,
+      bech32_hrp: None,
       key_derivation: None,
       hash: None,
+      chain_id: None,
+      eip1191: None,
+      evm: None,
     }
   }
 }
@@ -1248,7 +1268,9 @@ struct CryptoAddresses {
   derivation_path: Option<String>,
   address: Option<String>,
   public_key: Option<String>,
-  private_key: Option<String>,
+  private_key: Option<qr2m_lib::SecretString>,
+  private_view_key: Option<qr2m_lib::SecretString>,
+  mnemonic: Option<qr2m_lib::SecretString>,
 }
 
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
@@ -1595,6 +1617,8 @@ mod address_implementation {
     pub address: std::cell::RefCell<String>,
     pub public_key: std::cell::RefCell<String>,
     pub private_key: std::cell::RefCell<String>,
+    pub private_view_key: std::cell::RefCell<String>,
+    pub mnemonic: std::cell::RefCell<String>,
   }
 
   #[glib::object_subclass]
@@ -1635,6 +1659,14 @@ mod address_implementation {
             .blurb("Private key")
             .flags(glib::ParamFlags::READWRITE)
             .build(),
+          glib::ParamSpecString::builder("private-view-key")
+            .blurb("Private view key")
+            .flags(glib::ParamFlags::READWRITE)
+            .build(),
+          glib::ParamSpecString::builder("mnemonic")
+            .blurb("Mnemonic")
+            .flags(glib::ParamFlags::READWRITE)
+            .build(),
         ]
       })
     }
@@ -1647,6 +1679,8 @@ mod address_implementation {
         "address" => *self.address.borrow_mut() = value.get().unwrap_or_default(),
         "public-key" => *self.public_key.borrow_mut() = value.get().unwrap_or_default(),
         "private-key" => *self.private_key.borrow_mut() = value.get().unwrap_or_default(),
+        "private-view-key" => *self.private_view_key.borrow_mut() = value.get().unwrap_or_default(),
+        "mnemonic" => *self.mnemonic.borrow_mut() = value.get().unwrap_or_default(),
         _ => {
           #[cfg(debug_assertions)]
           eprintln!("Unknown property");
@@ -1662,6 +1696,8 @@ mod address_implementation {
         "address" => self.address.borrow().to_value(),
         "public-key" => self.public_key.borrow().to_value(),
         "private-key" => self.private_key.borrow().to_value(),
+        "private-view-key" => self.private_view_key.borrow().to_value(),
+        "mnemonic" => self.mnemonic.borrow().to_value(),
         _ => unimplemented!(),
       }
     }
@@ -1680,6 +1716,8 @@ impl AddressDatabase {
     address: &str,
     public_key: &str,
     private_key: &str,
+    private_view_key: &str,
+    mnemonic: &str,
   ) -> Self {
     let builder = glib::Object::builder::<AddressDatabase>()
       .property("id", id)
@@ -1687,7 +1725,9 @@ impl AddressDatabase {
       .property("path", path)
       .property("address", address)
       .property("public-key", public_key)
-      .property("private-key", private_key);
+      .property("private-key", private_key)
+      .property("private-view-key", private_view_key)
+      .property("mnemonic", mnemonic);
 
     builder.build()
   }
@@ -2396,6 +2436,11 @@ fn create_main_window(
   import_entropy_button.set_tooltip_text(Some(&t!("UI.button.import")));
   entropy_inner_box.append(&import_entropy_button);
 
+  let slip39_entropy_button = gtk::Button::new();
+  slip39_entropy_button.set_icon_name("network-workgroup");
+  slip39_entropy_button.set_tooltip_text(Some(&t!("UI.main.seed.slip39")));
+  entropy_inner_box.append(&slip39_entropy_button);
+
   entropy_frame.set_child(Some(&entropy_inner_box));
   entropy_box.append(&entropy_frame);
 
@@ -2413,6 +2458,17 @@ fn create_main_window(
     }
   ));
 
+  slip39_entropy_button.connect_clicked(clone!(
+    #[weak]
+    entropy_text,
+    move |_| {
+      let buffer = entropy_text.buffer();
+      let entropy = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      create_slip39_window(entropy.to_string().into());
+    }
+  ));
+
   let mnemonic_words_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
   let mnemonic_words_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.words")));
   let mnemonic_words_text = gtk::TextView::new();
@@ -2436,6 +2492,21 @@ fn create_main_window(
   import_mnemonic_button.set_tooltip_text(Some(&t!("UI.button.import")));
   mnemonic_inner_box.append(&import_mnemonic_button);
 
+  let validate_mnemonic_button = gtk::Button::new();
+  validate_mnemonic_button.set_icon_name("tools-check-spelling");
+  validate_mnemonic_button.set_tooltip_text(Some(&t!("UI.main.seed.mnemonic.validate")));
+  mnemonic_inner_box.append(&validate_mnemonic_button);
+
+  let recover_mnemonic_button = gtk::Button::new();
+  recover_mnemonic_button.set_icon_name("edit-find");
+  recover_mnemonic_button.set_tooltip_text(Some(&t!("UI.main.seed.mnemonic.recover")));
+  mnemonic_inner_box.append(&recover_mnemonic_button);
+
+  let electrum_mnemonic_button = gtk::Button::new();
+  electrum_mnemonic_button.set_icon_name("network-server");
+  electrum_mnemonic_button.set_tooltip_text(Some(&t!("UI.main.seed.mnemonic.electrum")));
+  mnemonic_inner_box.append(&electrum_mnemonic_button);
+
   mnemonic_words_frame.set_child(Some(&mnemonic_inner_box));
   mnemonic_words_box.append(&mnemonic_words_frame);
 
@@ -2453,6 +2524,49 @@ fn create_main_window(
     }
   ));
 
+  validate_mnemonic_button.connect_clicked(clone!(
+    #[weak]
+    mnemonic_words_text,
+    move |_| {
+      let buffer = mnemonic_words_text.buffer();
+      let mnemonic = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      create_mnemonic_validator_window(mnemonic.to_string().into());
+    }
+  ));
+
+  recover_mnemonic_button.connect_clicked(clone!(
+    #[weak]
+    mnemonic_words_text,
+    #[weak]
+    mnemonic_passphrase_text,
+    move |_| {
+      let buffer = mnemonic_words_text.buffer();
+      let mnemonic = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      create_mnemonic_recovery_window(
+        mnemonic.to_string().into(),
+        mnemonic_passphrase_text.text().to_string().into(),
+      );
+    }
+  ));
+
+  electrum_mnemonic_button.connect_clicked(clone!(
+    #[weak]
+    mnemonic_words_text,
+    #[weak]
+    mnemonic_passphrase_text,
+    move |_| {
+      let buffer = mnemonic_words_text.buffer();
+      let mnemonic = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      create_electrum_window(
+        mnemonic.to_string().into(),
+        mnemonic_passphrase_text.text().to_string().into(),
+      );
+    }
+  ));
+
   let seed_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
   let seed_frame = gtk::Frame::new(Some(&t!("UI.main.seed")));
   let seed_text = gtk::TextView::new();
@@ -3144,6 +3258,11 @@ fn create_main_window(
       &t!("UI.main.database.column.script").to_string(),
     ),
     #[cfg(feature = "dev")]
+    (
+      "bech32-hrp",
+      &t!("UI.main.database.column.hrp").to_string(),
+    ),
+    #[cfg(feature = "dev")]
     (
       "wallet-import-format",
       &t!("UI.main.database.column.wif").to_string(),
@@ -3151,6 +3270,11 @@ fn create_main_window(
     ("evm", &t!("UI.main.database.column.evm").to_string()),
     ("ucid", &t!("UI.main.database.column.UCID").to_string()),
     ("cmc-top", &t!("UI.main.database.column.cmc").to_string()),
+    #[cfg(feature = "dev")]
+    (
+      "chain-id",
+      &t!("UI.main.database.column.chain_id").to_string(),
+    ),
   ];
 
   let create_string_factory = |property: &str| {
@@ -3231,12 +3355,18 @@ fn create_main_window(
   let generate_master_keys_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
   let generate_master_keys_button = gtk::Button::new();
   let delete_master_keys_button = gtk::Button::new();
+  let sign_psbt_button = gtk::Button::new();
+  let bip85_button = gtk::Button::new();
 
   generate_master_keys_button.set_label(&t!("UI.main.coin.generate"));
   delete_master_keys_button.set_label(&t!("UI.main.coin.delete"));
+  sign_psbt_button.set_label(&t!("UI.main.coin.psbt"));
+  bip85_button.set_label(&t!("UI.main.coin.bip85"));
   generate_master_keys_box.set_halign(gtk::Align::Center);
   generate_master_keys_box.append(&generate_master_keys_button);
   generate_master_keys_box.append(&delete_master_keys_button);
+  generate_master_keys_box.append(&sign_psbt_button);
+  generate_master_keys_box.append(&bip85_button);
   coin_main_content_box.append(&generate_master_keys_box);
 
   // Master private keys entries
@@ -3290,6 +3420,11 @@ fn create_main_window(
   copy_master_xpub_button.set_tooltip_text(Some(&t!("UI.button.copy")));
   master_xpub_inner_box.append(&copy_master_xpub_button);
 
+  let import_master_key_button = gtk::Button::new();
+  import_master_key_button.set_icon_name("document-revert");
+  import_master_key_button.set_tooltip_text(Some(&t!("UI.button.import")));
+  master_xpub_inner_box.append(&import_master_key_button);
+
   master_xpub_frame.set_child(Some(&master_xpub_inner_box));
   master_keys_box.append(&master_xpub_frame);
 
@@ -3417,6 +3552,78 @@ fn create_main_window(
   derivation_label_text.set_css_classes(&["h1"]);
   derivation_label_text.buffer().set_text(&default_bip_label);
 
+  // Account extended keys
+  let account_keys_box = gtk::Box::new(gtk::Orientation::Horizontal, 20);
+  let account_xprv_frame = gtk::Frame::new(Some(&t!("UI.main.address.account.priv")));
+  account_xprv_frame.set_hexpand(true);
+
+  let account_private_key_text = gtk::TextView::new();
+  account_private_key_text.set_editable(false);
+  account_private_key_text.set_wrap_mode(gtk::WrapMode::Char);
+  account_private_key_text.set_left_margin(5);
+  account_private_key_text.set_top_margin(5);
+  account_private_key_text.set_hexpand(true);
+
+  let account_xprv_inner_box = gtk::Box::new(gtk::Orientation::Horizontal, 5);
+  account_xprv_inner_box.append(&account_private_key_text);
+
+  let copy_account_xprv_button = gtk::Button::new();
+  copy_account_xprv_button.set_icon_name("edit-copy");
+  copy_account_xprv_button.set_tooltip_text(Some(&t!("UI.button.copy")));
+  account_xprv_inner_box.append(&copy_account_xprv_button);
+
+  account_xprv_frame.set_child(Some(&account_xprv_inner_box));
+  account_keys_box.append(&account_xprv_frame);
+
+  copy_account_xprv_button.connect_clicked(clone!(
+    #[weak]
+    account_private_key_text,
+    move |button| {
+      let buffer = account_private_key_text.buffer();
+      let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+      if !text.is_empty() {
+        let display = button.display();
+        let clipboard = display.clipboard();
+        clipboard.set_text(&text);
+      };
+    }
+  ));
+
+  let account_xpub_frame = gtk::Frame::new(Some(&t!("UI.main.address.account.pub")));
+  account_xpub_frame.set_hexpand(true);
+
+  let account_public_key_text = gtk::TextView::new();
+  account_public_key_text.set_editable(false);
+  account_public_key_text.set_wrap_mode(gtk::WrapMode::Char);
+  account_public_key_text.set_left_margin(5);
+  account_public_key_text.set_top_margin(5);
+  account_public_key_text.set_hexpand(true);
+
+  let account_xpub_inner_box = gtk::Box::new(gtk::Orientation::Horizontal, 5);
+  account_xpub_inner_box.append(&account_public_key_text);
+
+  let copy_account_xpub_button = gtk::Button::new();
+  copy_account_xpub_button.set_icon_name("edit-copy");
+  copy_account_xpub_button.set_tooltip_text(Some(&t!("UI.button.copy")));
+  account_xpub_inner_box.append(&copy_account_xpub_button);
+
+  account_xpub_frame.set_child(Some(&account_xpub_inner_box));
+  account_keys_box.append(&account_xpub_frame);
+
+  copy_account_xpub_button.connect_clicked(clone!(
+    #[weak]
+    account_public_key_text,
+    move |button| {
+      let buffer = account_public_key_text.buffer();
+      let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+      if !text.is_empty() {
+        let display = button.display();
+        let clipboard = display.clipboard();
+        clipboard.set_text(&text);
+      };
+    }
+  ));
+
   let address_generation_buttons_box = gtk::Box::new(gtk::Orientation::Horizontal, 20);
   address_generation_buttons_box.set_halign(gtk::Align::Center);
 
@@ -3494,6 +3701,122 @@ fn create_main_window(
         .downcast_ref::<gtk::ListItem>()
         .expect("Needs to be ListItem");
       let label = gtk::Label::new(None);
+
+      let sign_message_button = gtk::Button::with_label(&t!("UI.main.address.sign"));
+      sign_message_button.add_css_class("flat");
+      let sign_transaction_button = gtk::Button::with_label(&t!("UI.main.address.transaction"));
+      sign_transaction_button.add_css_class("flat");
+      let monero_keys_button = gtk::Button::with_label(&t!("UI.main.address.monero"));
+      monero_keys_button.add_css_class("flat");
+      let address_popover_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+      address_popover_box.append(&sign_message_button);
+      address_popover_box.append(&sign_transaction_button);
+      address_popover_box.append(&monero_keys_button);
+      let address_popover = gtk::Popover::new();
+      address_popover.set_child(Some(&address_popover_box));
+      address_popover.set_parent(&label);
+
+      sign_message_button.connect_clicked(clone!(
+        #[weak]
+        list_item,
+        #[weak]
+        address_popover,
+        move |_| {
+          address_popover.popdown();
+
+          if let Some(entry) = list_item
+            .item()
+            .and_then(|item| item.downcast::<AddressDatabase>().ok())
+          {
+            create_sign_message_window(keys::Address {
+              address: entry.property::<String>("address"),
+              public_key: entry.property::<String>("public-key"),
+              private_key: entry.property::<String>("private-key").into(),
+              private_view_key: None,
+              mnemonic: None,
+            });
+          }
+        }
+      ));
+
+      monero_keys_button.connect_clicked(clone!(
+        #[weak]
+        list_item,
+        #[weak]
+        address_popover,
+        move |_| {
+          address_popover.popdown();
+
+          if let Some(entry) = list_item
+            .item()
+            .and_then(|item| item.downcast::<AddressDatabase>().ok())
+          {
+            create_monero_keys_window(keys::Address {
+              address: entry.property::<String>("address"),
+              public_key: entry.property::<String>("public-key"),
+              private_key: entry.property::<String>("private-key").into(),
+              private_view_key: Some(entry.property::<String>("private-view-key").into()),
+              mnemonic: Some(entry.property::<String>("mnemonic").into()),
+            });
+          }
+        }
+      ));
+
+      sign_transaction_button.connect_clicked(clone!(
+        #[weak]
+        list_item,
+        #[weak]
+        address_popover,
+        move |_| {
+          address_popover.popdown();
+
+          if let Some(entry) = list_item
+            .item()
+            .and_then(|item| item.downcast::<AddressDatabase>().ok())
+          {
+            create_eth_transaction_window(
+              entry.property::<String>("address"),
+              entry.property::<String>("path"),
+            );
+          }
+        }
+      ));
+
+      let right_click = gtk::GestureClick::new();
+      right_click.set_button(gtk::gdk::BUTTON_SECONDARY);
+      right_click.connect_pressed(clone!(
+        #[weak]
+        list_item,
+        #[weak]
+        address_popover,
+        #[weak]
+        monero_keys_button,
+        #[weak]
+        sign_transaction_button,
+        move |_gesture, _n_press, x, y| {
+          let has_monero_keys = list_item
+            .item()
+            .and_then(|item| item.downcast::<AddressDatabase>().ok())
+            .is_some_and(|entry| !entry.property::<String>("mnemonic").is_empty());
+          monero_keys_button.set_visible(has_monero_keys);
+
+          let is_evm_coin = WALLET_SETTINGS.lock().unwrap().evm.unwrap_or_default();
+          sign_transaction_button.set_visible(is_evm_coin);
+
+          let pointer = gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1);
+          address_popover.set_pointing_to(Some(&pointer));
+          address_popover.popup();
+        }
+      ));
+
+      label.add_controller(right_click);
+      label.connect_destroy(clone!(
+        #[weak]
+        address_popover,
+        move |_| {
+          address_popover.unparent();
+        }
+      ));
       list_item.set_child(Some(&label));
     });
 
@@ -3579,6 +3902,18 @@ fn create_main_window(
   address_options_hardened_address_frame.set_child(Some(&address_options_hardened_address_box));
   address_options_content.append(&address_options_hardened_address_frame);
 
+  // Compressed public key
+  let address_options_compressed_frame =
+    gtk::Frame::new(Some(&t!("UI.main.address.options.compressed")));
+  let address_options_compressed_box = gtk::Box::new(gtk::Orientation::Horizontal, 20);
+  let address_options_compressed_checkbox = gtk::CheckButton::new();
+
+  address_options_compressed_checkbox.set_active(true);
+  address_options_compressed_box.set_halign(gtk4::Align::Center);
+  address_options_compressed_box.append(&address_options_compressed_checkbox);
+  address_options_compressed_frame.set_child(Some(&address_options_compressed_box));
+  address_options_content.append(&address_options_compressed_frame);
+
   // JUMP: Speed controller
   let address_speed_controller_frame =
     gtk::Frame::new(Some(&t!("UI.main.address.stats.controller")));
@@ -3696,6 +4031,7 @@ fn create_main_window(
   address_scrolled_window.set_child(Some(&address_treeview));
   main_address_box.append(&derivation_box);
   main_address_box.append(&derivation_label_box);
+  main_address_box.append(&account_keys_box);
   main_address_box.append(&address_generation_buttons_box);
   main_address_box.append(&address_treeview_box);
   main_address_box.append(&address_options_box);
@@ -3909,104 +4245,508 @@ fn create_main_window(
     master_private_key_text,
     #[weak]
     master_public_key_text,
+    #[weak]
+    account_private_key_text,
+    #[weak]
+    account_public_key_text,
     move |_| {
       master_private_key_text.buffer().set_text("");
       master_public_key_text.buffer().set_text("");
+      account_private_key_text.buffer().set_text("");
+      account_public_key_text.buffer().set_text("");
 
       let mut wallet_settings = WALLET_SETTINGS.lock().unwrap();
       wallet_settings.master_chain_code_bytes = None;
       wallet_settings.master_private_key_bytes = None;
       wallet_settings.master_public_key_bytes = None;
+      wallet_settings.master_depth = None;
+      wallet_settings.master_parent_fingerprint = None;
+      wallet_settings.master_child_number = None;
     }
   ));
 
-  // JUMP: Action: Generate Master Keys button
-  generate_master_keys_button.connect_clicked(clone!(
+  sign_psbt_button.connect_clicked(clone!(
     #[strong]
-    coin_entry,
+    app_messages_state,
+    move |_| {
+      let master_keys = {
+        let wallet_settings = WALLET_SETTINGS.lock().unwrap();
+
+        match (
+          &wallet_settings.master_private_key_bytes,
+          &wallet_settings.master_chain_code_bytes,
+          wallet_settings.key_derivation.as_deref(),
+          wallet_settings.master_depth.unwrap_or_default(),
+        ) {
+          (Some(private_key), Some(chain_code), Some("secp256k1"), 0)
+            if !private_key.is_empty() =>
+          {
+            Some((private_key.clone(), chain_code.clone()))
+          }
+          _ => None,
+        }
+      };
+
+      match master_keys {
+        Some((private_key, chain_code)) => create_psbt_window(private_key, chain_code),
+        None => {
+          let lock_app_messages = app_messages_state.borrow();
+          match lock_app_messages
+            .queue_message(t!("error.psbt.master").to_string(), gtk::MessageType::Error)
+          {
+            Ok(_) => {}
+            Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+          };
+        }
+      }
+    }
+  ));
+
+  bip85_button.connect_clicked(clone!(
+    #[strong]
+    app_messages_state,
+    move |_| {
+      let master_keys = {
+        let wallet_settings = WALLET_SETTINGS.lock().unwrap();
+
+        match (
+          &wallet_settings.master_private_key_bytes,
+          &wallet_settings.master_chain_code_bytes,
+          wallet_settings.key_derivation.as_deref(),
+          wallet_settings.master_depth.unwrap_or_default(),
+        ) {
+          (Some(private_key), Some(chain_code), Some("secp256k1"), 0)
+            if !private_key.is_empty() =>
+          {
+            Some((private_key.clone(), chain_code.clone()))
+          }
+          _ => None,
+        }
+      };
+
+      match master_keys {
+        Some((private_key, chain_code)) => create_bip85_window(private_key, chain_code),
+        None => {
+          let lock_app_messages = app_messages_state.borrow();
+          match lock_app_messages.queue_message(
+            t!("error.bip85.master").to_string(),
+            gtk::MessageType::Error,
+          ) {
+            Ok(_) => {}
+            Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+          };
+        }
+      }
+    }
+  ));
+
+  import_master_key_button.connect_clicked(clone!(
     #[strong]
     app_messages_state,
     #[strong]
+    coin_entry,
+    #[strong]
     coin_selection_model,
     #[weak]
-    seed_text,
-    #[weak]
     master_private_key_text,
     #[weak]
     master_public_key_text,
+    #[weak]
+    account_private_key_text,
+    #[weak]
+    account_public_key_text,
+    #[weak]
+    address_options_hardened_address_checkbox,
+    #[weak]
+    bip_dropdown,
     move |_| {
-      let buffer = seed_text.buffer();
-      let start_iter = buffer.start_iter();
-      let end_iter = buffer.end_iter();
-      let text = buffer.text(&start_iter, &end_iter, false);
+      let import_extended_key_dialog = gtk::ApplicationWindow::builder()
+        .title("Import Extended Key")
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Center)
+        .height_request(150)
+        .width_request(500)
+        .resizable(false)
+        .modal(true)
+        .build();
 
-      let single_selection = coin_selection_model
-        .clone()
-        .downcast::<gtk::SingleSelection>()
-        .expect("The selection model is not a SingleSelection");
+      let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+      main_dialog_box.set_margin_bottom(10);
+      main_dialog_box.set_margin_top(10);
+      main_dialog_box.set_margin_start(10);
+      main_dialog_box.set_margin_end(10);
+      main_dialog_box.set_hexpand(true);
+      main_dialog_box.set_vexpand(true);
 
-      if !text.is_empty() {
-        if let Some(model) = single_selection.selected_item() {
-          let _status = model.property::<String>("status");
-          let coin_index = model.property::<u32>("coin-index");
-          let _coin_symbol = model.property::<String>("coin-symbol");
-          let coin_name = model.property::<String>("coin-name");
-          let key_derivation = model.property::<String>("key-derivation");
-          let hash = model.property::<String>("hash");
-          let private_header = model.property::<String>("private-header");
-          let public_header = model.property::<String>("public-header");
-          let public_key_hash = model.property::<String>("public-key-hash");
-          let _script_hash = model.property::<String>("script-hash");
-          let wallet_import_format = model.property::<String>("wallet-import-format");
-          let _evm = model.property::<String>("evm");
-          let _ucid = model.property::<String>("ucid");
-          let _cmc_top = model.property::<String>("cmc-top");
+      let dialog_frame = gtk::Frame::new(Some(&t!("UI.main.coin.keys.pub")));
 
-          master_private_key_text.buffer().set_text("");
-          master_public_key_text.buffer().set_text("");
+      let extended_key_import_text = gtk::TextView::new();
+      extended_key_import_text.set_wrap_mode(gtk::WrapMode::Char);
+      extended_key_import_text.set_hexpand(true);
+      extended_key_import_text.set_vexpand(true);
 
-          #[cfg(debug_assertions)]
-          {
-            println!("\n#### Coin info ####");
-            println!("\t- status: {_status}");
-            println!("\t- index: {coin_index}");
-            println!("\t- coin_symbol: {_coin_symbol}");
-            println!("\t- coin_name: {coin_name}");
-            println!("\t- key_derivation: {key_derivation}");
-            println!("\t- hash: {hash}");
-            println!("\t- private_header: {private_header}");
-            println!("\t- public_header: {public_header}");
-            println!("\t- public_key_hash: {public_key_hash}");
-            println!("\t- script_hash: {_script_hash}");
-            println!("\t- wallet_import_format: {wallet_import_format}");
-            println!("\t- EVM: {_evm}");
-            println!("\t- UCID: {_ucid}");
-            println!("\t- cmc_top: {_cmc_top}");
-          }
+      dialog_frame.set_child(Some(&extended_key_import_text));
 
-          let buffer = seed_text.buffer();
-          let start_iter = buffer.start_iter();
-          let end_iter = buffer.end_iter();
-          let seed_string = buffer.text(&start_iter, &end_iter, true);
+      let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+      button_box.set_hexpand(true);
+      button_box.set_halign(gtk::Align::Center);
 
-          if key_derivation == "secp256k1" {
-            match keys::generate_master_keys_secp256k1(
-              &seed_string,
-              Some(&private_header),
-              Some(&public_header),
-            ) {
-              Ok(_) => {
-                d3bug("<<< generate_master_keys_secp256k1", "debug");
-              }
-              Err(err) => d3bug(
-                &format!("generate_master_keys_secp256k1: \n{err:?}"),
-                "error",
-              ),
-            };
-          } else {
-            #[cfg(feature = "dev")]
-            dev::generate_master_keys_ed25519(&seed_string);
-          };
+      let import_button = gtk::Button::with_label("Import");
+      let close_button = gtk::Button::with_label("Close");
+      button_box.append(&import_button);
+      button_box.append(&close_button);
+
+      main_dialog_box.append(&dialog_frame);
+      main_dialog_box.append(&button_box);
+
+      import_extended_key_dialog.set_child(Some(&main_dialog_box));
+
+      import_button.connect_clicked(clone!(
+        #[strong]
+        app_messages_state,
+        #[strong]
+        coin_entry,
+        #[strong]
+        coin_selection_model,
+        #[weak]
+        master_private_key_text,
+        #[weak]
+        master_public_key_text,
+        #[weak]
+        account_private_key_text,
+        #[weak]
+        account_public_key_text,
+        #[weak]
+        address_options_hardened_address_checkbox,
+        #[weak]
+        bip_dropdown,
+        #[weak]
+        import_extended_key_dialog,
+        move |_| {
+          let buffer = extended_key_import_text.buffer();
+          let text = buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+            .trim()
+            .to_string();
+
+          let (header, node) = match keys::decode_extended_key(&text) {
+            Ok(value) => value,
+            Err(err) => {
+              eprintln!("\t Imported extended key invalid: {err:?}");
+
+              let lock_app_messages = app_messages_state.borrow();
+              match lock_app_messages.queue_message(
+                t!("error.master.import.invalid").to_string(),
+                gtk::MessageType::Error,
+              ) {
+                Ok(_) => {}
+                Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+              };
+
+              import_extended_key_dialog.close();
+              return;
+            }
+          };
+
+          let single_selection = coin_selection_model
+            .clone()
+            .downcast::<gtk::SingleSelection>()
+            .expect("The selection model is not a SingleSelection");
+
+          let selected_coin_index = single_selection
+            .selected_item()
+            .map(|item| item.property::<u32>("coin-index"));
+
+          let header_lookup =
+            coin_db::find_coin_by_extended_key_header(header, selected_coin_index);
+
+          let detected_coin = match header_lookup {
+            Ok(Some((coin, private_header, public_header, bip))) => {
+              if (header == private_header) != node.private_key.is_some() {
+                eprintln!("\t Imported extended key header {header:#010x} does not match key data");
+
+                let lock_app_messages = app_messages_state.borrow();
+                match lock_app_messages.queue_message(
+                  t!("error.master.import.invalid").to_string(),
+                  gtk::MessageType::Error,
+                ) {
+                  Ok(_) => {}
+                  Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+                };
+
+                import_extended_key_dialog.close();
+                return;
+              }
+
+              let coin_index = coin.property::<u32>("coin-index");
+
+              for position in 0..single_selection.n_items() {
+                if let Some(item) = single_selection.item(position)
+                  && item.property::<u32>("coin-index") == coin_index
+                {
+                  single_selection.set_selected(position);
+                  break;
+                }
+              }
+
+              // ypub/zpub style headers also tell which address type the key was exported for
+              if let Some(bip) = bip
+                && let Some(position) = VALID_BIP_DERIVATIONS
+                  .iter()
+                  .position(|value| *value == bip.to_string())
+              {
+                bip_dropdown.set_selected(position as u32);
+              }
+
+              Some((
+                coin.upcast::<glib::Object>(),
+                Some((private_header, public_header)),
+              ))
+            }
+            Ok(None) => {
+              d3bug(
+                &format!("No coin found for extended key header {header:#010x}"),
+                "warning",
+              );
+              single_selection.selected_item().map(|item| (item, None))
+            }
+            Err(err) => {
+              d3bug(
+                &format!("find_coin_by_extended_key_header: {err:?}"),
+                "error",
+              );
+              single_selection.selected_item().map(|item| (item, None))
+            }
+          };
+
+          let (model, detected_headers) = match detected_coin {
+            Some((model, detected_headers))
+              if model.property::<String>("key-derivation") == "secp256k1" =>
+            {
+              (model, detected_headers)
+            }
+            _ => {
+              let lock_app_messages = app_messages_state.borrow();
+              match lock_app_messages.queue_message(
+                t!("error.master.import.coin").to_string(),
+                gtk::MessageType::Warning,
+              ) {
+                Ok(_) => {}
+                Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+              };
+
+              import_extended_key_dialog.close();
+              return;
+            }
+          };
+
+          #[cfg(debug_assertions)]
+          {
+            println!("Imported extended key: {text}");
+            println!("\t- header: {header:#010x}");
+            println!("\t- depth: {}", node.depth);
+            println!("\t- parent fingerprint: {}", hex::encode(node.parent_fingerprint));
+            println!("\t- child number: {}", node.child_number);
+          }
+
+          let coin_index = model.property::<u32>("coin-index");
+          let (private_header, public_header) = match detected_headers {
+            Some((private_header, public_header)) => (
+              format!("0x{private_header:08X}"),
+              format!("0x{public_header:08X}"),
+            ),
+            None => (
+              model.property::<String>("private-header"),
+              model.property::<String>("public-header"),
+            ),
+          };
+
+          let master_private_key = node.private_key.map(|_| text.clone());
+          let master_public_key = if node.private_key.is_some() {
+            match keys::parse_extended_key_header(Some(&public_header), "0x0488B21E") {
+              Ok(public_header) => node.serialize_public(public_header),
+              Err(err) => {
+                d3bug(&format!("parse_extended_key_header: {err:?}"), "error");
+                String::new()
+              }
+            }
+          } else {
+            text.clone()
+          };
+
+          let mut wallet_settings = WALLET_SETTINGS.lock().unwrap();
+          wallet_settings.master_private_key = master_private_key.clone().map(Into::into);
+          wallet_settings.master_public_key = Some(master_public_key.clone());
+          wallet_settings.master_private_key_bytes = node.private_key.map(qr2m_lib::SecretBytes::from);
+          wallet_settings.master_chain_code_bytes = Some(node.chain_code.into());
+          wallet_settings.master_public_key_bytes = Some(node.public_key.to_vec());
+          wallet_settings.master_depth = Some(node.depth);
+          wallet_settings.master_parent_fingerprint = Some(node.parent_fingerprint);
+          wallet_settings.master_child_number = Some(node.child_number);
+          wallet_settings.private_header = Some(private_header);
+          wallet_settings.public_header = Some(public_header);
+          wallet_settings.public_key_hash = Some(model.property::<String>("public-key-hash"));
+          wallet_settings.script_hash = Some(model.property::<String>("script-hash"));
+          wallet_settings.bech32_hrp = Some(model.property::<String>("bech32-hrp"));
+          wallet_settings.wallet_import_format =
+            Some(model.property::<String>("wallet-import-format"));
+          wallet_settings.key_derivation = Some(model.property::<String>("key-derivation"));
+          wallet_settings.hash = Some(model.property::<String>("hash"));
+          wallet_settings.chain_id = model.property::<String>("chain-id").parse().ok();
+          wallet_settings.eip1191 = Some(model.property::<String>("eip1191") == "TRUE");
+          wallet_settings.evm = Some(model.property::<String>("evm") == "TRUE");
+          wallet_settings.coin_index = Some(coin_index);
+          wallet_settings.coin_name = Some(model.property::<String>("coin-name"));
+
+          coin_entry.set_text(&coin_index.to_string());
+          master_private_key_text
+            .buffer()
+            .set_text(&master_private_key.unwrap_or_default());
+          master_public_key_text.buffer().set_text(&master_public_key);
+          account_private_key_text.buffer().set_text("");
+          account_public_key_text.buffer().set_text("");
+
+          if node.private_key.is_none() {
+            address_options_hardened_address_checkbox.set_active(false);
+          }
+
+          import_extended_key_dialog.close();
+        }
+      ));
+
+      close_button.connect_clicked(clone!(
+        #[weak]
+        import_extended_key_dialog,
+        move |_| {
+          import_extended_key_dialog.close();
+        }
+      ));
+
+      import_extended_key_dialog.present();
+    }
+  ));
+
+  // JUMP: Action: Generate Master Keys button
+  generate_master_keys_button.connect_clicked(clone!(
+    #[strong]
+    coin_entry,
+    #[strong]
+    app_messages_state,
+    #[strong]
+    coin_selection_model,
+    #[weak]
+    seed_text,
+    #[weak]
+    master_private_key_text,
+    #[weak]
+    master_public_key_text,
+    move |_| {
+      let buffer = seed_text.buffer();
+      let start_iter = buffer.start_iter();
+      let end_iter = buffer.end_iter();
+      let text = buffer.text(&start_iter, &end_iter, false);
+
+      let single_selection = coin_selection_model
+        .clone()
+        .downcast::<gtk::SingleSelection>()
+        .expect("The selection model is not a SingleSelection");
+
+      if !text.is_empty() {
+        if let Some(model) = single_selection.selected_item() {
+          let _status = model.property::<String>("status");
+          let coin_index = model.property::<u32>("coin-index");
+          let _coin_symbol = model.property::<String>("coin-symbol");
+          let coin_name = model.property::<String>("coin-name");
+          let key_derivation = model.property::<String>("key-derivation");
+          let hash = model.property::<String>("hash");
+          let private_header = model.property::<String>("private-header");
+          let public_header = model.property::<String>("public-header");
+          let public_key_hash = model.property::<String>("public-key-hash");
+          let script_hash = model.property::<String>("script-hash");
+          let bech32_hrp = model.property::<String>("bech32-hrp");
+          let wallet_import_format = model.property::<String>("wallet-import-format");
+          let evm = model.property::<String>("evm");
+          let _ucid = model.property::<String>("ucid");
+          let _cmc_top = model.property::<String>("cmc-top");
+          let chain_id = model.property::<String>("chain-id");
+          let eip1191 = model.property::<String>("eip1191");
+
+          master_private_key_text.buffer().set_text("");
+          master_public_key_text.buffer().set_text("");
+
+          #[cfg(debug_assertions)]
+          {
+            println!("\n#### Coin info ####");
+            println!("\t- status: {_status}");
+            println!("\t- index: {coin_index}");
+            println!("\t- coin_symbol: {_coin_symbol}");
+            println!("\t- coin_name: {coin_name}");
+            println!("\t- key_derivation: {key_derivation}");
+            println!("\t- hash: {hash}");
+            println!("\t- private_header: {private_header}");
+            println!("\t- public_header: {public_header}");
+            println!("\t- public_key_hash: {public_key_hash}");
+            println!("\t- script_hash: {script_hash}");
+            println!("\t- bech32_hrp: {bech32_hrp}");
+            println!("\t- wallet_import_format: {wallet_import_format}");
+            println!("\t- EVM: {evm}");
+            println!("\t- UCID: {_ucid}");
+            println!("\t- cmc_top: {_cmc_top}");
+            println!("\t- chain_id: {chain_id}");
+            println!("\t- EIP-1191: {eip1191}");
+          }
+
+          let buffer = seed_text.buffer();
+          let start_iter = buffer.start_iter();
+          let end_iter = buffer.end_iter();
+          let seed_string = buffer.text(&start_iter, &end_iter, true);
+
+          if key_derivation == "secp256k1" || key_derivation == "secp256k1-monero" {
+            match keys::generate_master_keys_secp256k1(
+              &seed_string,
+              Some(&private_header),
+              Some(&public_header),
+            ) {
+              Ok(_) => {
+                d3bug("<<< generate_master_keys_secp256k1", "debug");
+              }
+              Err(err) => d3bug(
+                &format!("generate_master_keys_secp256k1: \n{err:?}"),
+                "error",
+              ),
+            };
+          } else if key_derivation == "ed25519" || key_derivation == "ed25519-blake2b" {
+            match keys::generate_master_keys_ed25519(&seed_string) {
+              Ok(_) => {
+                d3bug("<<< generate_master_keys_ed25519", "debug");
+              }
+              Err(err) => d3bug(
+                &format!("generate_master_keys_ed25519: \n{err:?}"),
+                "error",
+              ),
+            };
+          } else if key_derivation == "ed25519-bip32" {
+            let (entropy, passphrase) = {
+              let wallet_settings = WALLET_SETTINGS.lock().unwrap();
+              (
+                wallet_settings.entropy_string.clone().unwrap_or_default(),
+                wallet_settings
+                  .mnemonic_passphrase
+                  .clone()
+                  .unwrap_or_default(),
+              )
+            };
+
+            match keys::generate_master_keys_icarus(&entropy, &passphrase) {
+              Ok(_) => {
+                d3bug("<<< generate_master_keys_icarus", "debug");
+              }
+              Err(err) => d3bug(
+                &format!("generate_master_keys_icarus: \n{err:?}"),
+                "error",
+              ),
+            };
+          };
 
           coin_entry.set_text(&coin_index.to_string());
 
@@ -4028,9 +4768,16 @@ fn create_main_window(
           master_public_key_text.buffer().set_text(&master_public_key);
 
           wallet_settings.public_key_hash = Some(public_key_hash.clone());
+          wallet_settings.script_hash = Some(script_hash.clone());
+          wallet_settings.bech32_hrp = Some(bech32_hrp.clone());
+          wallet_settings.private_header = Some(private_header.clone());
+          wallet_settings.public_header = Some(public_header.clone());
           wallet_settings.wallet_import_format = Some(wallet_import_format.to_string());
           wallet_settings.key_derivation = Some(key_derivation.to_string());
           wallet_settings.hash = Some(hash.to_string());
+          wallet_settings.chain_id = chain_id.parse().ok();
+          wallet_settings.eip1191 = Some(eip1191 == "TRUE");
+          wallet_settings.evm = Some(evm == "TRUE");
           wallet_settings.coin_index = Some(coin_index);
           wallet_settings.coin_name = Some(coin_name.parse().unwrap());
         }
@@ -4193,10 +4940,10 @@ fn create_main_window(
         seed_text.buffer().set_text(&seed_hex.to_string());
 
         let mut wallet_settings = WALLET_SETTINGS.lock().unwrap();
-        wallet_settings.entropy_string = Some(final_entropy);
-        wallet_settings.mnemonic_words = Some(final_mnemonic_words);
-        wallet_settings.mnemonic_passphrase = Some(final_mnemonic_passphrase);
-        wallet_settings.seed = Some(seed_hex.clone());
+        wallet_settings.entropy_string = Some(final_entropy.into());
+        wallet_settings.mnemonic_words = Some(final_mnemonic_words.into());
+        wallet_settings.mnemonic_passphrase = Some(final_mnemonic_passphrase.into());
+        wallet_settings.seed = Some(seed_hex.clone().into());
       }
     }
   ));
@@ -4527,12 +5274,20 @@ fn create_main_window(
     #[weak]
     master_private_key_text,
     #[weak]
+    master_public_key_text,
+    #[weak]
+    account_private_key_text,
+    #[weak]
+    account_public_key_text,
+    #[weak]
     address_start_spinbutton,
     #[weak]
     address_count_spinbutton,
     #[weak]
     address_options_hardened_address_checkbox,
     #[weak]
+    address_options_compressed_checkbox,
+    #[weak]
     address_generation_progress_bar,
     #[weak]
     address_fill_progress_bar,
@@ -4552,7 +5307,12 @@ fn create_main_window(
       let end_iter = buffer.end_iter();
       let master_private_key_string = buffer.text(&start_iter, &end_iter, true);
 
-      if master_private_key_string.is_empty() {
+      let buffer = master_public_key_text.buffer();
+      let start_iter = buffer.start_iter();
+      let end_iter = buffer.end_iter();
+      let master_public_key_string = buffer.text(&start_iter, &end_iter, true);
+
+      if master_private_key_string.is_empty() && master_public_key_string.is_empty() {
         let lock_app_messages = app_messages_state.borrow();
         match lock_app_messages.queue_message(
           t!("error.address.master").to_string(),
@@ -4563,10 +5323,7 @@ fn create_main_window(
         };
         return;
       } else {
-        d3bug(
-          &format!("master_private_key_string: {master_private_key_string:?}"),
-          "debug",
-        );
+        d3bug("master_private_key_string: [REDACTED]", "debug");
       }
 
       address_generation_speed_label.set_label("0/sec");
@@ -4584,8 +5341,14 @@ fn create_main_window(
       stop_addresses_button_box.set_visible(true);
       delete_addresses_button_box.set_visible(false);
 
-      #[cfg(not(feature = "dev"))]
-      if wallet_settings.key_derivation.clone().unwrap_or_default() != "secp256k1" {
+      if !matches!(
+        wallet_settings.key_derivation.as_deref(),
+        Some("secp256k1")
+          | Some("secp256k1-monero")
+          | Some("ed25519")
+          | Some("ed25519-blake2b")
+          | Some("ed25519-bip32")
+      ) {
         let lock_app_messages = app_messages_state.borrow();
         match lock_app_messages.queue_message(
           t!("error.address.unsupported").to_string(),
@@ -4606,7 +5369,122 @@ fn create_main_window(
 
       d3bug(&format!("derivation_path: {derivation_path:?}"), "debug");
 
+      let parsed_derivation_path = keys::parse_derivation_path(&derivation_path);
+      let bip = parsed_derivation_path
+        .as_ref()
+        .ok()
+        .and_then(|path| path.children().first().map(|purpose| purpose.index()))
+        .unwrap_or(44);
+
+      d3bug(&format!("bip: {bip:?}"), "debug");
+
+      if wallet_settings.key_derivation.as_deref() == Some("secp256k1") {
+        let account_path = parsed_derivation_path.map(|path| {
+          if bip == 32 {
+            path
+          } else {
+            path.parent()
+          }
+        });
+
+        match keys::ExtendedKey::from_parts(
+          wallet_settings.master_depth.unwrap_or_default(),
+          wallet_settings.master_parent_fingerprint.unwrap_or_default(),
+          wallet_settings.master_child_number.unwrap_or_default(),
+          &wallet_settings
+            .master_chain_code_bytes
+            .clone()
+            .unwrap_or_default(),
+          wallet_settings.master_private_key_bytes.as_deref(),
+          &wallet_settings
+            .master_public_key_bytes
+            .clone()
+            .unwrap_or_default(),
+        )
+        .and_then(|root_node| {
+          account_path
+            .and_then(|account_path| keys::derive_extended_key_from_path(&root_node, &account_path))
+        })
+        .and_then(|account_node| {
+          let private_header = keys::parse_extended_key_header(
+            wallet_settings.private_header.as_deref(),
+            "0x0488ADE4",
+          )?;
+          let public_header = keys::parse_extended_key_header(
+            wallet_settings.public_header.as_deref(),
+            "0x0488B21E",
+          )?;
+
+          let account_xprv = match account_node.private_key {
+            Some(_) => account_node.serialize_private(private_header)?,
+            None => String::new(),
+          };
+
+          Ok((account_xprv, account_node.serialize_public(public_header)))
+        }) {
+          Ok((account_xprv, account_xpub)) => {
+            d3bug(&format!("account_path: {account_path:?}"), "debug");
+            account_private_key_text.buffer().set_text(&account_xprv);
+            account_public_key_text.buffer().set_text(&account_xpub);
+          }
+          Err(err) => {
+            account_private_key_text.buffer().set_text("");
+            account_public_key_text.buffer().set_text("");
+            d3bug(&format!("derive_extended_key_from_path: {err:?}"), "error");
+          }
+        };
+      }
+
       let hardened_address = address_options_hardened_address_checkbox.is_active();
+      let compressed = address_options_compressed_checkbox.is_active();
+
+      // SLIP-10 Ed25519 has no public derivation, so unhardened segments are refused
+      if matches!(
+        wallet_settings.key_derivation.as_deref(),
+        Some("ed25519") | Some("ed25519-blake2b")
+      ) && (!hardened_address
+        || keys::parse_derivation_path(&derivation_path)
+          .is_ok_and(|path| path.children().iter().any(|child| !child.is_hardened())))
+      {
+        let lock_app_messages = app_messages_state.borrow();
+        match lock_app_messages.queue_message(
+          t!("error.address.hardened").to_string(),
+          gtk::MessageType::Error,
+        ) {
+          Ok(_) => {}
+          Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+        };
+        return;
+      }
+
+      let master_node = keys::DerivationNode::new(
+        wallet_settings
+          .master_private_key_bytes
+          .clone()
+          .unwrap_or_default(),
+        wallet_settings
+          .master_chain_code_bytes
+          .clone()
+          .unwrap_or_default(),
+        wallet_settings
+          .master_public_key_bytes
+          .clone()
+          .unwrap_or_default(),
+        wallet_settings.master_depth.unwrap_or_default(),
+        wallet_settings.master_child_number.unwrap_or_default(),
+      );
+      let parent_node = match keys::parse_derivation_path(&derivation_path).and_then(|path| {
+        master_node.derive(
+          wallet_settings.key_derivation.as_deref().unwrap_or_default(),
+          &path,
+        )
+      }) {
+        Ok(node) => node,
+        Err(err) => {
+          d3bug(&format!("DerivationNode::derive: {err:?}"), "error");
+          master_node
+        }
+      };
 
       let address_start_point = address_start_spinbutton.text();
       let address_start_point_int = address_start_point.parse::<usize>().unwrap_or(0);
@@ -4928,24 +5806,27 @@ fn create_main_window(
                       let magic_ingredients = keys::AddressHocusPokus {
                         coin_index: wallet_settings.coin_index.unwrap_or_default(),
                         derivation_path: derivation_path.clone(),
-                        master_private_key_bytes: wallet_settings
-                          .master_private_key_bytes
-                          .clone()
-                          .unwrap_or_default(),
-                        master_chain_code_bytes: wallet_settings
-                          .master_chain_code_bytes
-                          .clone()
-                          .unwrap_or_default(),
+                        master_private_key_bytes: parent_node.private_key.clone(),
+                        master_chain_code_bytes: parent_node.chain_code.clone(),
+                        master_public_key_bytes: parent_node.public_key.clone(),
+                        master_depth: parent_node.depth,
+                        master_child_number: parent_node.child_number,
                         public_key_hash: wallet_settings
                           .public_key_hash
                           .clone()
                           .unwrap_or_default(),
+                        script_hash: wallet_settings.script_hash.clone().unwrap_or_default(),
+                        bech32_hrp: wallet_settings.bech32_hrp.clone().unwrap_or_default(),
                         key_derivation: wallet_settings.key_derivation.clone().unwrap_or_default(),
                         wallet_import_format: wallet_settings
                           .wallet_import_format
                           .clone()
                           .unwrap_or_default(),
                         hash: wallet_settings.hash.clone().unwrap_or_default(),
+                        chain_id: wallet_settings.chain_id,
+                        eip1191: wallet_settings.eip1191.unwrap_or_default(),
+                        bip,
+                        compressed,
                       };
 
                       if let Ok(Some(address)) = keys::generate_address(magic_ingredients) {
@@ -4956,6 +5837,8 @@ fn create_main_window(
                           address: Some(address.address),
                           public_key: Some(address.public_key),
                           private_key: Some(address.private_key),
+                          private_view_key: address.private_view_key,
+                          mnemonic: address.mnemonic,
                         };
 
                         entry.insert(new_entry);
@@ -5100,6 +5983,8 @@ fn create_main_window(
                     new_coin.address.as_deref().unwrap_or_default(),
                     new_coin.public_key.as_deref().unwrap_or_default(),
                     new_coin.private_key.as_deref().unwrap_or_default(),
+                    new_coin.private_view_key.as_deref().unwrap_or_default(),
+                    new_coin.mnemonic.as_deref().unwrap_or_default(),
                   )
                 })
                 .collect();
@@ -5177,184 +6062,2231 @@ fn create_main_window(
           *address_speed_generation_value.lock().unwrap() = new_speed;
           brain_batch.lock().unwrap().update_config(new_speed);
 
-          d3bug(
-            "<<< address_speed_controller_slider.connect_value_changed",
-            "debug",
-          );
-        }
-      ));
+          d3bug(
+            "<<< address_speed_controller_slider.connect_value_changed",
+            "debug",
+          );
+        }
+      ));
+
+      *generator_handler.lock().unwrap() = Some((address_generation_handler, cancel_flag.clone()));
+
+      d3bug("<<< generate_addresses_button.connect_clicked", "debug");
+    }
+  ));
+
+  // JUMP: Delete Addresses button
+  delete_addresses_button.connect_clicked(clone!(
+    #[strong]
+    address_store,
+    #[weak]
+    delete_addresses_button_box,
+    #[weak]
+    address_start_spinbutton,
+    #[weak]
+    address_generation_progress_bar,
+    #[weak]
+    address_fill_progress_bar,
+    #[weak]
+    window,
+    #[strong]
+    address_generation_speed_label,
+    move |_| {
+      d3bug(">>> delete_addresses_button.connect_clicked", "debug");
+
+      let busy_cursor = gtk::gdk::Cursor::from_name("wait", None);
+      window.set_cursor(busy_cursor.as_ref());
+      address_store.remove_all();
+      CRYPTO_ADDRESS.clear();
+      address_start_spinbutton.set_text("0");
+
+      address_generation_progress_bar.set_fraction(0.0);
+      address_generation_progress_bar.set_show_text(false);
+      address_fill_progress_bar.set_fraction(0.0);
+      address_fill_progress_bar.set_show_text(false);
+
+      delete_addresses_button_box.set_visible(false);
+      address_generation_speed_label.set_label("0/sec");
+      window.set_cursor(None);
+
+      d3bug("<<< delete_addresses_button.connect_clicked", "debug");
+    }
+  ));
+
+  // JUMP: Stop Addresses button
+  stop_address_generation_button.connect_clicked(clone!(
+    #[strong]
+    generator_handler,
+    #[strong]
+    app_messages_state,
+    #[weak]
+    delete_addresses_button_box,
+    #[weak]
+    stop_addresses_button_box,
+    #[weak]
+    window,
+    move |_| {
+      d3bug(
+        ">>> stop_address_generation_button.connect_clicked",
+        "debug",
+      );
+
+      // TODO: Add message what to do with generated addresses, import or drop?
+      if let Some((handle, cancel_flag)) = generator_handler.lock().unwrap().take() {
+        *cancel_flag.lock().unwrap() = true;
+        let _ = handle.join();
+      } else {
+        d3bug("No generator handle", "error");
+      }
+
+      let message = "Address generation aborted";
+      let lock_app_messages = app_messages_state.borrow();
+      match lock_app_messages.queue_message(message.to_string(), gtk::MessageType::Warning) {
+        Ok(_) => d3bug("Address generation aborted", "info"),
+        Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+      };
+
+      delete_addresses_button_box.set_visible(true);
+      stop_addresses_button_box.set_visible(false);
+
+      window.set_cursor(None);
+
+      d3bug(
+        "<<< stop_address_generation_button.connect_clicked",
+        "debug",
+      );
+    }
+  ));
+
+  // Main sidebar
+  let main_window_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+  let main_sidebar_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+  let main_infobar_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+
+  main_sidebar_box.append(&stack_sidebar);
+  main_sidebar_box.append(&stack);
+  main_infobar_box.append(&info_bar);
+  main_infobar_box.set_hexpand(true);
+  main_window_box.append(&main_sidebar_box);
+  main_window_box.append(&main_infobar_box);
+  main_window_box.set_hexpand(true);
+
+  {
+    let lock_app_messages = app_messages_state.borrow();
+    match lock_app_messages.queue_message(t!("hello").to_string(), gtk::MessageType::Info) {
+      Ok(_) => {}
+      Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+    };
+  }
+
+  window.set_child(Some(&main_window_box));
+
+  #[cfg(feature = "dev")]
+  {
+    if let Some(value) = start_time {
+      let elapsed = value.elapsed();
+
+      let message = format!("Application startup time: {elapsed:.2?}");
+      // println!("{}", message);
+
+      let lock_app_messages = app_messages_state.borrow();
+      match lock_app_messages.queue_message(message, gtk::MessageType::Info) {
+        Ok(_) => {}
+        Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
+      };
+    };
+  }
+
+  window.connect_close_request(clone!(
+    #[strong]
+    last_window,
+    #[strong]
+    generator_handler,
+    move |window| {
+      let gui_last_width = window.width() as i64;
+      let gui_last_height = window.height() as i64;
+      let gui_maximized = window.is_maximized();
+
+      let gui_save_size = {
+        let app_settings_lock = APP_SETTINGS.read().unwrap();
+        app_settings_lock.gui_save_size.unwrap()
+      };
+
+      if gui_save_size {
+        std::thread::spawn(move || {
+          let mut settings = APP_SETTINGS.write().unwrap();
+          settings.update_value("gui_last_width", toml_edit::value(gui_last_width), None);
+          settings.update_value("gui_last_height", toml_edit::value(gui_last_height), None);
+          settings.update_value("gui_maximized", toml_edit::value(gui_maximized), None);
+          AppSettings::save_settings(&settings);
+        });
+      }
+
+      if let Some(window) = last_window.as_ref() {
+        window.set_visible(true);
+        window.present()
+      }
+
+      if let Some((handle, cancel_flag)) = generator_handler.lock().unwrap().take() {
+        let _ = handle.join();
+        *cancel_flag.lock().unwrap() = true;
+      } else {
+        #[cfg(debug_assertions)]
+        eprintln!("No generator handle!");
+      }
+
+      glib::Propagation::Proceed
+    }
+  ));
+
+  Ok(window)
+}
+
+fn create_psbt_window(
+  master_private_key: qr2m_lib::SecretBytes,
+  master_chain_code: qr2m_lib::SecretBytes,
+) {
+  #[cfg(debug_assertions)]
+  println!("[+] Sign PSBT");
+
+  let psbt_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.coin.psbt").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(350)
+    .width_request(600)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let psbt_frame = gtk::Frame::new(Some(&t!("UI.main.coin.psbt.text")));
+  let psbt_scrolled_window = gtk::ScrolledWindow::new();
+  let psbt_text = gtk::TextView::new();
+  psbt_text.set_wrap_mode(gtk::WrapMode::Char);
+  psbt_text.set_hexpand(true);
+  psbt_text.set_vexpand(true);
+  psbt_scrolled_window.set_child(Some(&psbt_text));
+  psbt_frame.set_child(Some(&psbt_scrolled_window));
+
+  let summary_frame = gtk::Frame::new(Some(&t!("UI.main.address.transaction.summary")));
+  let summary_label = gtk::Label::new(None);
+  summary_label.set_selectable(true);
+  summary_label.set_wrap(true);
+  summary_label.set_xalign(0.0);
+  summary_label.set_margin_top(5);
+  summary_label.set_margin_bottom(5);
+  summary_label.set_margin_start(5);
+  summary_label.set_margin_end(5);
+  summary_frame.set_child(Some(&summary_label));
+
+  let status_label = gtk::Label::new(None);
+  status_label.set_wrap(true);
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let open_button = gtk::Button::with_label(&t!("UI.button.open"));
+  let review_button = gtk::Button::with_label(&t!("UI.main.address.transaction.review"));
+  let sign_button = gtk::Button::with_label(&t!("UI.main.coin.psbt"));
+  let save_button = gtk::Button::with_label(&t!("UI.button.save"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  sign_button.set_sensitive(false);
+  button_box.append(&open_button);
+  button_box.append(&review_button);
+  button_box.append(&sign_button);
+  button_box.append(&save_button);
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&psbt_frame);
+  main_dialog_box.append(&summary_frame);
+  main_dialog_box.append(&status_label);
+  main_dialog_box.append(&button_box);
+
+  psbt_dialog.set_child(Some(&main_dialog_box));
+
+  let master_keys = Rc::new((master_private_key, master_chain_code));
+
+  let psbt_filter = gtk::FileFilter::new();
+  psbt_filter.add_pattern("*.psbt");
+  psbt_filter.set_name(Some("PSBT file (*.psbt)"));
+
+  // Signing is only allowed for the PSBT shown in the summary
+  let reviewed_psbt: Rc<RefCell<Option<sign::Psbt>>> = Rc::new(RefCell::new(None));
+
+  psbt_text.buffer().connect_changed(clone!(
+    #[strong]
+    reviewed_psbt,
+    #[weak]
+    sign_button,
+    move |_| {
+      reviewed_psbt.borrow_mut().take();
+      sign_button.set_sensitive(false);
+    }
+  ));
+
+  open_button.connect_clicked(clone!(
+    #[weak]
+    psbt_dialog,
+    #[weak]
+    psbt_text,
+    #[weak]
+    status_label,
+    #[strong]
+    psbt_filter,
+    move |_| {
+      let open_dialog = gtk::FileDialog::builder()
+        .title(t!("UI.button.open").to_string())
+        .modal(true)
+        .default_filter(&psbt_filter)
+        .build();
+
+      open_dialog.open(
+        Some(&psbt_dialog),
+        None::<&gio::Cancellable>,
+        clone!(
+          #[weak]
+          psbt_text,
+          #[weak]
+          status_label,
+          move |response| {
+            let Some(path) = response.ok().and_then(|file| file.path()) else {
+              return;
+            };
+
+            let psbt = fs::read(&path)
+              .map_err(AppError::Io)
+              .and_then(|contents| sign::Psbt::from_file_contents(&contents));
+
+            match psbt {
+              Ok(psbt) => {
+                psbt_text.buffer().set_text(&psbt.to_base64());
+                status_label.set_text(&path.to_string_lossy());
+              }
+              Err(err) => {
+                d3bug(&format!("Psbt::from_file_contents: {err:?}"), "error");
+                status_label.set_text(&t!("error.psbt.invalid", value = err));
+              }
+            }
+          }
+        ),
+      );
+    }
+  ));
+
+  review_button.connect_clicked(clone!(
+    #[strong]
+    reviewed_psbt,
+    #[weak]
+    psbt_text,
+    #[weak]
+    summary_label,
+    #[weak]
+    sign_button,
+    move |_| {
+      let buffer = psbt_text.buffer();
+      let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      let (public_key_hash, script_hash, bech32_hrp) = {
+        let wallet_settings = WALLET_SETTINGS.lock().unwrap();
+        let decode_prefix = |prefix: &Option<String>| {
+          hex::decode(prefix.as_deref().unwrap_or_default().trim_start_matches("0x"))
+            .unwrap_or_default()
+        };
+
+        (
+          decode_prefix(&wallet_settings.public_key_hash),
+          decode_prefix(&wallet_settings.script_hash),
+          wallet_settings.bech32_hrp.clone().unwrap_or_default(),
+        )
+      };
+
+      let result = sign::Psbt::from_base64(&text).and_then(|psbt| {
+        let summary = psbt.summarize(&public_key_hash, &script_hash, &bech32_hrp)?;
+        Ok((psbt, summary))
+      });
+
+      let (psbt, summary) = match result {
+        Ok(values) => values,
+        Err(err) => {
+          d3bug(&format!("Psbt::summarize: {err:?}"), "error");
+          reviewed_psbt.borrow_mut().take();
+          sign_button.set_sensitive(false);
+          summary_label.set_text(&t!("error.psbt.invalid", value = err));
+          return;
+        }
+      };
+
+      let mut lines = summary
+        .outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| {
+          let address = output.address.clone().unwrap_or_else(|| {
+            t!("UI.main.coin.psbt.script", value = hex::encode(&output.script_pubkey))
+              .to_string()
+          });
+
+          format!(
+            "{}: {address} {}",
+            t!("UI.main.coin.psbt.output", value = index + 1),
+            sign::format_eth_units(output.value as u128, 8)
+          )
+        })
+        .collect::<Vec<_>>();
+
+      lines.push(format!(
+        "{}: {}",
+        t!("UI.main.coin.psbt.fee"),
+        match summary.fee {
+          Some(fee) => sign::format_eth_units(fee as u128, 8),
+          None => t!("UI.main.coin.psbt.fee_unknown").to_string(),
+        }
+      ));
+
+      if summary.unverified_inputs > 0 {
+        lines.push(
+          t!(
+            "UI.main.coin.psbt.unverified",
+            value = summary.unverified_inputs
+          )
+          .to_string(),
+        );
+      }
+
+      summary_label.set_text(&lines.join("\n"));
+      *reviewed_psbt.borrow_mut() = Some(psbt);
+      sign_button.set_sensitive(true);
+    }
+  ));
+
+  sign_button.connect_clicked(clone!(
+    #[strong]
+    master_keys,
+    #[strong]
+    reviewed_psbt,
+    #[weak]
+    psbt_text,
+    #[weak]
+    status_label,
+    move |sign_button| {
+      let Some(mut psbt) = reviewed_psbt.borrow_mut().take() else {
+        return;
+      };
+      sign_button.set_sensitive(false);
+
+      match sign::sign_psbt(&mut psbt, &master_keys.0, &master_keys.1) {
+        Ok(signed_inputs) => {
+          psbt_text.buffer().set_text(&psbt.to_base64());
+          status_label.set_text(&t!(
+            "UI.main.coin.psbt.signed",
+            value = signed_inputs,
+            total = psbt.inputs.len()
+          ));
+        }
+        Err(err) => {
+          d3bug(&format!("sign_psbt: {err:?}"), "error");
+          status_label.set_text(&t!("error.psbt.invalid", value = err));
+        }
+      }
+    }
+  ));
+
+  save_button.connect_clicked(clone!(
+    #[weak]
+    psbt_dialog,
+    #[weak]
+    psbt_text,
+    #[weak]
+    status_label,
+    #[strong]
+    psbt_filter,
+    move |_| {
+      let buffer = psbt_text.buffer();
+      let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      let psbt = match sign::Psbt::from_base64(&text) {
+        Ok(psbt) => psbt,
+        Err(err) => {
+          status_label.set_text(&t!("error.psbt.invalid", value = err));
+          return;
+        }
+      };
+
+      let save_dialog = gtk::FileDialog::builder()
+        .title(t!("UI.dialog.save").to_string())
+        .modal(true)
+        .accept_label(t!("UI.button.save").to_string())
+        .default_filter(&psbt_filter)
+        .build();
+
+      save_dialog.save(
+        Some(&psbt_dialog),
+        None::<&gio::Cancellable>,
+        clone!(
+          #[weak]
+          status_label,
+          move |response| {
+            let Some(path) = response.ok().and_then(|file| file.path()) else {
+              return;
+            };
+
+            let path = path.with_extension("psbt");
+
+            match fs::write(&path, psbt.to_bytes()) {
+              Ok(_) => status_label.set_text(&path.to_string_lossy()),
+              Err(err) => {
+                d3bug(&format!("save psbt: {err:?}"), "error");
+                status_label.set_text(&err.to_string());
+              }
+            }
+          }
+        ),
+      );
+    }
+  ));
+
+  close_button.connect_clicked(clone!(
+    #[weak]
+    psbt_dialog,
+    move |_| {
+      psbt_dialog.close();
+    }
+  ));
+
+  psbt_dialog.present();
+}
+
+fn create_bip85_window(
+  master_private_key: qr2m_lib::SecretBytes,
+  master_chain_code: qr2m_lib::SecretBytes,
+) {
+  #[cfg(debug_assertions)]
+  println!("[+] BIP85");
+
+  let bip85_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.coin.bip85").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(300)
+    .width_request(700)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let options_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  options_box.set_halign(gtk::Align::Center);
+
+  let mnemonic_application = t!("UI.main.coin.bip85.mnemonic").to_string();
+  let password_application = t!("UI.main.coin.bip85.password").to_string();
+  let application_frame = gtk::Frame::new(Some(&t!("UI.main.coin.bip85.application")));
+  let application_dropdown = gtk::DropDown::from_strings(&[
+    mnemonic_application.as_str(),
+    "HEX",
+    "WIF",
+    password_application.as_str(),
+  ]);
+  application_frame.set_child(Some(&application_dropdown));
+
+  let dictionary_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.dictionary")));
+  // BIP85 has no language code for every wordlist, so only the covered ones are offered
+  let bip85_dictionaries: Rc<Vec<&str>> = Rc::new(
+    VALID_MNEMONIC_DICTIONARY
+      .iter()
+      .copied()
+      .filter(|dictionary| keys::get_bip85_language_code(dictionary).is_ok())
+      .collect(),
+  );
+  let dictionary_dropdown = gtk::DropDown::from_strings(&bip85_dictionaries);
+  dictionary_frame.set_child(Some(&dictionary_dropdown));
+
+  let valid_word_counts: Vec<String> = VALID_ENTROPY_LENGTHS
+    .iter()
+    .map(|length| (length / 32 * 3).to_string())
+    .collect();
+  let valid_word_counts_as_str_refs: Vec<&str> =
+    valid_word_counts.iter().map(|s| s.as_ref()).collect();
+
+  let word_count_frame = gtk::Frame::new(Some(&t!("UI.main.coin.bip85.words")));
+  let word_count_dropdown = gtk::DropDown::from_strings(&valid_word_counts_as_str_refs);
+  word_count_frame.set_child(Some(&word_count_dropdown));
+
+  let length_frame = gtk::Frame::new(Some(&t!("UI.main.coin.bip85.length")));
+  let length_adjustment = gtk::Adjustment::new(64.0, 16.0, 64.0, 1.0, 10.0, 0.0);
+  let length_spinbutton = gtk::SpinButton::new(Some(&length_adjustment), 1.0, 0);
+  length_frame.set_child(Some(&length_spinbutton));
+  length_frame.set_sensitive(false);
+
+  let index_frame = gtk::Frame::new(Some(&t!("UI.main.coin.bip85.index")));
+  let index_adjustment = gtk::Adjustment::new(0.0, 0.0, i32::MAX as f64, 1.0, 10.0, 0.0);
+  let index_spinbutton = gtk::SpinButton::new(Some(&index_adjustment), 1.0, 0);
+  index_frame.set_child(Some(&index_spinbutton));
+
+  options_box.append(&application_frame);
+  options_box.append(&dictionary_frame);
+  options_box.append(&word_count_frame);
+  options_box.append(&length_frame);
+  options_box.append(&index_frame);
+
+  let output_frame = gtk::Frame::new(Some(&t!("UI.main.coin.bip85.output")));
+  let output_text = gtk::TextView::new();
+  output_text.set_wrap_mode(gtk::WrapMode::Word);
+  output_text.set_editable(false);
+  output_text.set_hexpand(true);
+  output_text.set_vexpand(true);
+  output_frame.set_child(Some(&output_text));
+
+  let status_label = gtk::Label::new(None);
+  status_label.set_wrap(true);
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let derive_button = gtk::Button::with_label(&t!("UI.main.coin.bip85.derive"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  button_box.append(&derive_button);
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&options_box);
+  main_dialog_box.append(&output_frame);
+  main_dialog_box.append(&status_label);
+  main_dialog_box.append(&button_box);
+
+  bip85_dialog.set_child(Some(&main_dialog_box));
+
+  application_dropdown.connect_selected_notify(clone!(
+    #[weak]
+    dictionary_frame,
+    #[weak]
+    word_count_frame,
+    #[weak]
+    length_frame,
+    #[weak]
+    length_adjustment,
+    #[weak]
+    output_text,
+    move |dropdown| {
+      let selected = dropdown.selected();
+
+      dictionary_frame.set_sensitive(selected == 0);
+      word_count_frame.set_sensitive(selected == 0);
+      length_frame.set_sensitive(selected == 1 || selected == 3);
+
+      match selected {
+        1 => length_adjustment.configure(64.0, 16.0, 64.0, 1.0, 10.0, 0.0),
+        3 => length_adjustment.configure(21.0, 20.0, 86.0, 1.0, 10.0, 0.0),
+        _ => {}
+      }
+
+      output_text.buffer().set_text("");
+    }
+  ));
+
+  let master_keys = Rc::new((master_private_key, master_chain_code));
+
+  derive_button.connect_clicked(clone!(
+    #[strong]
+    master_keys,
+    #[strong]
+    bip85_dictionaries,
+    #[weak]
+    application_dropdown,
+    #[weak]
+    dictionary_dropdown,
+    #[weak]
+    word_count_dropdown,
+    #[weak]
+    length_spinbutton,
+    #[weak]
+    index_spinbutton,
+    #[weak]
+    output_text,
+    #[weak]
+    status_label,
+    move |_| {
+      let (master_private_key, master_chain_code) = &*master_keys;
+      let length = length_spinbutton.value_as_int() as u32;
+      let index = index_spinbutton.value_as_int() as u32;
+
+      let derived = match application_dropdown.selected() {
+        0 => {
+          let dictionary = bip85_dictionaries
+            .get(dictionary_dropdown.selected() as usize)
+            .copied()
+            .unwrap_or_default();
+          let word_count = VALID_ENTROPY_LENGTHS
+            .get(word_count_dropdown.selected() as usize)
+            .map(|length| length / 32 * 3)
+            .unwrap_or_default();
+
+          keys::derive_bip85_mnemonic(
+            master_private_key,
+            master_chain_code,
+            dictionary,
+            word_count,
+            index,
+          )
+        }
+        1 => keys::derive_bip85_hex(master_private_key, master_chain_code, length, index),
+        2 => keys::derive_bip85_wif(master_private_key, master_chain_code, index),
+        _ => keys::derive_bip85_password(master_private_key, master_chain_code, length, index),
+      };
+
+      match derived {
+        Ok(derived) => {
+          output_text.buffer().set_text(&derived);
+          status_label.set_text("");
+        }
+        Err(err) => {
+          d3bug(&format!("bip85: {err:?}"), "error");
+          output_text.buffer().set_text("");
+          status_label.set_text(&err.to_string());
+        }
+      }
+    }
+  ));
+
+  close_button.connect_clicked(clone!(
+    #[weak]
+    bip85_dialog,
+    #[weak]
+    output_text,
+    move |_| {
+      output_text.buffer().set_text("");
+      bip85_dialog.close();
+    }
+  ));
+
+  bip85_dialog.present();
+}
+
+fn create_eth_transaction_window(address: String, derivation_path: String) {
+  #[cfg(debug_assertions)]
+  println!("[+] Sign transaction for {address}");
+
+  let eth_transaction_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.address.transaction").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(500)
+    .width_request(700)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let address_frame = gtk::Frame::new(Some(&t!("UI.main.address.table.address")));
+  let address_label = gtk::Label::new(Some(&address));
+  address_label.set_selectable(true);
+  address_label.set_margin_top(5);
+  address_label.set_margin_bottom(5);
+  address_frame.set_child(Some(&address_label));
+
+  let path_frame = gtk::Frame::new(Some(&t!("UI.main.address.table.path")));
+  let path_entry = gtk::Entry::new();
+  path_entry.set_text(&derivation_path);
+  path_frame.set_child(Some(&path_entry));
+
+  let input_frame = gtk::Frame::new(Some(&t!("UI.main.address.transaction.input")));
+  let input_text = gtk::TextView::new();
+  input_text.set_wrap_mode(gtk::WrapMode::Char);
+  input_text.set_hexpand(true);
+  input_text.set_vexpand(true);
+  input_frame.set_child(Some(&input_text));
+
+  let summary_frame = gtk::Frame::new(Some(&t!("UI.main.address.transaction.summary")));
+  let summary_label = gtk::Label::new(None);
+  summary_label.set_selectable(true);
+  summary_label.set_wrap(true);
+  summary_label.set_xalign(0.0);
+  summary_label.set_margin_top(5);
+  summary_label.set_margin_bottom(5);
+  summary_label.set_margin_start(5);
+  summary_label.set_margin_end(5);
+  summary_frame.set_child(Some(&summary_label));
+
+  let output_frame = gtk::Frame::new(Some(&t!("UI.main.address.transaction.output")));
+  let output_text = gtk::TextView::new();
+  output_text.set_wrap_mode(gtk::WrapMode::Char);
+  output_text.set_editable(false);
+  output_text.set_hexpand(true);
+  output_text.set_vexpand(true);
+  output_frame.set_child(Some(&output_text));
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let review_button = gtk::Button::with_label(&t!("UI.main.address.transaction.review"));
+  let sign_button = gtk::Button::with_label(&t!("UI.main.address.transaction.sign"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  sign_button.set_sensitive(false);
+  button_box.append(&review_button);
+  button_box.append(&sign_button);
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&address_frame);
+  main_dialog_box.append(&path_frame);
+  main_dialog_box.append(&input_frame);
+  main_dialog_box.append(&summary_frame);
+  main_dialog_box.append(&output_frame);
+  main_dialog_box.append(&button_box);
+
+  eth_transaction_dialog.set_child(Some(&main_dialog_box));
+
+  // Signing is only allowed for the transaction shown in the summary
+  let reviewed_transaction: Rc<RefCell<Option<sign::EthTransaction>>> = Rc::new(RefCell::new(None));
+
+  let invalidate_review = Rc::new(clone!(
+    #[strong]
+    reviewed_transaction,
+    #[weak]
+    sign_button,
+    move || {
+      reviewed_transaction.borrow_mut().take();
+      sign_button.set_sensitive(false);
+    }
+  ));
+
+  input_text.buffer().connect_changed(clone!(
+    #[strong]
+    invalidate_review,
+    move |_| invalidate_review()
+  ));
+
+  path_entry.connect_changed(clone!(
+    #[strong]
+    invalidate_review,
+    move |_| invalidate_review()
+  ));
+
+  review_button.connect_clicked(clone!(
+    #[strong]
+    reviewed_transaction,
+    #[strong]
+    address,
+    #[weak]
+    input_text,
+    #[weak]
+    summary_label,
+    #[weak]
+    output_text,
+    #[weak]
+    sign_button,
+    move |_| {
+      let buffer = input_text.buffer();
+      let input = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+      output_text.buffer().set_text("");
+
+      let transaction = match sign::EthTransaction::parse(&input) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+          d3bug(&format!("EthTransaction::parse: {err:?}"), "error");
+          reviewed_transaction.borrow_mut().take();
+          sign_button.set_sensitive(false);
+          summary_label.set_text(&err.to_string());
+          return;
+        }
+      };
+
+      let recipient = match transaction.to {
+        Some(to) => keys::encode_eip55_address(&to, None),
+        None => t!("UI.main.address.transaction.contract").to_string(),
+      };
+
+      let mut summary = vec![
+        format!(
+          "{}: {}",
+          t!("UI.main.address.transaction.type"),
+          transaction.transaction_type.name()
+        ),
+        format!(
+          "{}: {}",
+          t!("UI.main.address.transaction.chain"),
+          transaction.chain_id
+        ),
+        format!("{}: {address}", t!("UI.main.address.transaction.from")),
+        format!("{}: {recipient}", t!("UI.main.address.transaction.to")),
+        format!(
+          "{}: {}",
+          t!("UI.main.address.transaction.value"),
+          sign::format_eth_units(transaction.value, 18)
+        ),
+        format!(
+          "{}: {}",
+          t!("UI.main.address.transaction.gas_limit"),
+          transaction.gas_limit
+        ),
+      ];
+
+      if transaction.transaction_type == sign::EthTransactionType::DynamicFee {
+        summary.push(format!(
+          "{}: {} gwei",
+          t!("UI.main.address.transaction.max_priority_fee"),
+          sign::format_eth_units(transaction.max_priority_fee_per_gas, 9)
+        ));
+        summary.push(format!(
+          "{}: {} gwei",
+          t!("UI.main.address.transaction.max_fee_per_gas"),
+          sign::format_eth_units(transaction.max_fee_per_gas, 9)
+        ));
+      } else {
+        summary.push(format!(
+          "{}: {} gwei",
+          t!("UI.main.address.transaction.gas_price"),
+          sign::format_eth_units(transaction.gas_price, 9)
+        ));
+      }
+
+      summary.push(format!(
+        "{}: {}",
+        t!("UI.main.address.transaction.max_fee"),
+        sign::format_eth_units(transaction.max_transaction_fee(), 18)
+      ));
+      summary.push(format!(
+        "{}: {}",
+        t!("UI.main.address.transaction.data"),
+        t!(
+          "UI.main.address.transaction.data_size",
+          value = transaction.data.len()
+        )
+      ));
+
+      summary_label.set_text(&summary.join("\n"));
+      *reviewed_transaction.borrow_mut() = Some(transaction);
+      sign_button.set_sensitive(true);
+    }
+  ));
+
+  sign_button.connect_clicked(clone!(
+    #[strong]
+    reviewed_transaction,
+    #[strong]
+    address,
+    #[weak]
+    path_entry,
+    #[weak]
+    summary_label,
+    #[weak]
+    output_text,
+    move |_| {
+      let Some(transaction) = reviewed_transaction.borrow().clone() else {
+        return;
+      };
+
+      let magic_ingredients = {
+        let wallet_settings = WALLET_SETTINGS.lock().unwrap();
+
+        keys::AddressHocusPokus {
+          coin_index: wallet_settings.coin_index.unwrap_or_default(),
+          derivation_path: path_entry.text().to_string(),
+          master_private_key_bytes: wallet_settings
+            .master_private_key_bytes
+            .clone()
+            .unwrap_or_default(),
+          master_chain_code_bytes: wallet_settings
+            .master_chain_code_bytes
+            .clone()
+            .unwrap_or_default(),
+          master_public_key_bytes: wallet_settings
+            .master_public_key_bytes
+            .clone()
+            .unwrap_or_default(),
+          master_depth: wallet_settings.master_depth.unwrap_or_default(),
+          master_child_number: wallet_settings.master_child_number.unwrap_or_default(),
+          public_key_hash: wallet_settings.public_key_hash.clone().unwrap_or_default(),
+          script_hash: wallet_settings.script_hash.clone().unwrap_or_default(),
+          bech32_hrp: wallet_settings.bech32_hrp.clone().unwrap_or_default(),
+          key_derivation: wallet_settings.key_derivation.clone().unwrap_or_default(),
+          wallet_import_format: wallet_settings
+            .wallet_import_format
+            .clone()
+            .unwrap_or_default(),
+          hash: wallet_settings.hash.clone().unwrap_or_default(),
+          chain_id: wallet_settings.chain_id,
+          eip1191: wallet_settings.eip1191.unwrap_or_default(),
+          bip: 44,
+          compressed: true,
+        }
+      };
+
+      match sign::sign_eth_transaction(&transaction, &magic_ingredients) {
+        Ok(signed) if signed.from.eq_ignore_ascii_case(&address) => {
+          output_text.buffer().set_text(&signed.raw_transaction);
+          summary_label.set_text(&format!(
+            "{}\n{}: {}",
+            summary_label.text(),
+            t!("UI.main.address.transaction.hash"),
+            signed.transaction_hash
+          ));
+        }
+        Ok(signed) => {
+          output_text.buffer().set_text("");
+          summary_label.set_text(&t!(
+            "UI.main.address.transaction.mismatch",
+            value = signed.from
+          ));
+        }
+        Err(err) => {
+          d3bug(&format!("sign_eth_transaction: {err:?}"), "error");
+          output_text.buffer().set_text("");
+          summary_label.set_text(&err.to_string());
+        }
+      }
+    }
+  ));
+
+  close_button.connect_clicked(clone!(
+    #[weak]
+    eth_transaction_dialog,
+    move |_| {
+      eth_transaction_dialog.close();
+    }
+  ));
+
+  eth_transaction_dialog.present();
+}
+
+fn create_slip39_window(entropy: qr2m_lib::SecretString) {
+  #[cfg(debug_assertions)]
+  println!("[+] SLIP-39");
+
+  let slip39_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.seed.slip39").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(500)
+    .width_request(700)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let notebook = gtk::Notebook::new();
+  notebook.set_vexpand(true);
+
+  // Create shares
+  let create_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  create_box.set_margin_top(10);
+  create_box.set_margin_start(10);
+  create_box.set_margin_end(10);
+  create_box.set_margin_bottom(10);
+
+  let create_options_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  create_options_box.set_halign(gtk::Align::Center);
+
+  let group_threshold_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.threshold")));
+  let group_threshold_adjustment = gtk::Adjustment::new(1.0, 1.0, 16.0, 1.0, 1.0, 0.0);
+  let group_threshold_spinbutton = gtk::SpinButton::new(Some(&group_threshold_adjustment), 1.0, 0);
+  group_threshold_frame.set_child(Some(&group_threshold_spinbutton));
+
+  let groups_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.groups")));
+  let groups_entry = gtk::Entry::new();
+  groups_entry.set_text("2/3");
+  groups_entry.set_hexpand(true);
+  groups_frame.set_child(Some(&groups_entry));
+
+  let iteration_exponent_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.exponent")));
+  let iteration_exponent_adjustment = gtk::Adjustment::new(1.0, 0.0, 15.0, 1.0, 1.0, 0.0);
+  let iteration_exponent_spinbutton =
+    gtk::SpinButton::new(Some(&iteration_exponent_adjustment), 1.0, 0);
+  iteration_exponent_frame.set_child(Some(&iteration_exponent_spinbutton));
+
+  create_options_box.append(&group_threshold_frame);
+  create_options_box.append(&groups_frame);
+  create_options_box.append(&iteration_exponent_frame);
+
+  let shares_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.shares")));
+  let shares_scrolled_window = gtk::ScrolledWindow::new();
+  let shares_text = gtk::TextView::new();
+  shares_text.set_wrap_mode(gtk::WrapMode::Word);
+  shares_text.set_editable(false);
+  shares_text.set_hexpand(true);
+  shares_text.set_vexpand(true);
+  shares_scrolled_window.set_child(Some(&shares_text));
+  shares_frame.set_child(Some(&shares_scrolled_window));
+
+  let generate_button = gtk::Button::with_label(&t!("UI.main.seed.slip39.generate"));
+  generate_button.set_halign(gtk::Align::Center);
+  generate_button.set_sensitive(!entropy.is_empty());
+
+  create_box.append(&create_options_box);
+  create_box.append(&shares_frame);
+  create_box.append(&generate_button);
+
+  // Recover
+  let recover_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  recover_box.set_margin_top(10);
+  recover_box.set_margin_start(10);
+  recover_box.set_margin_end(10);
+  recover_box.set_margin_bottom(10);
+
+  let recover_shares_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.input")));
+  let recover_scrolled_window = gtk::ScrolledWindow::new();
+  let recover_shares_text = gtk::TextView::new();
+  recover_shares_text.set_wrap_mode(gtk::WrapMode::Word);
+  recover_shares_text.set_hexpand(true);
+  recover_shares_text.set_vexpand(true);
+  recover_scrolled_window.set_child(Some(&recover_shares_text));
+  recover_shares_frame.set_child(Some(&recover_scrolled_window));
+
+  let recovered_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.recovered")));
+  let recovered_text = gtk::TextView::new();
+  recovered_text.set_wrap_mode(gtk::WrapMode::Char);
+  recovered_text.set_editable(false);
+  recovered_text.set_hexpand(true);
+  recovered_frame.set_child(Some(&recovered_text));
+
+  let recover_button = gtk::Button::with_label(&t!("UI.main.seed.slip39.recover"));
+  recover_button.set_halign(gtk::Align::Center);
+
+  recover_box.append(&recover_shares_frame);
+  recover_box.append(&recovered_frame);
+  recover_box.append(&recover_button);
+
+  notebook.append_page(
+    &create_box,
+    Some(&gtk::Label::new(Some(&t!("UI.main.seed.slip39.create")))),
+  );
+  notebook.append_page(
+    &recover_box,
+    Some(&gtk::Label::new(Some(&t!("UI.main.seed.slip39.recover")))),
+  );
+
+  let passphrase_frame = gtk::Frame::new(Some(&t!("UI.main.seed.slip39.passphrase")));
+  let passphrase_entry = gtk::PasswordEntry::new();
+  passphrase_entry.set_show_peek_icon(true);
+  passphrase_frame.set_child(Some(&passphrase_entry));
+
+  let status_label = gtk::Label::new(None);
+  status_label.set_wrap(true);
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&notebook);
+  main_dialog_box.append(&passphrase_frame);
+  main_dialog_box.append(&status_label);
+  main_dialog_box.append(&button_box);
+
+  slip39_dialog.set_child(Some(&main_dialog_box));
+
+  let entropy = Rc::new(entropy);
+
+  generate_button.connect_clicked(clone!(
+    #[strong]
+    entropy,
+    #[weak]
+    group_threshold_spinbutton,
+    #[weak]
+    groups_entry,
+    #[weak]
+    iteration_exponent_spinbutton,
+    #[weak]
+    passphrase_entry,
+    #[weak]
+    shares_text,
+    #[weak]
+    status_label,
+    move |_| {
+      shares_text.buffer().set_text("");
+
+      // Groups are written as member threshold/member count, separated by commas
+      let groups = groups_entry
+        .text()
+        .split(',')
+        .map(|group| {
+          let (threshold, count) = group.trim().split_once('/')?;
+          Some(slip39::Slip39Group {
+            member_threshold: threshold.trim().parse().ok()?,
+            member_count: count.trim().parse().ok()?,
+          })
+        })
+        .collect::<Option<Vec<_>>>();
+
+      let Some(groups) = groups else {
+        status_label.set_text(&t!("error.slip39.groups", value = groups_entry.text()));
+        return;
+      };
+
+      let shares = slip39::get_master_secret_from_entropy(&entropy).and_then(|master_secret| {
+        slip39::generate_slip39_shares(
+          &master_secret,
+          &passphrase_entry.text(),
+          group_threshold_spinbutton.value_as_int() as u8,
+          &groups,
+          iteration_exponent_spinbutton.value_as_int() as u8,
+          true,
+        )
+      });
+
+      match shares {
+        Ok(shares) => {
+          let mut output = qr2m_lib::SecretString::default();
+
+          for (group_index, (group_shares, group)) in shares.iter().zip(&groups).enumerate() {
+            output = format!(
+              "{output}{}\n{}\n\n",
+              t!(
+                "UI.main.seed.slip39.group",
+                value = group_index + 1,
+                threshold = group.member_threshold,
+                count = group.member_count
+              ),
+              group_shares
+                .iter()
+                .map(|share| share.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+            )
+            .into();
+          }
+
+          shares_text.buffer().set_text(output.trim_end());
+          status_label.set_text("");
+        }
+        Err(err) => {
+          d3bug(&format!("generate_slip39_shares: {err:?}"), "error");
+          status_label.set_text(&err.to_string());
+        }
+      }
+    }
+  ));
+
+  recover_button.connect_clicked(clone!(
+    #[weak]
+    recover_shares_text,
+    #[weak]
+    passphrase_entry,
+    #[weak]
+    recovered_text,
+    #[weak]
+    status_label,
+    move |_| {
+      recovered_text.buffer().set_text("");
+
+      let buffer = recover_shares_text.buffer();
+      let input = qr2m_lib::SecretString::new(
+        buffer
+          .text(&buffer.start_iter(), &buffer.end_iter(), false)
+          .to_string(),
+      );
+      let mnemonics: Vec<&str> = input.lines().collect();
+
+      let mnemonic_dictionary = {
+        let lock_app_settings = APP_SETTINGS.read().unwrap();
+        lock_app_settings
+          .wallet_mnemonic_dictionary
+          .clone()
+          .unwrap_or_default()
+      };
+
+      let recovered = slip39::recover_slip39_secret(&mnemonics, &passphrase_entry.text())
+        .and_then(|master_secret| slip39::get_entropy_from_master_secret(&master_secret))
+        .and_then(|entropy| {
+          let mnemonic_words = keys::encode_mnemonic_words(&entropy, Some(&mnemonic_dictionary))?;
+          Ok(qr2m_lib::SecretString::new(format!(
+            "{entropy}\n\n{mnemonic_words}"
+          )))
+        });
+
+      match recovered {
+        Ok(recovered) => {
+          recovered_text.buffer().set_text(&recovered);
+          status_label.set_text("");
+        }
+        Err(err) => {
+          d3bug(&format!("recover_slip39_secret: {err:?}"), "error");
+          status_label.set_text(&err.to_string());
+        }
+      }
+    }
+  ));
+
+  close_button.connect_clicked(clone!(
+    #[weak]
+    slip39_dialog,
+    #[weak]
+    shares_text,
+    #[weak]
+    recovered_text,
+    move |_| {
+      shares_text.buffer().set_text("");
+      recovered_text.buffer().set_text("");
+      slip39_dialog.close();
+    }
+  ));
+
+  slip39_dialog.present();
+}
+
+fn create_mnemonic_validator_window(mnemonic: qr2m_lib::SecretString) {
+  #[cfg(debug_assertions)]
+  println!("[+] Mnemonic validator");
+
+  let validator_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.seed.mnemonic.validate").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(450)
+    .width_request(600)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let dictionary_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.dictionary")));
+  let auto_detect_label = t!("UI.main.seed.mnemonic.validate.auto").to_string();
+  let mut dictionary_options = vec![auto_detect_label.as_str()];
+  dictionary_options.extend(VALID_MNEMONIC_DICTIONARY);
+  let dictionary_dropdown = gtk::DropDown::from_strings(&dictionary_options);
+  dictionary_frame.set_child(Some(&dictionary_dropdown));
+
+  let input_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.validate.input")));
+  let input_text = gtk::TextView::new();
+  input_text.set_wrap_mode(gtk::WrapMode::Word);
+  input_text.set_hexpand(true);
+  input_text.set_vexpand(true);
+  input_text.set_left_margin(5);
+  input_text.set_top_margin(5);
+  input_text.buffer().set_text(&mnemonic);
+  input_frame.set_child(Some(&input_text));
+
+  let result_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.validate.result")));
+  let result_scrolled_window = gtk::ScrolledWindow::new();
+  let result_text = gtk::TextView::new();
+  result_text.set_wrap_mode(gtk::WrapMode::Word);
+  result_text.set_editable(false);
+  result_text.set_hexpand(true);
+  result_text.set_vexpand(true);
+  result_text.set_left_margin(5);
+  result_text.set_top_margin(5);
+  result_scrolled_window.set_child(Some(&result_text));
+  result_frame.set_child(Some(&result_scrolled_window));
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let validate_button = gtk::Button::with_label(&t!("UI.main.seed.mnemonic.validate"));
+  let last_word_button = gtk::Button::with_label(&t!("UI.main.seed.mnemonic.validate.last"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  button_box.append(&validate_button);
+  button_box.append(&last_word_button);
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&dictionary_frame);
+  main_dialog_box.append(&input_frame);
+  main_dialog_box.append(&result_frame);
+  main_dialog_box.append(&button_box);
+
+  validator_dialog.set_child(Some(&main_dialog_box));
+
+  validate_button.connect_clicked(clone!(
+    #[weak]
+    dictionary_dropdown,
+    #[weak]
+    input_text,
+    #[weak]
+    result_text,
+    move |_| {
+      let buffer = input_text.buffer();
+      let mnemonic = qr2m_lib::SecretString::new(
+        buffer
+          .text(&buffer.start_iter(), &buffer.end_iter(), false)
+          .to_string(),
+      );
+
+      // First dropdown entry is auto-detect
+      let dictionary = (dictionary_dropdown.selected() as usize)
+        .checked_sub(1)
+        .and_then(|index| VALID_MNEMONIC_DICTIONARY.get(index).copied());
+
+      let validation = match keys::validate_mnemonic_words(&mnemonic, dictionary) {
+        Ok(validation) => validation,
+        Err(err) => {
+          d3bug(&format!("validate_mnemonic_words: {err:?}"), "error");
+          result_text.buffer().set_text(&err.to_string());
+          return;
+        }
+      };
+
+      let mut output = Vec::new();
+
+      for issue in &validation.issues {
+        match issue {
+          keys::MnemonicIssue::InvalidWordCount(count) => {
+            output.push(t!("UI.main.seed.mnemonic.validate.count", value = count).to_string());
+          }
+          keys::MnemonicIssue::UnknownWord {
+            position,
+            word,
+            suggestions,
+          } => {
+            output.push(
+              t!(
+                "UI.main.seed.mnemonic.validate.unknown",
+                position = position,
+                word = word,
+                value = validation.dictionary
+              )
+              .to_string(),
+            );
+
+            if !suggestions.is_empty() {
+              output.push(format!(
+                "  {}",
+                t!(
+                  "UI.main.seed.mnemonic.validate.suggestions",
+                  value = suggestions.join(", ")
+                )
+              ));
+            }
+          }
+          keys::MnemonicIssue::InvalidChecksum => {
+            output.push(t!("UI.main.seed.mnemonic.validate.checksum").to_string());
+          }
+        }
+      }
+
+      if let Some(entropy) = &validation.entropy {
+        output.push(
+          t!(
+            "UI.main.seed.mnemonic.validate.valid",
+            value = validation.dictionary
+          )
+          .to_string(),
+        );
+        output.push(format!(
+          "{}: {}",
+          t!("UI.main.seed.entropy"),
+          entropy.as_str()
+        ));
+      }
+
+      result_text.buffer().set_text(&output.join("\n"));
+    }
+  ));
+
+  last_word_button.connect_clicked(clone!(
+    #[weak]
+    dictionary_dropdown,
+    #[weak]
+    input_text,
+    #[weak]
+    result_text,
+    move |_| {
+      let buffer = input_text.buffer();
+      let mnemonic = qr2m_lib::SecretString::new(
+        buffer
+          .text(&buffer.start_iter(), &buffer.end_iter(), false)
+          .to_string(),
+      );
+
+      // First dropdown entry is auto-detect
+      let dictionary = (dictionary_dropdown.selected() as usize)
+        .checked_sub(1)
+        .and_then(|index| VALID_MNEMONIC_DICTIONARY.get(index).copied());
+
+      match keys::find_last_mnemonic_words(&mnemonic, dictionary) {
+        Ok(last_words) => {
+          result_text.buffer().set_text(&format!(
+            "{}\n{}",
+            t!(
+              "UI.main.seed.mnemonic.validate.candidates",
+              value = last_words.len()
+            ),
+            last_words.join(" ")
+          ));
+        }
+        Err(err) => {
+          d3bug(&format!("find_last_mnemonic_words: {err:?}"), "error");
+          result_text.buffer().set_text(&err.to_string());
+        }
+      }
+    }
+  ));
+
+  close_button.connect_clicked(clone!(
+    #[weak]
+    validator_dialog,
+    #[weak]
+    input_text,
+    #[weak]
+    result_text,
+    move |_| {
+      input_text.buffer().set_text("");
+      result_text.buffer().set_text("");
+      validator_dialog.close();
+    }
+  ));
+
+  validator_dialog.present();
+}
+
+fn create_mnemonic_recovery_window(
+  mnemonic: qr2m_lib::SecretString,
+  passphrase: qr2m_lib::SecretString,
+) {
+  #[cfg(debug_assertions)]
+  println!("[+] Mnemonic recovery");
+
+  let recovery_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.seed.mnemonic.recover").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(550)
+    .width_request(650)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let input_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.recover.input")));
+  let input_text = gtk::TextView::new();
+  input_text.set_wrap_mode(gtk::WrapMode::Word);
+  input_text.set_hexpand(true);
+  input_text.set_vexpand(true);
+  input_text.set_left_margin(5);
+  input_text.set_top_margin(5);
+  input_text.buffer().set_text(&mnemonic);
+  input_frame.set_child(Some(&input_text));
+
+  let options_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+
+  let dictionary_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.dictionary")));
+  let auto_detect_label = t!("UI.main.seed.mnemonic.validate.auto").to_string();
+  let mut dictionary_options = vec![auto_detect_label.as_str()];
+  dictionary_options.extend(VALID_MNEMONIC_DICTIONARY);
+  let dictionary_dropdown = gtk::DropDown::from_strings(&dictionary_options);
+  dictionary_frame.set_child(Some(&dictionary_dropdown));
+
+  let passphrase_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.pass")));
+  let passphrase_entry = gtk::PasswordEntry::new();
+  passphrase_entry.set_show_peek_icon(true);
+  passphrase_entry.set_hexpand(true);
+  passphrase_entry.set_text(&passphrase);
+  passphrase_frame.set_child(Some(&passphrase_entry));
+
+  let compressed_frame = gtk::Frame::new(Some(&t!("UI.main.address.options.compressed")));
+  let compressed_box = gtk::Box::new(gtk::Orientation::Horizontal, 20);
+  let compressed_checkbox = gtk::CheckButton::new();
+  compressed_checkbox.set_active(true);
+  compressed_box.set_halign(gtk::Align::Center);
+  compressed_box.append(&compressed_checkbox);
+  compressed_frame.set_child(Some(&compressed_box));
+
+  options_box.append(&dictionary_frame);
+  options_box.append(&passphrase_frame);
+  options_box.append(&compressed_frame);
+
+  let coin_index = {
+    let wallet_settings = WALLET_SETTINGS.lock().unwrap();
+    wallet_settings.coin_index.unwrap_or_default()
+  };
+
+  let path_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.recover.path")));
+  let path_entry = gtk::Entry::new();
+  path_entry.set_text(&format!("m/44'/{coin_index}'/0'/0/0"));
+  path_frame.set_child(Some(&path_entry));
+
+  let address_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.recover.address")));
+  let address_entry = gtk::Entry::new();
+  address_frame.set_child(Some(&address_entry));
+
+  let progress_bar = gtk::ProgressBar::new();
+  progress_bar.set_show_text(true);
+
+  let result_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.recover.result")));
+  let result_text = gtk::TextView::new();
+  result_text.set_wrap_mode(gtk::WrapMode::Word);
+  result_text.set_editable(false);
+  result_text.set_hexpand(true);
+  result_text.set_left_margin(5);
+  result_text.set_top_margin(5);
+  result_frame.set_child(Some(&result_text));
+
+  let status_label = gtk::Label::new(None);
+  status_label.set_wrap(true);
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let search_button = gtk::Button::with_label(&t!("UI.main.seed.mnemonic.recover.search"));
+  let stop_button = gtk::Button::with_label(&t!("UI.main.seed.mnemonic.recover.stop"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  stop_button.set_sensitive(false);
+  button_box.append(&search_button);
+  button_box.append(&stop_button);
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&input_frame);
+  main_dialog_box.append(&options_box);
+  main_dialog_box.append(&path_frame);
+  main_dialog_box.append(&address_frame);
+  main_dialog_box.append(&progress_bar);
+  main_dialog_box.append(&result_frame);
+  main_dialog_box.append(&status_label);
+  main_dialog_box.append(&button_box);
+
+  recovery_dialog.set_child(Some(&main_dialog_box));
+
+  let cancel_flag = Arc::new(Mutex::new(false));
+
+  search_button.connect_clicked(clone!(
+    #[strong]
+    cancel_flag,
+    #[weak]
+    input_text,
+    #[weak]
+    dictionary_dropdown,
+    #[weak]
+    passphrase_entry,
+    #[weak]
+    compressed_checkbox,
+    #[weak]
+    path_entry,
+    #[weak]
+    address_entry,
+    #[weak]
+    progress_bar,
+    #[weak]
+    result_text,
+    #[weak]
+    status_label,
+    #[weak]
+    stop_button,
+    move |search_button| {
+      d3bug(">>> search_button.connect_clicked", "debug");
+
+      let wallet_settings = {
+        let lock = WALLET_SETTINGS.lock().unwrap();
+        lock.clone()
+      };
+
+      let (Some(coin_index), Some(key_derivation)) =
+        (wallet_settings.coin_index, wallet_settings.key_derivation)
+      else {
+        status_label.set_text(&t!("error.recovery.coin"));
+        return;
+      };
+
+      let derivation_path = path_entry.text().to_string();
+      let bip = keys::parse_derivation_path(&derivation_path)
+        .ok()
+        .and_then(|path| path.children().first().map(|purpose| purpose.index()))
+        .unwrap_or(44);
+
+      let coin = keys::AddressHocusPokus {
+        coin_index,
+        derivation_path,
+        master_private_key_bytes: Default::default(),
+        master_chain_code_bytes: Default::default(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: wallet_settings.public_key_hash.unwrap_or_default(),
+        script_hash: wallet_settings.script_hash.unwrap_or_default(),
+        bech32_hrp: wallet_settings.bech32_hrp.unwrap_or_default(),
+        key_derivation,
+        wallet_import_format: wallet_settings.wallet_import_format.unwrap_or_default(),
+        hash: wallet_settings.hash.unwrap_or_default(),
+        chain_id: wallet_settings.chain_id,
+        eip1191: wallet_settings.eip1191.unwrap_or_default(),
+        bip,
+        compressed: compressed_checkbox.is_active(),
+      };
+
+      let buffer = input_text.buffer();
+      let mnemonic = qr2m_lib::SecretString::new(
+        buffer
+          .text(&buffer.start_iter(), &buffer.end_iter(), false)
+          .to_string(),
+      );
+
+      // First dropdown entry is auto-detect
+      let dictionary = (dictionary_dropdown.selected() as usize)
+        .checked_sub(1)
+        .and_then(|index| VALID_MNEMONIC_DICTIONARY.get(index).copied());
+
+      let passphrase = qr2m_lib::SecretString::new(passphrase_entry.text().to_string());
+      let target_address = address_entry.text().to_string();
+
+      *cancel_flag.lock().unwrap() = false;
+      progress_bar.set_fraction(0.0);
+      result_text.buffer().set_text("");
+      status_label.set_text("");
+      search_button.set_sensitive(false);
+      stop_button.set_sensitive(true);
+
+      let (channel_sender_progress, channel_receiver_progress) = mpsc::channel();
+      let (channel_sender_result, channel_receiver_result) = mpsc::channel();
+
+      std::thread::spawn(clone!(
+        #[strong]
+        cancel_flag,
+        move || {
+          let result = keys::recover_mnemonic_words(
+            &mnemonic,
+            dictionary,
+            &passphrase,
+            &coin,
+            &target_address,
+            &cancel_flag,
+            &|progress| channel_sender_progress.send(progress).unwrap_or_default(),
+          );
+
+          channel_sender_result.send(result).unwrap_or_default();
+        }
+      ));
+
+      glib::timeout_add_local(
+        std::time::Duration::from_millis(100),
+        clone!(
+          #[strong]
+          cancel_flag,
+          #[strong]
+          progress_bar,
+          #[strong]
+          result_text,
+          #[strong]
+          status_label,
+          #[strong]
+          search_button,
+          #[strong]
+          stop_button,
+          move || {
+            while let Ok(progress) = channel_receiver_progress.try_recv() {
+              progress_bar.set_fraction(progress);
+            }
+
+            let Ok(result) = channel_receiver_result.try_recv() else {
+              return glib::ControlFlow::Continue;
+            };
+
+            match result {
+              Ok(Some(mnemonic)) => {
+                result_text.buffer().set_text(&mnemonic);
+                status_label.set_text(&t!("UI.main.seed.mnemonic.recover.found"));
+              }
+              Ok(None) if *cancel_flag.lock().unwrap() => {
+                status_label.set_text(&t!("UI.main.seed.mnemonic.recover.cancelled"));
+              }
+              Ok(None) => {
+                status_label.set_text(&t!("UI.main.seed.mnemonic.recover.missing"));
+              }
+              Err(err) => {
+                d3bug(&format!("recover_mnemonic_words: {err:?}"), "error");
+                status_label.set_text(&err.to_string());
+              }
+            }
+
+            search_button.set_sensitive(true);
+            stop_button.set_sensitive(false);
+
+            glib::ControlFlow::Break
+          }
+        ),
+      );
+    }
+  ));
+
+  stop_button.connect_clicked(clone!(
+    #[strong]
+    cancel_flag,
+    move |_| {
+      *cancel_flag.lock().unwrap() = true;
+    }
+  ));
+
+  close_button.connect_clicked(clone!(
+    #[weak]
+    recovery_dialog,
+    move |_| {
+      recovery_dialog.close();
+    }
+  ));
+
+  // Both the close button and the window manager end here, so a running search is stopped
+  recovery_dialog.connect_close_request(clone!(
+    #[strong]
+    cancel_flag,
+    #[strong]
+    input_text,
+    #[strong]
+    result_text,
+    move |_| {
+      *cancel_flag.lock().unwrap() = true;
+      input_text.buffer().set_text("");
+      result_text.buffer().set_text("");
+
+      glib::Propagation::Proceed
+    }
+  ));
+
+  recovery_dialog.present();
+}
+
+fn create_electrum_window(mnemonic: qr2m_lib::SecretString, passphrase: qr2m_lib::SecretString) {
+  #[cfg(debug_assertions)]
+  println!("[+] Electrum seed");
+
+  let electrum_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.seed.mnemonic.electrum").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(550)
+    .width_request(800)
+    .resizable(true)
+    .modal(true)
+    .build();
+
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let input_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.words")));
+  let input_text = gtk::TextView::new();
+  input_text.set_wrap_mode(gtk::WrapMode::Word);
+  input_text.set_hexpand(true);
+  input_text.set_left_margin(5);
+  input_text.set_top_margin(5);
+  input_text.buffer().set_text(&mnemonic);
+  input_frame.set_child(Some(&input_text));
+
+  let passphrase_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.pass")));
+  let passphrase_entry = gtk::PasswordEntry::new();
+  passphrase_entry.set_show_peek_icon(true);
+  passphrase_entry.set_text(&passphrase);
+  passphrase_frame.set_child(Some(&passphrase_entry));
+
+  let options_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+
+  let seed_type_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.electrum.type")));
+  let seed_type_label = gtk::Label::new(None);
+  seed_type_label.set_hexpand(true);
+  seed_type_frame.set_child(Some(&seed_type_label));
+
+  let start_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.electrum.start")));
+  let start_adjustment =
+    gtk::Adjustment::new(0.0, 0.0, WALLET_MAX_ADDRESSES as f64, 1.0, 10.0, 0.0);
+  let start_spinbutton = gtk::SpinButton::new(Some(&start_adjustment), 1.0, 0);
+  start_frame.set_child(Some(&start_spinbutton));
+
+  let count_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.electrum.count")));
+  let count_adjustment = gtk::Adjustment::new(20.0, 1.0, 1000.0, 1.0, 10.0, 0.0);
+  let count_spinbutton = gtk::SpinButton::new(Some(&count_adjustment), 1.0, 0);
+  count_frame.set_child(Some(&count_spinbutton));
+
+  let change_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.electrum.change")));
+  let change_checkbox = gtk::CheckButton::new();
+  change_checkbox.set_halign(gtk::Align::Center);
+  change_frame.set_child(Some(&change_checkbox));
+
+  options_box.append(&seed_type_frame);
+  options_box.append(&start_frame);
+  options_box.append(&count_frame);
+  options_box.append(&change_frame);
+
+  let addresses_frame = gtk::Frame::new(Some(&t!("UI.main.seed.mnemonic.electrum.addresses")));
+  let addresses_scrolled_window = gtk::ScrolledWindow::new();
+  let addresses_text = gtk::TextView::new();
+  addresses_text.set_wrap_mode(gtk::WrapMode::Char);
+  addresses_text.set_editable(false);
+  addresses_text.set_monospace(true);
+  addresses_text.set_hexpand(true);
+  addresses_text.set_vexpand(true);
+  addresses_text.set_left_margin(5);
+  addresses_text.set_top_margin(5);
+  addresses_scrolled_window.set_child(Some(&addresses_text));
+  addresses_frame.set_child(Some(&addresses_scrolled_window));
+
+  let status_label = gtk::Label::new(None);
+  status_label.set_wrap(true);
+
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let generate_button = gtk::Button::with_label(&t!("UI.main.seed.mnemonic.electrum.generate"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  button_box.append(&generate_button);
+  button_box.append(&close_button);
 
-      *generator_handler.lock().unwrap() = Some((address_generation_handler, cancel_flag.clone()));
+  main_dialog_box.append(&input_frame);
+  main_dialog_box.append(&passphrase_frame);
+  main_dialog_box.append(&options_box);
+  main_dialog_box.append(&addresses_frame);
+  main_dialog_box.append(&status_label);
+  main_dialog_box.append(&button_box);
 
-      d3bug("<<< generate_addresses_button.connect_clicked", "debug");
+  electrum_dialog.set_child(Some(&main_dialog_box));
+
+  let update_seed_type = clone!(
+    #[weak]
+    seed_type_label,
+    #[weak]
+    generate_button,
+    move |buffer: &gtk::TextBuffer| {
+      let mnemonic = qr2m_lib::SecretString::new(
+        buffer
+          .text(&buffer.start_iter(), &buffer.end_iter(), false)
+          .to_string(),
+      );
+
+      match keys::get_electrum_seed_type(&mnemonic) {
+        Some(seed_type) => {
+          seed_type_label.set_text(seed_type.name());
+          generate_button.set_sensitive(true);
+        }
+        None => {
+          seed_type_label.set_text(&t!("UI.main.seed.mnemonic.electrum.unknown"));
+          generate_button.set_sensitive(false);
+        }
+      }
     }
-  ));
+  );
 
-  // JUMP: Delete Addresses button
-  delete_addresses_button.connect_clicked(clone!(
-    #[strong]
-    address_store,
+  update_seed_type(&input_text.buffer());
+  input_text.buffer().connect_changed(update_seed_type);
+
+  generate_button.connect_clicked(clone!(
     #[weak]
-    delete_addresses_button_box,
+    input_text,
     #[weak]
-    address_start_spinbutton,
+    passphrase_entry,
     #[weak]
-    address_generation_progress_bar,
+    start_spinbutton,
     #[weak]
-    address_fill_progress_bar,
+    count_spinbutton,
     #[weak]
-    window,
-    #[strong]
-    address_generation_speed_label,
+    change_checkbox,
+    #[weak]
+    addresses_text,
+    #[weak]
+    status_label,
     move |_| {
-      d3bug(">>> delete_addresses_button.connect_clicked", "debug");
-
-      let busy_cursor = gtk::gdk::Cursor::from_name("wait", None);
-      window.set_cursor(busy_cursor.as_ref());
-      address_store.remove_all();
-      CRYPTO_ADDRESS.clear();
-      address_start_spinbutton.set_text("0");
+      addresses_text.buffer().set_text("");
 
-      address_generation_progress_bar.set_fraction(0.0);
-      address_generation_progress_bar.set_show_text(false);
-      address_fill_progress_bar.set_fraction(0.0);
-      address_fill_progress_bar.set_show_text(false);
+      let buffer = input_text.buffer();
+      let mnemonic = qr2m_lib::SecretString::new(
+        buffer
+          .text(&buffer.start_iter(), &buffer.end_iter(), false)
+          .to_string(),
+      );
 
-      delete_addresses_button_box.set_visible(false);
-      address_generation_speed_label.set_label("0/sec");
-      window.set_cursor(None);
+      match keys::generate_electrum_addresses(
+        &mnemonic,
+        &passphrase_entry.text(),
+        change_checkbox.is_active(),
+        start_spinbutton.value_as_int() as u32,
+        count_spinbutton.value_as_int() as u32,
+      ) {
+        Ok(addresses) => {
+          let output = qr2m_lib::SecretString::new(
+            addresses
+              .iter()
+              .map(|(derivation_path, address)| {
+                format!(
+                  "{derivation_path}\t{}\t{}",
+                  address.address,
+                  address.private_key.as_str()
+                )
+              })
+              .collect::<Vec<String>>()
+              .join("\n"),
+          );
 
-      d3bug("<<< delete_addresses_button.connect_clicked", "debug");
+          addresses_text.buffer().set_text(&output);
+          status_label.set_text("");
+        }
+        Err(err) => {
+          d3bug(&format!("generate_electrum_addresses: {err:?}"), "error");
+          status_label.set_text(&err.to_string());
+        }
+      }
     }
   ));
 
-  // JUMP: Stop Addresses button
-  stop_address_generation_button.connect_clicked(clone!(
-    #[strong]
-    generator_handler,
-    #[strong]
-    app_messages_state,
+  close_button.connect_clicked(clone!(
     #[weak]
-    delete_addresses_button_box,
+    electrum_dialog,
     #[weak]
-    stop_addresses_button_box,
+    input_text,
     #[weak]
-    window,
+    addresses_text,
     move |_| {
-      d3bug(
-        ">>> stop_address_generation_button.connect_clicked",
-        "debug",
-      );
+      input_text.buffer().set_text("");
+      addresses_text.buffer().set_text("");
+      electrum_dialog.close();
+    }
+  ));
 
-      // TODO: Add message what to do with generated addresses, import or drop?
-      if let Some((handle, cancel_flag)) = generator_handler.lock().unwrap().take() {
-        *cancel_flag.lock().unwrap() = true;
-        let _ = handle.join();
-      } else {
-        d3bug("No generator handle", "error");
-      }
+  electrum_dialog.present();
+}
 
-      let message = "Address generation aborted";
-      let lock_app_messages = app_messages_state.borrow();
-      match lock_app_messages.queue_message(message.to_string(), gtk::MessageType::Warning) {
-        Ok(_) => d3bug("Address generation aborted", "info"),
-        Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
-      };
+fn create_sign_message_window(address: keys::Address) {
+  #[cfg(debug_assertions)]
+  println!("[+] Sign message for {}", address.address);
 
-      delete_addresses_button_box.set_visible(true);
-      stop_addresses_button_box.set_visible(false);
+  let sign_message_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.address.sign").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .height_request(350)
+    .width_request(600)
+    .resizable(true)
+    .modal(true)
+    .build();
 
-      window.set_cursor(None);
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
+  main_dialog_box.set_vexpand(true);
+
+  let address_frame = gtk::Frame::new(Some(&t!("UI.main.address.table.address")));
+  let address_label = gtk::Label::new(Some(&address.address));
+  address_label.set_selectable(true);
+  address_label.set_margin_top(5);
+  address_label.set_margin_bottom(5);
+  address_frame.set_child(Some(&address_label));
+
+  let message_frame = gtk::Frame::new(Some(&t!("UI.main.address.sign.message")));
+  let message_text = gtk::TextView::new();
+  message_text.set_wrap_mode(gtk::WrapMode::WordChar);
+  message_text.set_hexpand(true);
+  message_text.set_vexpand(true);
+  message_frame.set_child(Some(&message_text));
+
+  let signature_frame = gtk::Frame::new(Some(&t!("UI.main.address.sign.signature")));
+  let signature_text = gtk::TextView::new();
+  signature_text.set_wrap_mode(gtk::WrapMode::Char);
+  signature_text.set_hexpand(true);
+  signature_text.set_vexpand(true);
+  signature_frame.set_child(Some(&signature_text));
+
+  let status_label = gtk::Label::new(None);
+  status_label.set_wrap(true);
 
-      d3bug(
-        "<<< stop_address_generation_button.connect_clicked",
-        "debug",
+  let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+  button_box.set_hexpand(true);
+  button_box.set_halign(gtk::Align::Center);
+
+  let sign_button = gtk::Button::with_label(&t!("UI.main.address.sign"));
+  let verify_button = gtk::Button::with_label(&t!("UI.main.address.sign.verify"));
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  button_box.append(&sign_button);
+  button_box.append(&verify_button);
+  button_box.append(&close_button);
+
+  main_dialog_box.append(&address_frame);
+  main_dialog_box.append(&message_frame);
+  main_dialog_box.append(&signature_frame);
+  main_dialog_box.append(&status_label);
+  main_dialog_box.append(&button_box);
+
+  sign_message_dialog.set_child(Some(&main_dialog_box));
+
+  let address = Rc::new(address);
+
+  sign_button.connect_clicked(clone!(
+    #[strong]
+    address,
+    #[weak]
+    message_text,
+    #[weak]
+    signature_text,
+    #[weak]
+    status_label,
+    move |_| {
+      let buffer = message_text.buffer();
+      let message = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+
+      match sign::sign_message(&address, &message) {
+        Ok((format, signature)) => {
+          signature_text.buffer().set_text(&signature);
+          status_label.set_text(format.name());
+        }
+        Err(err) => {
+          d3bug(&format!("sign_message: {err:?}"), "error");
+          signature_text.buffer().set_text("");
+          status_label.set_text(&err.to_string());
+        }
+      }
+    }
+  ));
+
+  verify_button.connect_clicked(clone!(
+    #[strong]
+    address,
+    #[weak]
+    message_text,
+    #[weak]
+    signature_text,
+    #[weak]
+    status_label,
+    move |_| {
+      let message_buffer = message_text.buffer();
+      let message = message_buffer.text(
+        &message_buffer.start_iter(),
+        &message_buffer.end_iter(),
+        false,
       );
+      let signature_buffer = signature_text.buffer();
+      let signature = signature_buffer
+        .text(
+          &signature_buffer.start_iter(),
+          &signature_buffer.end_iter(),
+          false,
+        )
+        .trim()
+        .to_string();
+
+      match sign::verify_message(&address.address, &message, &signature) {
+        Ok(true) => {
+          status_label.set_text(&t!("UI.main.address.sign.valid", value = &address.address))
+        }
+        Ok(false) => status_label.set_text(&t!("UI.main.address.sign.invalid")),
+        Err(err) => {
+          d3bug(&format!("verify_message: {err:?}"), "error");
+          status_label.set_text(&err.to_string());
+        }
+      }
     }
   ));
 
-  // Main sidebar
-  let main_window_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
-  let main_sidebar_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
-  let main_infobar_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+  close_button.connect_clicked(clone!(
+    #[weak]
+    sign_message_dialog,
+    move |_| {
+      sign_message_dialog.close();
+    }
+  ));
 
-  main_sidebar_box.append(&stack_sidebar);
-  main_sidebar_box.append(&stack);
-  main_infobar_box.append(&info_bar);
-  main_infobar_box.set_hexpand(true);
-  main_window_box.append(&main_sidebar_box);
-  main_window_box.append(&main_infobar_box);
-  main_window_box.set_hexpand(true);
+  sign_message_dialog.present();
+}
 
-  {
-    let lock_app_messages = app_messages_state.borrow();
-    match lock_app_messages.queue_message(t!("hello").to_string(), gtk::MessageType::Info) {
-      Ok(_) => {}
-      Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
-    };
-  }
+fn create_monero_keys_window(address: keys::Address) {
+  #[cfg(debug_assertions)]
+  println!("[+] Monero keys for {}", address.address);
 
-  window.set_child(Some(&main_window_box));
+  let monero_keys_dialog = gtk::ApplicationWindow::builder()
+    .title(t!("UI.main.address.monero").to_string())
+    .halign(gtk::Align::Center)
+    .valign(gtk::Align::Center)
+    .width_request(600)
+    .resizable(true)
+    .modal(true)
+    .build();
 
-  #[cfg(feature = "dev")]
-  {
-    if let Some(value) = start_time {
-      let elapsed = value.elapsed();
+  let main_dialog_box = gtk::Box::new(gtk::Orientation::Vertical, 10);
+  main_dialog_box.set_margin_bottom(10);
+  main_dialog_box.set_margin_top(10);
+  main_dialog_box.set_margin_start(10);
+  main_dialog_box.set_margin_end(10);
+  main_dialog_box.set_hexpand(true);
 
-      let message = format!("Application startup time: {elapsed:.2?}");
-      // println!("{}", message);
+  let values = [
+    (
+      t!("UI.main.address.table.address"),
+      address.address.as_str(),
+    ),
+    (
+      t!("UI.main.address.monero.view"),
+      address.private_view_key.as_deref().unwrap_or_default(),
+    ),
+    (
+      t!("UI.main.address.monero.mnemonic"),
+      address.mnemonic.as_deref().unwrap_or_default(),
+    ),
+  ];
 
-      let lock_app_messages = app_messages_state.borrow();
-      match lock_app_messages.queue_message(message, gtk::MessageType::Info) {
-        Ok(_) => {}
-        Err(err) => d3bug(&format!("queue_message: {err:?}"), "error"),
-      };
-    };
+  for (title, value) in values {
+    let value_frame = gtk::Frame::new(Some(&title));
+    let value_label = gtk::Label::new(Some(value));
+    value_label.set_selectable(true);
+    value_label.set_wrap(true);
+    value_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    value_label.set_margin_top(5);
+    value_label.set_margin_bottom(5);
+    value_label.set_margin_start(5);
+    value_label.set_margin_end(5);
+    value_frame.set_child(Some(&value_label));
+    main_dialog_box.append(&value_frame);
   }
 
-  window.connect_close_request(clone!(
-    #[strong]
-    last_window,
-    #[strong]
-    generator_handler,
-    move |window| {
-      let gui_last_width = window.width() as i64;
-      let gui_last_height = window.height() as i64;
-      let gui_maximized = window.is_maximized();
-
-      let gui_save_size = {
-        let app_settings_lock = APP_SETTINGS.read().unwrap();
-        app_settings_lock.gui_save_size.unwrap()
-      };
-
-      if gui_save_size {
-        std::thread::spawn(move || {
-          let mut settings = APP_SETTINGS.write().unwrap();
-          settings.update_value("gui_last_width", toml_edit::value(gui_last_width), None);
-          settings.update_value("gui_last_height", toml_edit::value(gui_last_height), None);
-          settings.update_value("gui_maximized", toml_edit::value(gui_maximized), None);
-          AppSettings::save_settings(&settings);
-        });
-      }
-
-      if let Some(window) = last_window.as_ref() {
-        window.set_visible(true);
-        window.present()
-      }
+  let close_button = gtk::Button::with_label(&t!("UI.button.close"));
+  close_button.set_halign(gtk::Align::Center);
+  main_dialog_box.append(&close_button);
 
-      if let Some((handle, cancel_flag)) = generator_handler.lock().unwrap().take() {
-        let _ = handle.join();
-        *cancel_flag.lock().unwrap() = true;
-      } else {
-        #[cfg(debug_assertions)]
-        eprintln!("No generator handle!");
-      }
+  monero_keys_dialog.set_child(Some(&main_dialog_box));
 
-      glib::Propagation::Proceed
+  close_button.connect_clicked(clone!(
+    #[weak]
+    monero_keys_dialog,
+    move |_| {
+      monero_keys_dialog.close();
     }
   ));
 
-  Ok(window)
+  monero_keys_dialog.present();
 }
 
 #[cfg(feature = "dev")]
@@ -7104,27 +10036,32 @@ fn update_derivation_label(dp: DerivationPath, label: gtk::TextView) {
   #[cfg(debug_assertions)]
   println!("[+] {}", &t!("log.update_derivation_label").to_string());
 
-  let mut path = String::new();
-  path.push('m');
-
-  path.push_str(&format!("/{}", dp.bip.unwrap_or_default()));
-  if dp.hardened_bip.unwrap_or_default() {
-    path.push('\'')
-  }
+  let mut segments = vec![
+    (dp.bip.unwrap_or_default(), dp.hardened_bip.unwrap_or_default()),
+    (dp.coin.unwrap_or_default(), dp.hardened_coin.unwrap_or_default()),
+    (
+      dp.address.unwrap_or_default(),
+      dp.hardened_address.unwrap_or_default(),
+    ),
+  ];
 
-  path.push_str(&format!("/{}", dp.coin.unwrap_or_default()));
-  if dp.hardened_coin.unwrap_or_default() {
-    path.push('\'')
+  if dp.bip.unwrap() != 32 {
+    segments.push((dp.purpose.unwrap_or_default(), false));
   }
 
-  path.push_str(&format!("/{}", dp.address.unwrap_or_default()));
-  if dp.hardened_address.unwrap_or_default() {
-    path.push('\'')
-  }
+  let children = match segments
+    .into_iter()
+    .map(|(index, hardened)| qr2m_lib::ChildNumber::new(index, hardened))
+    .collect::<Result<Vec<qr2m_lib::ChildNumber>, String>>()
+  {
+    Ok(children) => children,
+    Err(err) => {
+      d3bug(&format!("update_derivation_label: {err}"), "error");
+      return;
+    }
+  };
 
-  if dp.bip.unwrap() != 32 {
-    path.push_str(&format!("/{}", dp.purpose.unwrap_or_default()));
-  }
+  let path = qr2m_lib::DerivationPath::from_children(children).to_string();
 
   #[cfg(debug_assertions)]
   println!("\t- Derivation path: {path:?}");
@@ -7522,13 +10459,10 @@ fn create_new_wallet_window(
           }
         };
 
-      d3bug(&format!("entropy: {entropy:?}"), "debug");
-      d3bug(&format!("mnemonic_words: {mnemonic_words:?}"), "debug");
-      d3bug(
-        &format!("mnemonic_rng_string: {mnemonic_rng_string:?}"),
-        "debug",
-      );
-      d3bug(&format!("seed: {seed:?}"), "debug");
+      d3bug("entropy: [REDACTED]", "debug");
+      d3bug("mnemonic_words: [REDACTED]", "debug");
+      d3bug("mnemonic_rng_string: [REDACTED]", "debug");
+      d3bug("seed: [REDACTED]", "debug");
 
       // Generate Master keys
       let (master_private, master_public) =
@@ -7545,7 +10479,7 @@ fn create_new_wallet_window(
           }
         };
 
-      d3bug(&format!("master_private: {master_private:?}"), "debug");
+      d3bug("master_private: [REDACTED]", "debug");
       d3bug(&format!("master_public: {master_public:?}"), "debug");
 
       let resource_path = std::path::Path::new("coin").join("ECDB.csv");
@@ -7568,15 +10502,13 @@ fn create_new_wallet_window(
           if columns.len() > 1 && columns[0] == "1" {
             let active_coin_index = columns[1].parse().unwrap_or(0);
 
-            let derivation_path = match selected_derivation_path_value {
-              Some(value) => {
-                if let "32" = *value {
-                  String::from("m/0'/0'/0'")
-                } else {
-                  format!("m/44'/{}'/0'/0/0'", active_coin_index)
-                }
-              }
-              None => format!("m/44'/{}'/0'/0/0'", active_coin_index),
+            let bip = selected_derivation_path_value
+              .and_then(|value| value.parse::<u32>().ok())
+              .unwrap_or(44);
+
+            let derivation_path = match bip {
+              32 => String::from("m/0'/0'/0'"),
+              _ => format!("m/{}'/{}'/0'/0/0'", bip, active_coin_index),
             };
 
             let magic_ingredients = keys::AddressHocusPokus {
@@ -7590,10 +10522,22 @@ fn create_new_wallet_window(
                 .master_chain_code_bytes
                 .clone()
                 .unwrap_or_default(),
+              master_public_key_bytes: wallet_settings
+                .master_public_key_bytes
+                .clone()
+                .unwrap_or_default(),
+              master_depth: wallet_settings.master_depth.unwrap_or_default(),
+              master_child_number: wallet_settings.master_child_number.unwrap_or_default(),
               public_key_hash: columns[8].parse().unwrap_or("".to_string()),
+              script_hash: columns[9].parse().unwrap_or("".to_string()),
+              bech32_hrp: columns[10].parse().unwrap_or("".to_string()),
               key_derivation: columns[4].parse().unwrap_or("".to_string()),
-              wallet_import_format: columns[10].parse().unwrap_or("".to_string()),
+              wallet_import_format: columns[11].parse().unwrap_or("".to_string()),
               hash: columns[5].parse().unwrap_or("".to_string()),
+              chain_id: columns.get(15).and_then(|value| value.parse().ok()),
+              eip1191: columns.get(16) == Some(&"TRUE"),
+              bip,
+              compressed: true,
             };
 
             let mut batch = Vec::new();
@@ -7606,6 +10550,8 @@ fn create_new_wallet_window(
                 &address.address,
                 &address.public_key,
                 &address.private_key,
+                address.private_view_key.as_deref().unwrap_or_default(),
+                address.mnemonic.as_deref().unwrap_or_default(),
               );
 
               batch.push(new_entry);
@@ -7635,47 +10581,13 @@ fn create_new_wallet_window(
 }
 
 fn derivation_path_to_integer(path: &str) -> FunctionOutput<String> {
-  if !path.starts_with("m") {
-    return Err(AppError::Custom("Path must start with 'm'".to_string()));
-  }
-
-  let segments = if path.len() > 1 {
-    if &path[1..2] != "/" {
-      return Err(AppError::Custom("Expected '/' after 'm'".to_string()));
-    }
-    path[2..].split('/').collect::<Vec<&str>>()
-  } else {
-    vec![]
-  };
-
-  if segments.len() > 5 {
-    return Err(AppError::Custom("Too many segments for u64".to_string()));
-  }
-
-  let mut result: u128 = 0;
-
-  for segment in segments.iter() {
-    if segment.is_empty() {
-      return Err(AppError::Custom("Empty segment not allowed".to_string()));
-    }
+  let derivation_path = keys::parse_derivation_path(path)?;
 
-    let (number_part, hardened) = if let Some(stripped) = segment.strip_suffix('\'') {
-      (stripped, true)
-    } else {
-      (*segment, false)
-    };
-
-    if number_part.is_empty() || !number_part.chars().all(|c| c.is_ascii_digit()) {
-      return Err(AppError::Custom("Invalid number in segment".to_string()));
-    }
-
-    let index: u32 = number_part
-      .parse()
-      .map_err(|err| AppError::Custom(format!("Invalid number in path: {err:?}")))?;
-    let value = if hardened { index + 0x80000000 } else { index };
-
-    result += value as u128;
-  }
+  let result: u128 = derivation_path
+    .children()
+    .iter()
+    .map(|child| child.to_raw() as u128)
+    .sum();
 
   Ok(result.to_string())
 }
@@ -7969,10 +10881,10 @@ fn generate_seed(
   {
     let mut wallet_settings = WALLET_SETTINGS.lock().unwrap();
     wallet_settings.entropy_checksum = Some(checksum.clone());
-    wallet_settings.entropy_string = Some(full_entropy.clone());
-    wallet_settings.mnemonic_passphrase = Some(passphrase_text.unwrap_or_default().to_string());
-    wallet_settings.mnemonic_words = Some(mnemonic_words.clone());
-    wallet_settings.seed = Some(seed_hex.clone());
+    wallet_settings.entropy_string = Some(full_entropy.clone().into());
+    wallet_settings.mnemonic_passphrase = Some(passphrase_text.unwrap_or_default().into());
+    wallet_settings.mnemonic_words = Some(mnemonic_words.clone().into());
+    wallet_settings.seed = Some(seed_hex.clone().into());
   }
 
   Ok((full_entropy, mnemonic_words, seed_hex))
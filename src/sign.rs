@@ -0,0 +1,2374 @@
+// authors = ["Control Owl <qr2m[at]r-o0-t[dot]wtf>"]
+// license = "CC-BY-NC-ND-4.0  [2023-2025]  Control Owl"
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+use crate::{AppError, FunctionOutput, d3bug, keys};
+use qr2m_lib::{ChildNumber, DerivationPath};
+use rand::Rng;
+use sha3::{Digest, Keccak256};
+
+const BITCOIN_MESSAGE_MAGIC: &str = "Bitcoin Signed Message:\n";
+const ETHEREUM_MESSAGE_MAGIC: &str = "\x19Ethereum Signed Message:\n";
+const BIP322_MESSAGE_TAG: &str = "BIP0322-signed-message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageSignatureFormat {
+  BitcoinLegacy,
+  Bip322Simple,
+  Eip191,
+}
+
+impl MessageSignatureFormat {
+  pub fn name(&self) -> &'static str {
+    match self {
+      MessageSignatureFormat::BitcoinLegacy => "Bitcoin Signed Message",
+      MessageSignatureFormat::Bip322Simple => "BIP322 simple",
+      MessageSignatureFormat::Eip191 => "EIP-191 personal_sign",
+    }
+  }
+}
+
+enum MessageAddress {
+  Base58 { hash: Vec<u8> },
+  WitnessV0 { program: Vec<u8> },
+  Taproot { program: Vec<u8> },
+  Evm { address: String },
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// MESSAGE SIGNING
+
+pub fn sign_message(
+  address: &keys::Address,
+  message: &str,
+) -> FunctionOutput<(MessageSignatureFormat, String)> {
+  d3bug(">>> sign_message", "debug");
+  d3bug(&format!("address {:?}", address.address), "debug");
+
+  let (secret_key, compressed) = decode_private_key(&address.private_key)?;
+
+  match parse_message_address(&address.address)? {
+    MessageAddress::Evm { address } => {
+      let signature = sign_message_eip191(&secret_key, message)?;
+      ensure_signer(verify_message_eip191(&address, message, &signature)?)?;
+
+      Ok((MessageSignatureFormat::Eip191, signature))
+    }
+    MessageAddress::Base58 { hash } => {
+      let secp = keys::secp256k1_context();
+      let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key);
+      let public_key_hash = calculate_public_key_hash(&public_key, compressed);
+
+      let header_offset = if public_key_hash == hash {
+        if compressed { 31 } else { 27 }
+      } else if compressed && calculate_p2sh_p2wpkh_hash(&public_key_hash) == hash {
+        35
+      } else {
+        return Err(AppError::Custom(
+          "Private key does not belong to this address".to_string(),
+        ));
+      };
+
+      Ok((
+        MessageSignatureFormat::BitcoinLegacy,
+        sign_message_bitcoin(&secret_key, message, header_offset),
+      ))
+    }
+    MessageAddress::WitnessV0 { program } => {
+      let secp = keys::secp256k1_context();
+      let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key);
+
+      if calculate_public_key_hash(&public_key, true) != program {
+        return Err(AppError::Custom(
+          "Private key does not belong to this address".to_string(),
+        ));
+      }
+
+      Ok((
+        MessageSignatureFormat::Bip322Simple,
+        sign_message_bip322_p2wpkh(&secret_key, &program, message),
+      ))
+    }
+    MessageAddress::Taproot { program } => Ok((
+      MessageSignatureFormat::Bip322Simple,
+      sign_message_bip322_p2tr(&secret_key, &program, message)?,
+    )),
+  }
+}
+
+pub fn verify_message(address: &str, message: &str, signature: &str) -> FunctionOutput<bool> {
+  d3bug(">>> verify_message", "debug");
+  d3bug(&format!("address {address:?}"), "debug");
+
+  let message_address = parse_message_address(address)?;
+
+  if let MessageAddress::Evm { address } = &message_address {
+    return verify_message_eip191(address, message, signature);
+  }
+
+  let signature = qr2m_lib::decode_base64(signature).map_err(AppError::Custom)?;
+
+  // Legacy signatures are always 65 bytes starting with a BIP137 header, a BIP322 witness
+  // starts with its item count instead
+  if signature.len() == 65 && (27..=42).contains(&signature[0]) {
+    return verify_message_bitcoin(&message_address, message, &signature);
+  }
+
+  match message_address {
+    MessageAddress::WitnessV0 { program } => {
+      verify_message_bip322_p2wpkh(&program, message, &signature)
+    }
+    MessageAddress::Taproot { program } => {
+      verify_message_bip322_p2tr(&program, message, &signature)
+    }
+    _ => Err(AppError::Custom(
+      "BIP322 signatures require a SegWit address".to_string(),
+    )),
+  }
+}
+
+fn ensure_signer(is_valid: bool) -> FunctionOutput<()> {
+  if is_valid {
+    Ok(())
+  } else {
+    Err(AppError::Custom(
+      "Private key does not belong to this address".to_string(),
+    ))
+  }
+}
+
+fn parse_message_address(address: &str) -> FunctionOutput<MessageAddress> {
+  let address = address.trim();
+
+  if let Some(hex_address) = address
+    .strip_prefix("0x")
+    .or_else(|| address.strip_prefix("0X"))
+    && hex_address.len() == 40
+    && hex_address.chars().all(|c| c.is_ascii_hexdigit())
+  {
+    return Ok(MessageAddress::Evm {
+      address: hex_address.to_lowercase(),
+    });
+  }
+
+  if let Ok((_hrp, version, program)) = bech32::segwit::decode(address) {
+    return match (version.to_u8(), program.len()) {
+      (0, 20) => Ok(MessageAddress::WitnessV0 { program }),
+      (1, 32) => Ok(MessageAddress::Taproot { program }),
+      (version, length) => Err(AppError::Custom(format!(
+        "Unsupported witness program v{version} with {length} bytes"
+      ))),
+    };
+  }
+
+  let payload = bs58::decode(address).into_vec().map_err(|err| {
+    AppError::Custom(format!(
+      "Message signing is not supported for address {address}: {err}"
+    ))
+  })?;
+
+  if payload.len() < 25 {
+    return Err(AppError::Custom(format!(
+      "Invalid address length {}",
+      payload.len()
+    )));
+  }
+
+  let (data, checksum) = payload.split_at(payload.len() - 4);
+
+  if qr2m_lib::calculate_double_sha256_hash(data)[..4] != *checksum {
+    return Err(AppError::Custom("Invalid address checksum".to_string()));
+  }
+
+  // Version prefixes are one or two bytes depending on the coin, the hash is always last
+  Ok(MessageAddress::Base58 {
+    hash: data[data.len() - 20..].to_vec(),
+  })
+}
+
+fn decode_private_key(private_key: &str) -> FunctionOutput<(secp256k1::SecretKey, bool)> {
+  let private_key = private_key.trim();
+  let hex_key = private_key.strip_prefix("0x").unwrap_or(private_key);
+
+  if hex_key.len() == 64 && hex_key.chars().all(|c| c.is_ascii_hexdigit()) {
+    let mut key_bytes = [0u8; 32];
+    hex::decode_to_slice(hex_key, &mut key_bytes)
+      .map_err(|err| AppError::Custom(format!("Invalid private key: {err}")))?;
+
+    let secret_key = secp256k1::SecretKey::from_byte_array(key_bytes)
+      .map_err(|err| AppError::Custom(format!("Invalid private key: {err}")));
+    zeroize::Zeroize::zeroize(&mut key_bytes);
+
+    return Ok((secret_key?, true));
+  }
+
+  let mut payload = bs58::decode(private_key)
+    .into_vec()
+    .map_err(|err| AppError::Custom(format!("Invalid WIF private key: {err}")))?;
+
+  if payload.len() < 37 {
+    zeroize::Zeroize::zeroize(&mut payload);
+    return Err(AppError::Custom(format!(
+      "Invalid WIF length {}",
+      payload.len()
+    )));
+  }
+
+  let (data, checksum) = payload.split_at(payload.len() - 4);
+
+  if qr2m_lib::calculate_double_sha256_hash(data)[..4] != *checksum {
+    zeroize::Zeroize::zeroize(&mut payload);
+    return Err(AppError::Custom("Invalid WIF checksum".to_string()));
+  }
+
+  let compressed = data.len() >= 34 && data[data.len() - 1] == 0x01;
+  let key_end = if compressed {
+    data.len() - 1
+  } else {
+    data.len()
+  };
+
+  let mut key_bytes = [0u8; 32];
+  key_bytes.copy_from_slice(&data[key_end - 32..key_end]);
+  zeroize::Zeroize::zeroize(&mut payload);
+
+  let secret_key = secp256k1::SecretKey::from_byte_array(key_bytes)
+    .map_err(|err| AppError::Custom(format!("Invalid WIF private key: {err}")));
+  zeroize::Zeroize::zeroize(&mut key_bytes);
+
+  Ok((secret_key?, compressed))
+}
+
+fn calculate_public_key_hash(public_key: &secp256k1::PublicKey, compressed: bool) -> Vec<u8> {
+  if compressed {
+    qr2m_lib::calculate_sha256_and_ripemd160_hash(&public_key.serialize())
+  } else {
+    qr2m_lib::calculate_sha256_and_ripemd160_hash(&public_key.serialize_uncompressed())
+  }
+}
+
+fn calculate_p2sh_p2wpkh_hash(public_key_hash: &[u8]) -> Vec<u8> {
+  let mut redeem_script = vec![0x00, 0x14];
+  redeem_script.extend_from_slice(public_key_hash);
+
+  qr2m_lib::calculate_sha256_and_ripemd160_hash(&redeem_script)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// BITCOIN SIGNED MESSAGE
+
+fn calculate_bitcoin_message_hash(message: &str) -> [u8; 32] {
+  let mut data = Vec::with_capacity(BITCOIN_MESSAGE_MAGIC.len() + message.len() + 10);
+  write_compact_size(&mut data, BITCOIN_MESSAGE_MAGIC.len());
+  data.extend_from_slice(BITCOIN_MESSAGE_MAGIC.as_bytes());
+  write_compact_size(&mut data, message.len());
+  data.extend_from_slice(message.as_bytes());
+
+  let mut hash = [0u8; 32];
+  hash.copy_from_slice(&qr2m_lib::calculate_double_sha256_hash(&data));
+  hash
+}
+
+fn sign_message_bitcoin(
+  secret_key: &secp256k1::SecretKey,
+  message: &str,
+  header_offset: u8,
+) -> String {
+  let secp = keys::secp256k1_context();
+  let message_hash = secp256k1::Message::from_digest(calculate_bitcoin_message_hash(message));
+
+  let (recovery_id, compact) = secp
+    .sign_ecdsa_recoverable(message_hash, secret_key)
+    .serialize_compact();
+
+  let mut signature = Vec::with_capacity(65);
+  signature.push(header_offset + i32::from(recovery_id) as u8);
+  signature.extend_from_slice(&compact);
+
+  qr2m_lib::encode_base64(&signature)
+}
+
+fn verify_message_bitcoin(
+  address: &MessageAddress,
+  message: &str,
+  signature: &[u8],
+) -> FunctionOutput<bool> {
+  let header = signature[0];
+  let compressed = header >= 31;
+  let recovery_id = secp256k1::ecdsa::RecoveryId::from_u8_masked(header - 27);
+
+  let recoverable_signature =
+    secp256k1::ecdsa::RecoverableSignature::from_compact(&signature[1..], recovery_id)
+      .map_err(|err| AppError::Custom(format!("Invalid signature: {err}")))?;
+
+  let message_hash = secp256k1::Message::from_digest(calculate_bitcoin_message_hash(message));
+
+  let public_key =
+    match keys::secp256k1_context().recover_ecdsa(message_hash, &recoverable_signature) {
+      Ok(public_key) => public_key,
+      Err(_) => return Ok(false),
+    };
+
+  let public_key_hash = calculate_public_key_hash(&public_key, compressed);
+
+  Ok(match address {
+    MessageAddress::Base58 { hash } => {
+      public_key_hash == *hash
+        || (compressed && calculate_p2sh_p2wpkh_hash(&public_key_hash) == *hash)
+    }
+    MessageAddress::WitnessV0 { program } => compressed && public_key_hash == *program,
+    _ => false,
+  })
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// TRANSACTION
+
+const SIGHASH_DEFAULT: u8 = 0x00;
+const SIGHASH_ALL: u8 = 0x01;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxInput {
+  pub previous_txid: [u8; 32],
+  pub previous_index: u32,
+  pub script_sig: Vec<u8>,
+  pub sequence: u32,
+  pub witness: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOutput {
+  pub value: u64,
+  pub script_pubkey: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+  pub version: u32,
+  pub inputs: Vec<TxInput>,
+  pub outputs: Vec<TxOutput>,
+  pub lock_time: u32,
+}
+
+impl TxInput {
+  fn write_outpoint(&self, buffer: &mut Vec<u8>) {
+    buffer.extend_from_slice(&self.previous_txid);
+    buffer.extend_from_slice(&self.previous_index.to_le_bytes());
+  }
+}
+
+impl TxOutput {
+  fn read(reader: &mut &[u8]) -> FunctionOutput<Self> {
+    let value = read_u64(reader)?;
+    let script_length = read_compact_size(reader)?;
+    let script_pubkey = read_bytes(reader, script_length)?.to_vec();
+
+    Ok(Self {
+      value,
+      script_pubkey,
+    })
+  }
+
+  fn write(&self, buffer: &mut Vec<u8>) {
+    buffer.extend_from_slice(&self.value.to_le_bytes());
+    write_compact_size(buffer, self.script_pubkey.len());
+    buffer.extend_from_slice(&self.script_pubkey);
+  }
+}
+
+impl Transaction {
+  pub fn from_bytes(data: &[u8]) -> FunctionOutput<Self> {
+    let mut reader = data;
+    let transaction = Self::read(&mut reader)?;
+
+    if !reader.is_empty() {
+      return Err(AppError::Custom(
+        "Trailing data after transaction".to_string(),
+      ));
+    }
+
+    Ok(transaction)
+  }
+
+  fn read(reader: &mut &[u8]) -> FunctionOutput<Self> {
+    let version = read_u32(reader)?;
+
+    // BIP144 marker and flag, a transaction without inputs is never valid on its own
+    let has_witness = reader.len() >= 2 && reader[0] == 0x00 && reader[1] == 0x01;
+    if has_witness {
+      *reader = &reader[2..];
+    }
+
+    let input_count = read_compact_size(reader)?;
+    let mut inputs = Vec::with_capacity(input_count.min(1024));
+
+    for _ in 0..input_count {
+      let previous_txid = read_array(reader)?;
+      let previous_index = read_u32(reader)?;
+      let script_length = read_compact_size(reader)?;
+      let script_sig = read_bytes(reader, script_length)?.to_vec();
+      let sequence = read_u32(reader)?;
+
+      inputs.push(TxInput {
+        previous_txid,
+        previous_index,
+        script_sig,
+        sequence,
+        witness: Vec::new(),
+      });
+    }
+
+    let output_count = read_compact_size(reader)?;
+    let mut outputs = Vec::with_capacity(output_count.min(1024));
+
+    for _ in 0..output_count {
+      outputs.push(TxOutput::read(reader)?);
+    }
+
+    if has_witness {
+      for input in inputs.iter_mut() {
+        let item_count = read_compact_size(reader)?;
+
+        for _ in 0..item_count {
+          let item_length = read_compact_size(reader)?;
+          input
+            .witness
+            .push(read_bytes(reader, item_length)?.to_vec());
+        }
+      }
+    }
+
+    let lock_time = read_u32(reader)?;
+
+    Ok(Self {
+      version,
+      inputs,
+      outputs,
+      lock_time,
+    })
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let has_witness = self.inputs.iter().any(|input| !input.witness.is_empty());
+    self.serialize(has_witness, None)
+  }
+
+  pub fn txid(&self) -> [u8; 32] {
+    to_hash_array(&qr2m_lib::calculate_double_sha256_hash(
+      &self.serialize(false, None),
+    ))
+  }
+
+  // script_override replaces every scriptSig, which is what the legacy sighash needs
+  fn serialize(&self, include_witness: bool, script_override: Option<(usize, &[u8])>) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(256);
+    buffer.extend_from_slice(&self.version.to_le_bytes());
+
+    if include_witness {
+      buffer.extend_from_slice(&[0x00, 0x01]);
+    }
+
+    write_compact_size(&mut buffer, self.inputs.len());
+
+    for (index, input) in self.inputs.iter().enumerate() {
+      input.write_outpoint(&mut buffer);
+
+      let script_sig = match script_override {
+        Some((signed_index, script_code)) if signed_index == index => script_code,
+        Some(_) => &[],
+        None => &input.script_sig,
+      };
+
+      write_compact_size(&mut buffer, script_sig.len());
+      buffer.extend_from_slice(script_sig);
+      buffer.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+
+    write_compact_size(&mut buffer, self.outputs.len());
+
+    for output in &self.outputs {
+      output.write(&mut buffer);
+    }
+
+    if include_witness {
+      for input in &self.inputs {
+        write_compact_size(&mut buffer, input.witness.len());
+
+        for item in &input.witness {
+          write_compact_size(&mut buffer, item.len());
+          buffer.extend_from_slice(item);
+        }
+      }
+    }
+
+    buffer.extend_from_slice(&self.lock_time.to_le_bytes());
+    buffer
+  }
+
+  fn serialize_outputs(&self) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(self.outputs.len() * 34);
+
+    for output in &self.outputs {
+      output.write(&mut buffer);
+    }
+
+    buffer
+  }
+
+  // Only SIGHASH_ALL is supported by the sighash functions, callers reject anything else
+  pub fn calculate_legacy_sighash(&self, input_index: usize, script_code: &[u8]) -> [u8; 32] {
+    let mut preimage = self.serialize(false, Some((input_index, script_code)));
+    preimage.extend_from_slice(&(SIGHASH_ALL as u32).to_le_bytes());
+
+    to_hash_array(&qr2m_lib::calculate_double_sha256_hash(&preimage))
+  }
+
+  pub fn calculate_segwit_v0_sighash(
+    &self,
+    input_index: usize,
+    script_code: &[u8],
+    amount: u64,
+  ) -> [u8; 32] {
+    let mut prevouts = Vec::with_capacity(self.inputs.len() * 36);
+    let mut sequences = Vec::with_capacity(self.inputs.len() * 4);
+
+    for input in &self.inputs {
+      input.write_outpoint(&mut prevouts);
+      sequences.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+
+    let input = &self.inputs[input_index];
+
+    let mut preimage = Vec::with_capacity(200);
+    preimage.extend_from_slice(&self.version.to_le_bytes());
+    preimage.extend_from_slice(&qr2m_lib::calculate_double_sha256_hash(&prevouts));
+    preimage.extend_from_slice(&qr2m_lib::calculate_double_sha256_hash(&sequences));
+    input.write_outpoint(&mut preimage);
+    write_compact_size(&mut preimage, script_code.len());
+    preimage.extend_from_slice(script_code);
+    preimage.extend_from_slice(&amount.to_le_bytes());
+    preimage.extend_from_slice(&input.sequence.to_le_bytes());
+    preimage.extend_from_slice(&qr2m_lib::calculate_double_sha256_hash(
+      &self.serialize_outputs(),
+    ));
+    preimage.extend_from_slice(&self.lock_time.to_le_bytes());
+    preimage.extend_from_slice(&(SIGHASH_ALL as u32).to_le_bytes());
+
+    to_hash_array(&qr2m_lib::calculate_double_sha256_hash(&preimage))
+  }
+
+  // Key path spend only, hash_type is either SIGHASH_DEFAULT or SIGHASH_ALL
+  pub fn calculate_taproot_sighash(
+    &self,
+    input_index: usize,
+    spent_outputs: &[TxOutput],
+    hash_type: u8,
+  ) -> [u8; 32] {
+    let mut prevouts = Vec::with_capacity(self.inputs.len() * 36);
+    let mut amounts = Vec::with_capacity(self.inputs.len() * 8);
+    let mut script_pubkeys = Vec::with_capacity(self.inputs.len() * 35);
+    let mut sequences = Vec::with_capacity(self.inputs.len() * 4);
+
+    for (input, spent_output) in self.inputs.iter().zip(spent_outputs) {
+      input.write_outpoint(&mut prevouts);
+      amounts.extend_from_slice(&spent_output.value.to_le_bytes());
+      write_compact_size(&mut script_pubkeys, spent_output.script_pubkey.len());
+      script_pubkeys.extend_from_slice(&spent_output.script_pubkey);
+      sequences.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+
+    let mut preimage = Vec::with_capacity(200);
+    preimage.push(0x00);
+    preimage.push(hash_type);
+    preimage.extend_from_slice(&self.version.to_le_bytes());
+    preimage.extend_from_slice(&self.lock_time.to_le_bytes());
+    preimage.extend_from_slice(&qr2m_lib::calculate_sha256_hash(&prevouts));
+    preimage.extend_from_slice(&qr2m_lib::calculate_sha256_hash(&amounts));
+    preimage.extend_from_slice(&qr2m_lib::calculate_sha256_hash(&script_pubkeys));
+    preimage.extend_from_slice(&qr2m_lib::calculate_sha256_hash(&sequences));
+    preimage.extend_from_slice(&qr2m_lib::calculate_sha256_hash(&self.serialize_outputs()));
+    preimage.push(0x00);
+    preimage.extend_from_slice(&(input_index as u32).to_le_bytes());
+
+    to_hash_array(&qr2m_lib::calculate_tagged_hash("TapSighash", &preimage))
+  }
+}
+
+fn to_hash_array(hash: &[u8]) -> [u8; 32] {
+  let mut array = [0u8; 32];
+  array.copy_from_slice(&hash[..32]);
+  array
+}
+
+fn write_compact_size(buffer: &mut Vec<u8>, value: usize) {
+  match value {
+    0..=0xfc => buffer.push(value as u8),
+    0xfd..=0xffff => {
+      buffer.push(0xfd);
+      buffer.extend_from_slice(&(value as u16).to_le_bytes());
+    }
+    0x10000..=0xffff_ffff => {
+      buffer.push(0xfe);
+      buffer.extend_from_slice(&(value as u32).to_le_bytes());
+    }
+    _ => {
+      buffer.push(0xff);
+      buffer.extend_from_slice(&(value as u64).to_le_bytes());
+    }
+  }
+}
+
+fn read_compact_size(reader: &mut &[u8]) -> FunctionOutput<usize> {
+  let value = match read_bytes(reader, 1)?[0] {
+    0xfd => u16::from_le_bytes(read_array(reader)?) as u64,
+    0xfe => read_u32(reader)? as u64,
+    0xff => read_u64(reader)?,
+    value => value as u64,
+  };
+
+  usize::try_from(value).map_err(|_| AppError::Custom(format!("Length {value} is too large")))
+}
+
+fn read_bytes<'a>(reader: &mut &'a [u8], length: usize) -> FunctionOutput<&'a [u8]> {
+  if reader.len() < length {
+    return Err(AppError::Custom("Unexpected end of data".to_string()));
+  }
+
+  let (bytes, rest) = reader.split_at(length);
+  *reader = rest;
+
+  Ok(bytes)
+}
+
+fn read_array<const N: usize>(reader: &mut &[u8]) -> FunctionOutput<[u8; N]> {
+  let mut array = [0u8; N];
+  array.copy_from_slice(read_bytes(reader, N)?);
+  Ok(array)
+}
+
+fn read_u32(reader: &mut &[u8]) -> FunctionOutput<u32> {
+  Ok(u32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_u64(reader: &mut &[u8]) -> FunctionOutput<u64> {
+  Ok(u64::from_le_bytes(read_array(reader)?))
+}
+
+fn get_p2pkh_script_pubkey(public_key_hash: &[u8]) -> Vec<u8> {
+  let mut script_pubkey = vec![0x76, 0xa9, 0x14];
+  script_pubkey.extend_from_slice(public_key_hash);
+  script_pubkey.extend_from_slice(&[0x88, 0xac]);
+  script_pubkey
+}
+
+fn get_p2wpkh_script_pubkey(program: &[u8]) -> Vec<u8> {
+  let mut script_pubkey = vec![0x00, 0x14];
+  script_pubkey.extend_from_slice(program);
+  script_pubkey
+}
+
+fn get_p2tr_script_pubkey(program: &[u8]) -> Vec<u8> {
+  let mut script_pubkey = vec![0x51, 0x20];
+  script_pubkey.extend_from_slice(program);
+  script_pubkey
+}
+
+fn tweak_taproot_keypair(
+  secret_key: &secp256k1::SecretKey,
+  merkle_root: Option<&[u8]>,
+) -> FunctionOutput<secp256k1::Keypair> {
+  let secp = keys::secp256k1_context();
+  let keypair = secp256k1::Keypair::from_secret_key(secp, secret_key);
+  let (internal_key, _parity) = keypair.x_only_public_key();
+
+  let mut tweak_data = internal_key.serialize().to_vec();
+  if let Some(merkle_root) = merkle_root {
+    tweak_data.extend_from_slice(merkle_root);
+  }
+
+  let tweak_hash = qr2m_lib::calculate_tagged_hash("TapTweak", &tweak_data);
+  let tweak = secp256k1::Scalar::from_be_bytes(to_hash_array(&tweak_hash))
+    .map_err(|err| AppError::Custom(format!("Invalid TapTweak scalar: {err}")))?;
+
+  keypair
+    .add_xonly_tweak(secp, &tweak)
+    .map_err(|err| AppError::Custom(format!("Can not tweak key pair: {err}")))
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// BIP322
+
+fn build_bip322_to_sign(script_pubkey: &[u8], message: &str) -> Transaction {
+  let message_hash = qr2m_lib::calculate_tagged_hash(BIP322_MESSAGE_TAG, message.as_bytes());
+  let mut script_sig = vec![0x00, 0x20];
+  script_sig.extend_from_slice(&message_hash);
+
+  let to_spend = Transaction {
+    version: 0,
+    inputs: vec![TxInput {
+      previous_txid: [0u8; 32],
+      previous_index: 0xffff_ffff,
+      script_sig,
+      sequence: 0,
+      witness: Vec::new(),
+    }],
+    outputs: vec![TxOutput {
+      value: 0,
+      script_pubkey: script_pubkey.to_vec(),
+    }],
+    lock_time: 0,
+  };
+
+  // to_sign has a single OP_RETURN output with zero value
+  Transaction {
+    version: 0,
+    inputs: vec![TxInput {
+      previous_txid: to_spend.txid(),
+      previous_index: 0,
+      script_sig: Vec::new(),
+      sequence: 0,
+      witness: Vec::new(),
+    }],
+    outputs: vec![TxOutput {
+      value: 0,
+      script_pubkey: vec![0x6a],
+    }],
+    lock_time: 0,
+  }
+}
+
+fn calculate_bip322_sighash_p2wpkh(program: &[u8], message: &str) -> [u8; 32] {
+  build_bip322_to_sign(&get_p2wpkh_script_pubkey(program), message).calculate_segwit_v0_sighash(
+    0,
+    &get_p2pkh_script_pubkey(program),
+    0,
+  )
+}
+
+fn calculate_bip322_sighash_p2tr(program: &[u8], message: &str, hash_type: u8) -> [u8; 32] {
+  let spent_output = TxOutput {
+    value: 0,
+    script_pubkey: get_p2tr_script_pubkey(program),
+  };
+
+  build_bip322_to_sign(&spent_output.script_pubkey, message).calculate_taproot_sighash(
+    0,
+    &[spent_output],
+    hash_type,
+  )
+}
+
+fn encode_witness(items: &[&[u8]]) -> String {
+  let mut witness = Vec::new();
+  write_compact_size(&mut witness, items.len());
+
+  for item in items {
+    write_compact_size(&mut witness, item.len());
+    witness.extend_from_slice(item);
+  }
+
+  qr2m_lib::encode_base64(&witness)
+}
+
+fn decode_witness(witness: &[u8]) -> FunctionOutput<Vec<Vec<u8>>> {
+  let mut reader = witness;
+  let count = read_compact_size(&mut reader)?;
+  let mut items = Vec::with_capacity(count.min(16));
+
+  for _ in 0..count {
+    let length = read_compact_size(&mut reader)?;
+    items.push(read_bytes(&mut reader, length)?.to_vec());
+  }
+
+  if !reader.is_empty() {
+    return Err(AppError::Custom("Trailing data after witness".to_string()));
+  }
+
+  Ok(items)
+}
+
+fn sign_message_bip322_p2wpkh(
+  secret_key: &secp256k1::SecretKey,
+  program: &[u8],
+  message: &str,
+) -> String {
+  let secp = keys::secp256k1_context();
+  let sighash = calculate_bip322_sighash_p2wpkh(program, message);
+
+  let mut signature = secp
+    .sign_ecdsa_low_r(secp256k1::Message::from_digest(sighash), secret_key)
+    .serialize_der()
+    .to_vec();
+  signature.push(SIGHASH_ALL);
+
+  let public_key = secp256k1::PublicKey::from_secret_key(secp, secret_key).serialize();
+
+  encode_witness(&[&signature, &public_key])
+}
+
+fn verify_message_bip322_p2wpkh(
+  program: &[u8],
+  message: &str,
+  witness: &[u8],
+) -> FunctionOutput<bool> {
+  let items = decode_witness(witness)?;
+
+  let [signature, public_key] = items.as_slice() else {
+    return Ok(false);
+  };
+
+  let Some((&SIGHASH_ALL, der_signature)) = signature.split_last() else {
+    return Ok(false);
+  };
+
+  let Ok(public_key) = secp256k1::PublicKey::from_slice(public_key) else {
+    return Ok(false);
+  };
+
+  if calculate_public_key_hash(&public_key, true) != program {
+    return Ok(false);
+  }
+
+  let Ok(signature) = secp256k1::ecdsa::Signature::from_der(der_signature) else {
+    return Ok(false);
+  };
+
+  let sighash = calculate_bip322_sighash_p2wpkh(program, message);
+
+  Ok(
+    keys::secp256k1_context()
+      .verify_ecdsa(
+        secp256k1::Message::from_digest(sighash),
+        &signature,
+        &public_key,
+      )
+      .is_ok(),
+  )
+}
+
+fn sign_message_bip322_p2tr(
+  secret_key: &secp256k1::SecretKey,
+  program: &[u8],
+  message: &str,
+) -> FunctionOutput<String> {
+  let secp = keys::secp256k1_context();
+  let tweaked_keypair = tweak_taproot_keypair(secret_key, None)?;
+
+  if tweaked_keypair.x_only_public_key().0.serialize() != program {
+    return Err(AppError::Custom(
+      "Private key does not belong to this address".to_string(),
+    ));
+  }
+
+  let sighash = calculate_bip322_sighash_p2tr(program, message, SIGHASH_DEFAULT);
+
+  let aux_rand = rand::rng().random::<[u8; 32]>();
+  let signature = secp.sign_schnorr_with_aux_rand(&sighash, &tweaked_keypair, &aux_rand);
+
+  Ok(encode_witness(&[signature.as_ref()]))
+}
+
+fn verify_message_bip322_p2tr(
+  program: &[u8],
+  message: &str,
+  witness: &[u8],
+) -> FunctionOutput<bool> {
+  let items = decode_witness(witness)?;
+
+  let [signature] = items.as_slice() else {
+    return Ok(false);
+  };
+
+  // A 64-byte signature implies SIGHASH_DEFAULT, otherwise the hash type is appended
+  let hash_type = match signature.len() {
+    64 => SIGHASH_DEFAULT,
+    65 if signature[64] == SIGHASH_ALL => SIGHASH_ALL,
+    _ => return Ok(false),
+  };
+
+  let mut signature_bytes = [0u8; 64];
+  signature_bytes.copy_from_slice(&signature[..64]);
+  let signature = secp256k1::schnorr::Signature::from_byte_array(signature_bytes);
+
+  let Ok(program_bytes) = <[u8; 32]>::try_from(program) else {
+    return Ok(false);
+  };
+
+  let Ok(output_key) = secp256k1::XOnlyPublicKey::from_byte_array(program_bytes) else {
+    return Ok(false);
+  };
+
+  let sighash = calculate_bip322_sighash_p2tr(program, message, hash_type);
+
+  Ok(
+    keys::secp256k1_context()
+      .verify_schnorr(&signature, &sighash, &output_key)
+      .is_ok(),
+  )
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// PSBT
+
+const PSBT_MAGIC: &[u8; 5] = b"psbt\xff";
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_GLOBAL_VERSION: u8 = 0xfb;
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_BIP32_DERIVATION: u8 = 0x06;
+const PSBT_IN_FINAL_SCRIPTSIG: u8 = 0x07;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u8 = 0x08;
+const PSBT_IN_TAP_KEY_SIG: u8 = 0x13;
+const PSBT_IN_TAP_BIP32_DERIVATION: u8 = 0x16;
+const PSBT_IN_TAP_INTERNAL_KEY: u8 = 0x17;
+const PSBT_IN_TAP_MERKLE_ROOT: u8 = 0x18;
+
+// Entries keep their original order and raw bytes, so fields we do not understand survive
+// the round trip untouched
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsbtMap {
+  pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Psbt {
+  pub global: PsbtMap,
+  pub inputs: Vec<PsbtMap>,
+  pub outputs: Vec<PsbtMap>,
+  pub transaction: Transaction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsbtOutputSummary {
+  pub address: Option<String>,
+  pub script_pubkey: Vec<u8>,
+  pub value: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsbtSummary {
+  pub outputs: Vec<PsbtOutputSummary>,
+  pub fee: Option<u64>,
+  pub unverified_inputs: usize,
+}
+
+impl PsbtMap {
+  fn read(reader: &mut &[u8]) -> FunctionOutput<Self> {
+    let mut map = Self::default();
+
+    loop {
+      let key_length = read_compact_size(reader)?;
+      if key_length == 0 {
+        break;
+      }
+
+      let key = read_bytes(reader, key_length)?.to_vec();
+      let value_length = read_compact_size(reader)?;
+      let value = read_bytes(reader, value_length)?.to_vec();
+
+      if map.entries.iter().any(|(existing, _)| *existing == key) {
+        return Err(AppError::Custom(format!(
+          "Duplicate PSBT key {}",
+          hex::encode(&key)
+        )));
+      }
+
+      map.entries.push((key, value));
+    }
+
+    Ok(map)
+  }
+
+  fn write(&self, buffer: &mut Vec<u8>) {
+    for (key, value) in &self.entries {
+      write_compact_size(buffer, key.len());
+      buffer.extend_from_slice(key);
+      write_compact_size(buffer, value.len());
+      buffer.extend_from_slice(value);
+    }
+
+    buffer.push(0x00);
+  }
+
+  pub fn get(&self, key_type: u8) -> Option<&[u8]> {
+    self
+      .entries
+      .iter()
+      .find(|(key, _)| key.as_slice() == [key_type])
+      .map(|(_, value)| value.as_slice())
+  }
+
+  pub fn get_all(&self, key_type: u8) -> impl Iterator<Item = (&[u8], &[u8])> {
+    self
+      .entries
+      .iter()
+      .filter(move |(key, _)| key.first() == Some(&key_type))
+      .map(|(key, value)| (&key[1..], value.as_slice()))
+  }
+
+  pub fn insert(&mut self, key: Vec<u8>, value: Vec<u8>) {
+    match self
+      .entries
+      .iter_mut()
+      .find(|(existing, _)| *existing == key)
+    {
+      Some(entry) => entry.1 = value,
+      None => self.entries.push((key, value)),
+    }
+  }
+}
+
+impl Psbt {
+  pub fn from_bytes(data: &[u8]) -> FunctionOutput<Self> {
+    d3bug(">>> Psbt::from_bytes", "debug");
+
+    let mut reader = data;
+
+    if read_bytes(&mut reader, PSBT_MAGIC.len())? != PSBT_MAGIC {
+      return Err(AppError::Custom("Missing PSBT magic bytes".to_string()));
+    }
+
+    let global = PsbtMap::read(&mut reader)?;
+
+    if let Some(version) = global.get(PSBT_GLOBAL_VERSION)
+      && version != [0u8; 4]
+    {
+      return Err(AppError::Custom(format!(
+        "Unsupported PSBT version {}",
+        hex::encode(version)
+      )));
+    }
+
+    let transaction = global
+      .get(PSBT_GLOBAL_UNSIGNED_TX)
+      .ok_or_else(|| AppError::Custom("PSBT has no unsigned transaction".to_string()))
+      .and_then(Transaction::from_bytes)?;
+
+    if transaction
+      .inputs
+      .iter()
+      .any(|input| !input.script_sig.is_empty() || !input.witness.is_empty())
+    {
+      return Err(AppError::Custom(
+        "PSBT unsigned transaction contains signatures".to_string(),
+      ));
+    }
+
+    let mut inputs = Vec::with_capacity(transaction.inputs.len());
+    for _ in 0..transaction.inputs.len() {
+      inputs.push(PsbtMap::read(&mut reader)?);
+    }
+
+    let mut outputs = Vec::with_capacity(transaction.outputs.len());
+    for _ in 0..transaction.outputs.len() {
+      outputs.push(PsbtMap::read(&mut reader)?);
+    }
+
+    if !reader.is_empty() {
+      return Err(AppError::Custom("Trailing data after PSBT".to_string()));
+    }
+
+    Ok(Self {
+      global,
+      inputs,
+      outputs,
+      transaction,
+    })
+  }
+
+  pub fn from_base64(encoded: &str) -> FunctionOutput<Self> {
+    let data = qr2m_lib::decode_base64(encoded.trim()).map_err(AppError::Custom)?;
+    Self::from_bytes(&data)
+  }
+
+  // PSBT files are either raw binary or the Base64 text most wallets export
+  pub fn from_file_contents(contents: &[u8]) -> FunctionOutput<Self> {
+    if contents.starts_with(PSBT_MAGIC) {
+      return Self::from_bytes(contents);
+    }
+
+    let text = std::str::from_utf8(contents)
+      .map_err(|err| AppError::Custom(format!("PSBT file is neither binary nor Base64: {err}")))?;
+
+    Self::from_base64(text)
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut buffer = PSBT_MAGIC.to_vec();
+    self.global.write(&mut buffer);
+
+    for map in self.inputs.iter().chain(&self.outputs) {
+      map.write(&mut buffer);
+    }
+
+    buffer
+  }
+
+  pub fn to_base64(&self) -> String {
+    qr2m_lib::encode_base64(&self.to_bytes())
+  }
+
+  fn get_spent_output(&self, input_index: usize) -> FunctionOutput<Option<TxOutput>> {
+    let input = &self.inputs[input_index];
+    let tx_input = &self.transaction.inputs[input_index];
+
+    if let Some(previous_transaction) = input.get(PSBT_IN_NON_WITNESS_UTXO) {
+      let previous_transaction = Transaction::from_bytes(previous_transaction)?;
+
+      if previous_transaction.txid() != tx_input.previous_txid {
+        return Err(AppError::Custom(format!(
+          "Input {input_index} previous transaction does not match its outpoint"
+        )));
+      }
+
+      return previous_transaction
+        .outputs
+        .get(tx_input.previous_index as usize)
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| AppError::Custom(format!("Input {input_index} spends a missing output")));
+    }
+
+    match input.get(PSBT_IN_WITNESS_UTXO) {
+      Some(mut witness_utxo) => Ok(Some(TxOutput::read(&mut witness_utxo)?)),
+      None => Ok(None),
+    }
+  }
+
+  // Everything the user confirms before signing: where the coins go and what is left as fee.
+  // Amounts of inputs without their previous transaction are only claimed, not proven
+  pub fn summarize(
+    &self,
+    public_key_hash: &[u8],
+    script_hash: &[u8],
+    bech32_hrp: &str,
+  ) -> FunctionOutput<PsbtSummary> {
+    let outputs = self
+      .transaction
+      .outputs
+      .iter()
+      .map(|output| PsbtOutputSummary {
+        address: encode_script_address(
+          &output.script_pubkey,
+          public_key_hash,
+          script_hash,
+          bech32_hrp,
+        ),
+        script_pubkey: output.script_pubkey.clone(),
+        value: output.value,
+      })
+      .collect();
+
+    let spent_outputs = (0..self.inputs.len())
+      .map(|index| self.get_spent_output(index))
+      .collect::<FunctionOutput<Vec<_>>>()?;
+
+    let input_value = spent_outputs.iter().try_fold(0u64, |total, output| {
+      output
+        .as_ref()
+        .and_then(|output| total.checked_add(output.value))
+    });
+
+    let output_value = self
+      .transaction
+      .outputs
+      .iter()
+      .try_fold(0u64, |total, output| total.checked_add(output.value))
+      .ok_or_else(|| AppError::Custom("Output amounts overflow".to_string()))?;
+
+    let fee = input_value
+      .map(|input_value| {
+        input_value
+          .checked_sub(output_value)
+          .ok_or_else(|| AppError::Custom("Outputs spend more than the inputs".to_string()))
+      })
+      .transpose()?;
+
+    let unverified_inputs = self
+      .inputs
+      .iter()
+      .filter(|input| input.get(PSBT_IN_NON_WITNESS_UTXO).is_none())
+      .count();
+
+    Ok(PsbtSummary {
+      outputs,
+      fee,
+      unverified_inputs,
+    })
+  }
+}
+
+// Standard output scripts are shown as addresses of the selected coin, anything else stays raw
+fn encode_script_address(
+  script_pubkey: &[u8],
+  public_key_hash: &[u8],
+  script_hash: &[u8],
+  bech32_hrp: &str,
+) -> Option<String> {
+  let base58_payload = match script_pubkey {
+    [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == 20 => Some((public_key_hash, hash)),
+    [0xa9, 0x14, hash @ .., 0x87] if hash.len() == 20 => Some((script_hash, hash)),
+    _ => None,
+  };
+
+  if let Some((prefix, hash)) = base58_payload {
+    if prefix.is_empty() {
+      return None;
+    }
+
+    let mut payload = [prefix, hash].concat();
+    let checksum = qr2m_lib::calculate_double_sha256_hash(&payload);
+    payload.extend_from_slice(&checksum[..4]);
+
+    return Some(bs58::encode(payload).into_string());
+  }
+
+  match script_pubkey {
+    [version @ (0x00 | 0x51..=0x60), length, program @ ..] if *length as usize == program.len() => {
+      let hrp = bech32::Hrp::parse(bech32_hrp).ok()?;
+      let version = bech32::Fe32::try_from(version.saturating_sub(0x50)).ok()?;
+
+      bech32::segwit::encode(hrp, version, program).ok()
+    }
+    _ => None,
+  }
+}
+
+fn parse_key_origin(value: &[u8]) -> Option<([u8; 4], DerivationPath)> {
+  if value.len() < 4 || !value.len().is_multiple_of(4) {
+    return None;
+  }
+
+  let mut fingerprint = [0u8; 4];
+  fingerprint.copy_from_slice(&value[..4]);
+
+  let children = value[4..]
+    .chunks_exact(4)
+    .map(|chunk| {
+      ChildNumber::from_raw(u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+    })
+    .collect();
+
+  Some((fingerprint, DerivationPath::from_children(children)))
+}
+
+fn parse_tap_key_origin(value: &[u8]) -> FunctionOutput<(usize, &[u8])> {
+  let mut reader = value;
+  let leaf_hash_count = read_compact_size(&mut reader)?;
+  let leaf_hashes_length = leaf_hash_count
+    .checked_mul(32)
+    .ok_or_else(|| AppError::Custom(format!("Too many leaf hashes: {leaf_hash_count}")))?;
+  read_bytes(&mut reader, leaf_hashes_length)?;
+
+  Ok((leaf_hash_count, reader))
+}
+
+fn get_node_secret_key(node: &keys::DerivationNode) -> FunctionOutput<secp256k1::SecretKey> {
+  let mut key_bytes = [0u8; 32];
+  key_bytes.copy_from_slice(&node.private_key[..32]);
+
+  let secret_key = secp256k1::SecretKey::from_byte_array(key_bytes)
+    .map_err(|err| AppError::Custom(format!("Invalid derived SecretKey: {err}")));
+  zeroize::Zeroize::zeroize(&mut key_bytes);
+
+  secret_key
+}
+
+// Signs every input whose BIP32 derivation points at the loaded master key and returns how
+// many inputs received a new signature. Key origins start at the root fingerprint, so the keys
+// passed in must be the root master keys and not an imported account key
+pub fn sign_psbt(
+  psbt: &mut Psbt,
+  master_private_key: &[u8],
+  master_chain_code: &[u8],
+) -> FunctionOutput<usize> {
+  d3bug(">>> sign_psbt", "debug");
+
+  let master_key = keys::ExtendedKey::new_master(master_private_key, master_chain_code)?;
+  let master_fingerprint = master_key.fingerprint();
+  let master_node = keys::DerivationNode::new(
+    master_private_key.to_vec(),
+    master_chain_code.to_vec(),
+    master_key.public_key.to_vec(),
+    0,
+    0,
+  );
+
+  d3bug(
+    &format!("master fingerprint {}", hex::encode(master_fingerprint)),
+    "debug",
+  );
+
+  let spent_outputs = (0..psbt.inputs.len())
+    .map(|index| psbt.get_spent_output(index))
+    .collect::<FunctionOutput<Vec<_>>>()?;
+
+  let mut signed_inputs = 0;
+
+  for index in 0..psbt.inputs.len() {
+    let input = &psbt.inputs[index];
+
+    if input.get(PSBT_IN_FINAL_SCRIPTSIG).is_some()
+      || input.get(PSBT_IN_FINAL_SCRIPTWITNESS).is_some()
+    {
+      continue;
+    }
+
+    let Some(spent_output) = &spent_outputs[index] else {
+      d3bug(
+        &format!("PSBT input {index} has no UTXO information"),
+        "warning",
+      );
+      continue;
+    };
+
+    let signature = if is_p2tr_script(&spent_output.script_pubkey) {
+      sign_psbt_input_taproot(
+        psbt,
+        index,
+        &spent_outputs,
+        &master_node,
+        &master_fingerprint,
+      )?
+    } else {
+      sign_psbt_input_ecdsa(psbt, index, spent_output, &master_node, &master_fingerprint)?
+    };
+
+    if let Some((key, value)) = signature {
+      psbt.inputs[index].insert(key, value);
+      signed_inputs += 1;
+    }
+  }
+
+  Ok(signed_inputs)
+}
+
+fn is_p2tr_script(script_pubkey: &[u8]) -> bool {
+  script_pubkey.len() == 34 && script_pubkey[..2] == [0x51, 0x20]
+}
+
+fn sign_psbt_input_ecdsa(
+  psbt: &Psbt,
+  index: usize,
+  spent_output: &TxOutput,
+  master_node: &keys::DerivationNode,
+  master_fingerprint: &[u8; 4],
+) -> FunctionOutput<Option<(Vec<u8>, Vec<u8>)>> {
+  let input = &psbt.inputs[index];
+  let has_previous_transaction = input.get(PSBT_IN_NON_WITNESS_UTXO).is_some();
+
+  if let Some(sighash_type) = input.get(PSBT_IN_SIGHASH_TYPE)
+    && sighash_type != (SIGHASH_ALL as u32).to_le_bytes()
+  {
+    d3bug(
+      &format!(
+        "PSBT input {index} requests unsupported sighash type {}",
+        hex::encode(sighash_type)
+      ),
+      "warning",
+    );
+    return Ok(None);
+  }
+
+  for (public_key, key_origin) in input.get_all(PSBT_IN_BIP32_DERIVATION) {
+    let Some((fingerprint, path)) = parse_key_origin(key_origin) else {
+      continue;
+    };
+
+    if fingerprint != *master_fingerprint || public_key.len() != 33 {
+      continue;
+    }
+
+    let node = match master_node.derive("secp256k1", &path) {
+      Ok(node) => node,
+      Err(err) => {
+        d3bug(
+          &format!("PSBT input {index} derivation {path} failed: {err:?}"),
+          "warning",
+        );
+        continue;
+      }
+    };
+
+    if node.public_key != public_key {
+      d3bug(
+        &format!("PSBT input {index} derivation {path} does not match its public key"),
+        "warning",
+      );
+      continue;
+    }
+
+    let public_key_hash = qr2m_lib::calculate_sha256_and_ripemd160_hash(public_key);
+    let p2wpkh_script = get_p2wpkh_script_pubkey(&public_key_hash);
+    let script = &spent_output.script_pubkey;
+
+    let sighash = if *script == get_p2pkh_script_pubkey(&public_key_hash) {
+      // Legacy sighashes do not commit to the amount, only the full previous transaction proves it
+      if !has_previous_transaction {
+        d3bug(
+          &format!("PSBT input {index} spends P2PKH without its previous transaction"),
+          "warning",
+        );
+        return Ok(None);
+      }
+
+      psbt.transaction.calculate_legacy_sighash(index, script)
+    } else if *script == p2wpkh_script
+      || (input.get(PSBT_IN_REDEEM_SCRIPT) == Some(p2wpkh_script.as_slice())
+        && script.len() == 23
+        && script[2..22] == calculate_p2sh_p2wpkh_hash(&public_key_hash))
+    {
+      // CVE-2020-14199: a witness UTXO alone lets a second signing round lie about the fee
+      if !has_previous_transaction {
+        d3bug(
+          &format!("PSBT input {index} amount is not proven by its previous transaction"),
+          "warning",
+        );
+      }
+
+      psbt.transaction.calculate_segwit_v0_sighash(
+        index,
+        &get_p2pkh_script_pubkey(&public_key_hash),
+        spent_output.value,
+      )
+    } else {
+      d3bug(
+        &format!("PSBT input {index} has an unsupported script"),
+        "warning",
+      );
+      continue;
+    };
+
+    let secret_key = get_node_secret_key(&node)?;
+    let mut signature = keys::secp256k1_context()
+      .sign_ecdsa_low_r(secp256k1::Message::from_digest(sighash), &secret_key)
+      .serialize_der()
+      .to_vec();
+    signature.push(SIGHASH_ALL);
+
+    let mut key = vec![PSBT_IN_PARTIAL_SIG];
+    key.extend_from_slice(public_key);
+
+    return Ok(Some((key, signature)));
+  }
+
+  Ok(None)
+}
+
+fn sign_psbt_input_taproot(
+  psbt: &Psbt,
+  index: usize,
+  spent_outputs: &[Option<TxOutput>],
+  master_node: &keys::DerivationNode,
+  master_fingerprint: &[u8; 4],
+) -> FunctionOutput<Option<(Vec<u8>, Vec<u8>)>> {
+  let input = &psbt.inputs[index];
+
+  let hash_type = match input.get(PSBT_IN_SIGHASH_TYPE) {
+    None => SIGHASH_DEFAULT,
+    Some(value) if value == (SIGHASH_DEFAULT as u32).to_le_bytes() => SIGHASH_DEFAULT,
+    Some(value) if value == (SIGHASH_ALL as u32).to_le_bytes() => SIGHASH_ALL,
+    Some(value) => {
+      d3bug(
+        &format!(
+          "PSBT input {index} requests unsupported sighash type {}",
+          hex::encode(value)
+        ),
+        "warning",
+      );
+      return Ok(None);
+    }
+  };
+
+  // BIP341 commits to every spent output, so all of them have to be known
+  let Some(spent_outputs) = spent_outputs.iter().cloned().collect::<Option<Vec<_>>>() else {
+    d3bug(
+      &format!("PSBT input {index} can not be signed without every spent output"),
+      "warning",
+    );
+    return Ok(None);
+  };
+
+  let internal_key = input.get(PSBT_IN_TAP_INTERNAL_KEY);
+  let merkle_root = input.get(PSBT_IN_TAP_MERKLE_ROOT);
+
+  for (x_only_public_key, value) in input.get_all(PSBT_IN_TAP_BIP32_DERIVATION) {
+    // Entries from other signers are not ours to validate, a malformed one is just skipped
+    let (leaf_hash_count, key_origin) = match parse_tap_key_origin(value) {
+      Ok(entry) => entry,
+      Err(err) => {
+        d3bug(
+          &format!("PSBT input {index} has an unreadable taproot derivation: {err:?}"),
+          "warning",
+        );
+        continue;
+      }
+    };
+
+    // Only the key path is signed, script path leaves need their own tapscript signatures
+    if internal_key.is_some_and(|internal_key| internal_key != x_only_public_key)
+      || (internal_key.is_none() && leaf_hash_count > 0)
+    {
+      continue;
+    }
+
+    let Some((fingerprint, path)) = parse_key_origin(key_origin) else {
+      continue;
+    };
+
+    if fingerprint != *master_fingerprint {
+      continue;
+    }
+
+    let node = match master_node.derive("secp256k1", &path) {
+      Ok(node) => node,
+      Err(err) => {
+        d3bug(
+          &format!("PSBT input {index} derivation {path} failed: {err:?}"),
+          "warning",
+        );
+        continue;
+      }
+    };
+
+    if node.public_key.get(1..) != Some(x_only_public_key) {
+      d3bug(
+        &format!("PSBT input {index} derivation {path} does not match its public key"),
+        "warning",
+      );
+      continue;
+    }
+
+    let tweaked_keypair = tweak_taproot_keypair(&get_node_secret_key(&node)?, merkle_root)?;
+
+    if tweaked_keypair.x_only_public_key().0.serialize()[..]
+      != spent_outputs[index].script_pubkey[2..]
+    {
+      d3bug(
+        &format!("PSBT input {index} output key does not match the derived key"),
+        "warning",
+      );
+      continue;
+    }
+
+    let sighash = psbt
+      .transaction
+      .calculate_taproot_sighash(index, &spent_outputs, hash_type);
+
+    let aux_rand = rand::rng().random::<[u8; 32]>();
+    let mut signature = keys::secp256k1_context()
+      .sign_schnorr_with_aux_rand(&sighash, &tweaked_keypair, &aux_rand)
+      .to_byte_array()
+      .to_vec();
+
+    if hash_type != SIGHASH_DEFAULT {
+      signature.push(hash_type);
+    }
+
+    return Ok(Some((vec![PSBT_IN_TAP_KEY_SIG], signature)));
+  }
+
+  Ok(None)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// EIP-191
+
+fn calculate_eip191_message_hash(message: &str) -> [u8; 32] {
+  let mut hasher = Keccak256::new();
+  hasher.update(ETHEREUM_MESSAGE_MAGIC.as_bytes());
+  hasher.update(message.len().to_string().as_bytes());
+  hasher.update(message.as_bytes());
+  hasher.finalize().into()
+}
+
+fn sign_message_eip191(secret_key: &secp256k1::SecretKey, message: &str) -> FunctionOutput<String> {
+  let message_hash = secp256k1::Message::from_digest(calculate_eip191_message_hash(message));
+
+  let (recovery_id, compact) = keys::secp256k1_context()
+    .sign_ecdsa_recoverable(message_hash, secret_key)
+    .serialize_compact();
+
+  let mut signature = compact.to_vec();
+  signature.push(27 + i32::from(recovery_id) as u8);
+
+  Ok(format!("0x{}", hex::encode(signature)))
+}
+
+fn verify_message_eip191(address: &str, message: &str, signature: &str) -> FunctionOutput<bool> {
+  let signature = signature.trim();
+  let signature = hex::decode(signature.strip_prefix("0x").unwrap_or(signature))
+    .map_err(|err| AppError::Custom(format!("Invalid signature: {err}")))?;
+
+  if signature.len() != 65 {
+    return Err(AppError::Custom(format!(
+      "Invalid signature length {}",
+      signature.len()
+    )));
+  }
+
+  let recovery_id = match signature[64] {
+    0 | 27 => secp256k1::ecdsa::RecoveryId::Zero,
+    1 | 28 => secp256k1::ecdsa::RecoveryId::One,
+    v => return Err(AppError::Custom(format!("Invalid recovery id {v}"))),
+  };
+
+  let recoverable_signature =
+    secp256k1::ecdsa::RecoverableSignature::from_compact(&signature[..64], recovery_id)
+      .map_err(|err| AppError::Custom(format!("Invalid signature: {err}")))?;
+
+  let message_hash = secp256k1::Message::from_digest(calculate_eip191_message_hash(message));
+
+  let public_key =
+    match keys::secp256k1_context().recover_ecdsa(message_hash, &recoverable_signature) {
+      Ok(public_key) => public_key,
+      Err(_) => return Ok(false),
+    };
+
+  let public_key_hash = Keccak256::digest(&public_key.serialize_uncompressed()[1..]);
+
+  Ok(hex::encode(&public_key_hash[12..]) == address.to_lowercase())
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// ETHEREUM TRANSACTION
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpItem {
+  Bytes(Vec<u8>),
+  List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+  fn from_u128(value: u128) -> Self {
+    let bytes = value.to_be_bytes();
+    let first_non_zero = bytes
+      .iter()
+      .position(|&byte| byte != 0)
+      .unwrap_or(bytes.len());
+
+    RlpItem::Bytes(bytes[first_non_zero..].to_vec())
+  }
+
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    self.write(&mut buffer);
+    buffer
+  }
+
+  fn write(&self, buffer: &mut Vec<u8>) {
+    match self {
+      RlpItem::Bytes(bytes) if bytes.len() == 1 && bytes[0] < 0x80 => buffer.push(bytes[0]),
+      RlpItem::Bytes(bytes) => {
+        write_rlp_length(buffer, bytes.len(), 0x80);
+        buffer.extend_from_slice(bytes);
+      }
+      RlpItem::List(items) => {
+        let mut payload = Vec::new();
+        for item in items {
+          item.write(&mut payload);
+        }
+
+        write_rlp_length(buffer, payload.len(), 0xc0);
+        buffer.extend_from_slice(&payload);
+      }
+    }
+  }
+
+  pub fn decode(data: &[u8]) -> FunctionOutput<Self> {
+    let mut reader = data;
+    let item = Self::read(&mut reader)?;
+
+    if !reader.is_empty() {
+      return Err(AppError::Custom("Trailing data after RLP item".to_string()));
+    }
+
+    Ok(item)
+  }
+
+  fn read(reader: &mut &[u8]) -> FunctionOutput<Self> {
+    let prefix = read_bytes(reader, 1)?[0];
+
+    let (is_list, length) = match prefix {
+      0x00..=0x7f => return Ok(RlpItem::Bytes(vec![prefix])),
+      0x80..=0xb7 => (false, (prefix - 0x80) as usize),
+      0xb8..=0xbf => (false, read_rlp_long_length(reader, prefix - 0xb7)?),
+      0xc0..=0xf7 => (true, (prefix - 0xc0) as usize),
+      0xf8..=0xff => (true, read_rlp_long_length(reader, prefix - 0xf7)?),
+    };
+
+    let mut payload = read_bytes(reader, length)?;
+
+    if !is_list {
+      if length == 1 && payload[0] < 0x80 {
+        return Err(AppError::Custom(
+          "Non-canonical RLP single byte".to_string(),
+        ));
+      }
+
+      return Ok(RlpItem::Bytes(payload.to_vec()));
+    }
+
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+      items.push(Self::read(&mut payload)?);
+    }
+
+    Ok(RlpItem::List(items))
+  }
+
+  fn as_bytes(&self) -> FunctionOutput<&[u8]> {
+    match self {
+      RlpItem::Bytes(bytes) => Ok(bytes),
+      RlpItem::List(_) => Err(AppError::Custom(
+        "Expected RLP bytes, found a list".to_string(),
+      )),
+    }
+  }
+
+  fn as_list(&self) -> FunctionOutput<&[RlpItem]> {
+    match self {
+      RlpItem::List(items) => Ok(items),
+      RlpItem::Bytes(_) => Err(AppError::Custom(
+        "Expected RLP list, found bytes".to_string(),
+      )),
+    }
+  }
+
+  fn as_u128(&self) -> FunctionOutput<u128> {
+    let bytes = self.as_bytes()?;
+
+    if bytes.len() > 16 || bytes.first() == Some(&0) {
+      return Err(AppError::Custom(format!(
+        "Invalid RLP integer 0x{}",
+        hex::encode(bytes)
+      )));
+    }
+
+    Ok(
+      bytes
+        .iter()
+        .fold(0u128, |value, &byte| (value << 8) | byte as u128),
+    )
+  }
+
+  fn as_u64(&self) -> FunctionOutput<u64> {
+    u64::try_from(self.as_u128()?)
+      .map_err(|_| AppError::Custom("RLP integer does not fit into 64 bits".to_string()))
+  }
+}
+
+fn write_rlp_length(buffer: &mut Vec<u8>, length: usize, offset: u8) {
+  if length <= 55 {
+    buffer.push(offset + length as u8);
+    return;
+  }
+
+  let length_bytes = length.to_be_bytes();
+  let first_non_zero = length_bytes.iter().position(|&byte| byte != 0).unwrap_or(0);
+
+  buffer.push(offset + 55 + (length_bytes.len() - first_non_zero) as u8);
+  buffer.extend_from_slice(&length_bytes[first_non_zero..]);
+}
+
+fn read_rlp_long_length(reader: &mut &[u8], length_of_length: u8) -> FunctionOutput<usize> {
+  let length_bytes = read_bytes(reader, length_of_length as usize)?;
+
+  if length_bytes.len() > 8 || length_bytes[0] == 0 {
+    return Err(AppError::Custom("Invalid RLP length".to_string()));
+  }
+
+  let length = length_bytes
+    .iter()
+    .fold(0u64, |length, &byte| (length << 8) | byte as u64);
+
+  if length <= 55 {
+    return Err(AppError::Custom("Non-canonical RLP length".to_string()));
+  }
+
+  usize::try_from(length).map_err(|_| AppError::Custom("RLP length is too large".to_string()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EthTransactionType {
+  Legacy,
+  AccessList,
+  DynamicFee,
+}
+
+impl EthTransactionType {
+  pub fn name(&self) -> &'static str {
+    match self {
+      EthTransactionType::Legacy => "Legacy (EIP-155)",
+      EthTransactionType::AccessList => "Access list (EIP-2930)",
+      EthTransactionType::DynamicFee => "Dynamic fee (EIP-1559)",
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthAccessListItem {
+  pub address: [u8; 20],
+  pub storage_keys: Vec<[u8; 32]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthTransaction {
+  pub transaction_type: EthTransactionType,
+  pub chain_id: u64,
+  pub nonce: u64,
+  pub gas_price: u128,
+  pub max_priority_fee_per_gas: u128,
+  pub max_fee_per_gas: u128,
+  pub gas_limit: u64,
+  pub to: Option<[u8; 20]>,
+  pub value: u128,
+  pub data: Vec<u8>,
+  pub access_list: Vec<EthAccessListItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthSignedTransaction {
+  pub from: String,
+  pub raw_transaction: String,
+  pub transaction_hash: String,
+}
+
+impl EthTransaction {
+  // JSON uses the field names of eth_signTransaction, anything else is treated as RLP hex
+  pub fn parse(input: &str) -> FunctionOutput<Self> {
+    let input = input.trim();
+
+    if input.starts_with('{') {
+      Self::from_json(input)
+    } else {
+      Self::from_rlp_hex(input)
+    }
+  }
+
+  pub fn from_json(json: &str) -> FunctionOutput<Self> {
+    d3bug(">>> EthTransaction::from_json", "debug");
+
+    let json: serde_json::Value = serde_json::from_str(json)
+      .map_err(|err| AppError::Custom(format!("Invalid transaction JSON: {err}")))?;
+
+    let field = |names: &[&str]| {
+      names
+        .iter()
+        .find_map(|name| json.get(*name))
+        .filter(|v| !v.is_null())
+    };
+
+    let quantity = |names: &[&str]| -> FunctionOutput<u128> {
+      match field(names) {
+        Some(value) => parse_json_quantity(value, names[0]),
+        None => Ok(0),
+      }
+    };
+
+    let access_list = match field(&["accessList"]) {
+      Some(value) => parse_json_access_list(value)?,
+      None => Vec::new(),
+    };
+
+    let transaction_type = match field(&["type"]) {
+      Some(value) => match parse_json_quantity(value, "type")? {
+        0 => EthTransactionType::Legacy,
+        1 => EthTransactionType::AccessList,
+        2 => EthTransactionType::DynamicFee,
+        other => {
+          return Err(AppError::Custom(format!(
+            "Unsupported transaction type {other}"
+          )));
+        }
+      },
+      None if field(&["maxFeePerGas"]).is_some() => EthTransactionType::DynamicFee,
+      None if field(&["accessList"]).is_some() => EthTransactionType::AccessList,
+      None => EthTransactionType::Legacy,
+    };
+
+    let chain_id = u64::try_from(quantity(&["chainId"])?)
+      .map_err(|_| AppError::Custom("chainId does not fit into 64 bits".to_string()))?;
+
+    // Missing, null and "" mean the field is empty, any other non-string value is rejected
+    let text = |names: &[&str]| -> FunctionOutput<Option<&str>> {
+      match field(names) {
+        Some(value) => value
+          .as_str()
+          .map(|text| Some(text).filter(|text| !text.is_empty()))
+          .ok_or_else(|| AppError::Custom(format!("Invalid value for {}", names[0]))),
+        None => Ok(None),
+      }
+    };
+
+    let to = match text(&["to"])? {
+      Some(to) => Some(parse_eth_address(to, chain_id)?),
+      None => None,
+    };
+
+    let data = match text(&["data", "input"])? {
+      Some(data) => hex::decode(data.strip_prefix("0x").unwrap_or(data))
+        .map_err(|err| AppError::Custom(format!("Invalid transaction data: {err}")))?,
+      None => Vec::new(),
+    };
+
+    let transaction = Self {
+      transaction_type,
+      chain_id,
+      nonce: u64::try_from(quantity(&["nonce"])?)
+        .map_err(|_| AppError::Custom("nonce does not fit into 64 bits".to_string()))?,
+      gas_price: quantity(&["gasPrice"])?,
+      max_priority_fee_per_gas: quantity(&["maxPriorityFeePerGas"])?,
+      max_fee_per_gas: quantity(&["maxFeePerGas"])?,
+      gas_limit: u64::try_from(quantity(&["gas", "gasLimit"])?)
+        .map_err(|_| AppError::Custom("gas does not fit into 64 bits".to_string()))?,
+      to,
+      value: quantity(&["value"])?,
+      data,
+      access_list,
+    };
+
+    transaction.validate()?;
+    Ok(transaction)
+  }
+
+  pub fn from_rlp_hex(rlp_hex: &str) -> FunctionOutput<Self> {
+    d3bug(">>> EthTransaction::from_rlp_hex", "debug");
+
+    let rlp_hex = rlp_hex.trim();
+    let bytes = hex::decode(rlp_hex.strip_prefix("0x").unwrap_or(rlp_hex))
+      .map_err(|err| AppError::Custom(format!("Invalid RLP hex: {err}")))?;
+
+    let (transaction_type, payload) = match bytes.first() {
+      Some(0x01) => (EthTransactionType::AccessList, &bytes[1..]),
+      Some(0x02) => (EthTransactionType::DynamicFee, &bytes[1..]),
+      Some(prefix) if *prefix >= 0xc0 => (EthTransactionType::Legacy, &bytes[..]),
+      _ => {
+        return Err(AppError::Custom(
+          "Unsupported transaction envelope".to_string(),
+        ));
+      }
+    };
+
+    let item = RlpItem::decode(payload)?;
+    let fields = item.as_list()?;
+
+    let parse_to = |item: &RlpItem| -> FunctionOutput<Option<[u8; 20]>> {
+      match item.as_bytes()? {
+        [] => Ok(None),
+        bytes => bytes
+          .try_into()
+          .map(Some)
+          .map_err(|_| AppError::Custom("Recipient must be 20 bytes".to_string())),
+      }
+    };
+
+    let transaction = match (transaction_type, fields.len()) {
+      (EthTransactionType::Legacy, 9) => {
+        if fields[7].as_u128()? != 0 || fields[8].as_u128()? != 0 {
+          return Err(AppError::Custom(
+            "Transaction is already signed".to_string(),
+          ));
+        }
+
+        Self {
+          transaction_type,
+          chain_id: fields[6].as_u64()?,
+          nonce: fields[0].as_u64()?,
+          gas_price: fields[1].as_u128()?,
+          max_priority_fee_per_gas: 0,
+          max_fee_per_gas: 0,
+          gas_limit: fields[2].as_u64()?,
+          to: parse_to(&fields[3])?,
+          value: fields[4].as_u128()?,
+          data: fields[5].as_bytes()?.to_vec(),
+          access_list: Vec::new(),
+        }
+      }
+      (EthTransactionType::Legacy, 6) => {
+        return Err(AppError::Custom(
+          "Legacy transaction has no chain ID, replay protection requires EIP-155 fields"
+            .to_string(),
+        ));
+      }
+      (EthTransactionType::AccessList, 8) => Self {
+        transaction_type,
+        chain_id: fields[0].as_u64()?,
+        nonce: fields[1].as_u64()?,
+        gas_price: fields[2].as_u128()?,
+        max_priority_fee_per_gas: 0,
+        max_fee_per_gas: 0,
+        gas_limit: fields[3].as_u64()?,
+        to: parse_to(&fields[4])?,
+        value: fields[5].as_u128()?,
+        data: fields[6].as_bytes()?.to_vec(),
+        access_list: decode_rlp_access_list(&fields[7])?,
+      },
+      (EthTransactionType::DynamicFee, 9) => Self {
+        transaction_type,
+        chain_id: fields[0].as_u64()?,
+        nonce: fields[1].as_u64()?,
+        gas_price: 0,
+        max_priority_fee_per_gas: fields[2].as_u128()?,
+        max_fee_per_gas: fields[3].as_u128()?,
+        gas_limit: fields[4].as_u64()?,
+        to: parse_to(&fields[5])?,
+        value: fields[6].as_u128()?,
+        data: fields[7].as_bytes()?.to_vec(),
+        access_list: decode_rlp_access_list(&fields[8])?,
+      },
+      (transaction_type, length) => {
+        return Err(AppError::Custom(format!(
+          "Unexpected {length} fields for an unsigned {} transaction",
+          transaction_type.name()
+        )));
+      }
+    };
+
+    transaction.validate()?;
+    Ok(transaction)
+  }
+
+  fn validate(&self) -> FunctionOutput<()> {
+    if self.chain_id == 0 {
+      return Err(AppError::Custom(
+        "Chain ID is required for replay protection".to_string(),
+      ));
+    }
+
+    if self.transaction_type == EthTransactionType::DynamicFee
+      && self.max_priority_fee_per_gas > self.max_fee_per_gas
+    {
+      return Err(AppError::Custom(
+        "maxPriorityFeePerGas is higher than maxFeePerGas".to_string(),
+      ));
+    }
+
+    Ok(())
+  }
+
+  pub fn fee_per_gas(&self) -> u128 {
+    match self.transaction_type {
+      EthTransactionType::DynamicFee => self.max_fee_per_gas,
+      _ => self.gas_price,
+    }
+  }
+
+  pub fn max_transaction_fee(&self) -> u128 {
+    self.fee_per_gas().saturating_mul(self.gas_limit as u128)
+  }
+
+  fn get_rlp_fields(&self) -> Vec<RlpItem> {
+    let to = RlpItem::Bytes(self.to.map(|to| to.to_vec()).unwrap_or_default());
+    let access_list = RlpItem::List(
+      self
+        .access_list
+        .iter()
+        .map(|item| {
+          RlpItem::List(vec![
+            RlpItem::Bytes(item.address.to_vec()),
+            RlpItem::List(
+              item
+                .storage_keys
+                .iter()
+                .map(|key| RlpItem::Bytes(key.to_vec()))
+                .collect(),
+            ),
+          ])
+        })
+        .collect(),
+    );
+
+    match self.transaction_type {
+      EthTransactionType::Legacy => vec![
+        RlpItem::from_u128(self.nonce as u128),
+        RlpItem::from_u128(self.gas_price),
+        RlpItem::from_u128(self.gas_limit as u128),
+        to,
+        RlpItem::from_u128(self.value),
+        RlpItem::Bytes(self.data.clone()),
+      ],
+      EthTransactionType::AccessList => vec![
+        RlpItem::from_u128(self.chain_id as u128),
+        RlpItem::from_u128(self.nonce as u128),
+        RlpItem::from_u128(self.gas_price),
+        RlpItem::from_u128(self.gas_limit as u128),
+        to,
+        RlpItem::from_u128(self.value),
+        RlpItem::Bytes(self.data.clone()),
+        access_list,
+      ],
+      EthTransactionType::DynamicFee => vec![
+        RlpItem::from_u128(self.chain_id as u128),
+        RlpItem::from_u128(self.nonce as u128),
+        RlpItem::from_u128(self.max_priority_fee_per_gas),
+        RlpItem::from_u128(self.max_fee_per_gas),
+        RlpItem::from_u128(self.gas_limit as u128),
+        to,
+        RlpItem::from_u128(self.value),
+        RlpItem::Bytes(self.data.clone()),
+        access_list,
+      ],
+    }
+  }
+
+  fn encode_envelope(&self, fields: Vec<RlpItem>) -> Vec<u8> {
+    let mut envelope = match self.transaction_type {
+      EthTransactionType::Legacy => Vec::new(),
+      EthTransactionType::AccessList => vec![0x01],
+      EthTransactionType::DynamicFee => vec![0x02],
+    };
+
+    envelope.extend_from_slice(&RlpItem::List(fields).encode());
+    envelope
+  }
+
+  pub fn encode_unsigned(&self) -> Vec<u8> {
+    let mut fields = self.get_rlp_fields();
+
+    // EIP-155 commits to the chain ID through three extra fields in the signing payload
+    if self.transaction_type == EthTransactionType::Legacy {
+      fields.push(RlpItem::from_u128(self.chain_id as u128));
+      fields.push(RlpItem::from_u128(0));
+      fields.push(RlpItem::from_u128(0));
+    }
+
+    self.encode_envelope(fields)
+  }
+
+  pub fn signing_hash(&self) -> [u8; 32] {
+    Keccak256::digest(self.encode_unsigned()).into()
+  }
+
+  fn encode_signed(&self, recovery_id: u8, r: &[u8], s: &[u8]) -> Vec<u8> {
+    let mut fields = self.get_rlp_fields();
+
+    let v = match self.transaction_type {
+      EthTransactionType::Legacy => self.chain_id as u128 * 2 + 35 + recovery_id as u128,
+      _ => recovery_id as u128,
+    };
+
+    let strip_zeros = |bytes: &[u8]| {
+      let first_non_zero = bytes
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(bytes.len());
+      RlpItem::Bytes(bytes[first_non_zero..].to_vec())
+    };
+
+    fields.push(RlpItem::from_u128(v));
+    fields.push(strip_zeros(r));
+    fields.push(strip_zeros(s));
+
+    self.encode_envelope(fields)
+  }
+}
+
+fn parse_json_quantity(value: &serde_json::Value, name: &str) -> FunctionOutput<u128> {
+  let invalid = || AppError::Custom(format!("Invalid value for {name}: {value}"));
+
+  match value {
+    serde_json::Value::Number(number) => number.as_u64().map(u128::from).ok_or_else(invalid),
+    serde_json::Value::String(text) => match text.strip_prefix("0x") {
+      Some("") => Ok(0),
+      Some(hex_value) => u128::from_str_radix(hex_value, 16).map_err(|_| invalid()),
+      None => text.parse::<u128>().map_err(|_| invalid()),
+    },
+    _ => Err(invalid()),
+  }
+}
+
+fn parse_json_access_list(value: &serde_json::Value) -> FunctionOutput<Vec<EthAccessListItem>> {
+  let invalid = || AppError::Custom("Invalid accessList".to_string());
+
+  value
+    .as_array()
+    .ok_or_else(invalid)?
+    .iter()
+    .map(|item| {
+      let address = item
+        .get("address")
+        .and_then(|address| address.as_str())
+        .ok_or_else(invalid)?;
+      let address = decode_hex_array::<20>(address).ok_or_else(invalid)?;
+
+      let storage_keys = item
+        .get("storageKeys")
+        .and_then(|keys| keys.as_array())
+        .map(|keys| {
+          keys
+            .iter()
+            .map(|key| key.as_str().and_then(decode_hex_array::<32>))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+      Ok(EthAccessListItem {
+        address,
+        storage_keys,
+      })
+    })
+    .collect()
+}
+
+fn decode_rlp_access_list(item: &RlpItem) -> FunctionOutput<Vec<EthAccessListItem>> {
+  let invalid = || AppError::Custom("Invalid RLP access list".to_string());
+
+  item
+    .as_list()?
+    .iter()
+    .map(|entry| {
+      let [address, storage_keys] = entry.as_list()? else {
+        return Err(invalid());
+      };
+
+      Ok(EthAccessListItem {
+        address: address.as_bytes()?.try_into().map_err(|_| invalid())?,
+        storage_keys: storage_keys
+          .as_list()?
+          .iter()
+          .map(|key| key.as_bytes()?.try_into().map_err(|_| invalid()))
+          .collect::<FunctionOutput<Vec<_>>>()?,
+      })
+    })
+    .collect()
+}
+
+fn decode_hex_array<const N: usize>(text: &str) -> Option<[u8; N]> {
+  let mut array = [0u8; N];
+  hex::decode_to_slice(text.strip_prefix("0x").unwrap_or(text), &mut array).ok()?;
+  Some(array)
+}
+
+// Mixed case recipients must carry a valid EIP-55 checksum, with or without the EIP-1191 chain ID
+fn parse_eth_address(address: &str, chain_id: u64) -> FunctionOutput<[u8; 20]> {
+  keys::validate_eip55_address(address, None)
+    .or_else(|_| keys::validate_eip55_address(address, Some(chain_id)))?;
+
+  decode_hex_array::<20>(address)
+    .ok_or_else(|| AppError::Custom(format!("Invalid recipient address {address}")))
+}
+
+pub fn format_eth_units(value: u128, decimals: u32) -> String {
+  let unit = 10u128.pow(decimals);
+  let fraction = format!("{:0width$}", value % unit, width = decimals as usize);
+  let fraction = fraction.trim_end_matches('0');
+
+  if fraction.is_empty() {
+    format!("{}", value / unit)
+  } else {
+    format!("{}.{fraction}", value / unit)
+  }
+}
+
+pub fn sign_eth_transaction(
+  transaction: &EthTransaction,
+  ingredients: &keys::AddressHocusPokus,
+) -> FunctionOutput<EthSignedTransaction> {
+  d3bug(">>> sign_eth_transaction", "debug");
+  d3bug(
+    &format!("derivation_path {:?}", ingredients.derivation_path),
+    "debug",
+  );
+
+  if ingredients.key_derivation != "secp256k1" || ingredients.hash != "keccak256" {
+    return Err(AppError::Custom(
+      "Ethereum transactions need a secp256k1 keccak256 coin".to_string(),
+    ));
+  }
+
+  if ingredients.coin_index == 195 {
+    return Err(AppError::Custom(
+      "Tron transactions are not RLP encoded".to_string(),
+    ));
+  }
+
+  if let Some(chain_id) = ingredients.chain_id
+    && transaction.chain_id != chain_id
+  {
+    return Err(AppError::Custom(format!(
+      "Transaction chain ID {} does not match coin chain ID {chain_id}",
+      transaction.chain_id
+    )));
+  }
+
+  let relative_path = keys::get_relative_derivation_path(ingredients)?;
+
+  let (mut private_key, mut chain_code, _public_key) = keys::derive_from_path_secp256k1(
+    &ingredients.master_private_key_bytes,
+    &ingredients.master_chain_code_bytes,
+    &ingredients.master_public_key_bytes,
+    &relative_path,
+  )?
+  .ok_or_else(|| {
+    AppError::Custom(format!(
+      "Can not derive key at {}",
+      ingredients.derivation_path
+    ))
+  })?;
+  zeroize::Zeroize::zeroize(&mut chain_code);
+
+  let signed_transaction = sign_eth_transaction_with_key(
+    transaction,
+    &private_key,
+    ingredients.coin_index,
+    keys::get_eip1191_chain_id(ingredients),
+  );
+  zeroize::Zeroize::zeroize(&mut private_key);
+
+  signed_transaction
+}
+
+pub fn sign_eth_transaction_with_key(
+  transaction: &EthTransaction,
+  private_key: &[u8],
+  coin_index: u32,
+  eip1191_chain_id: Option<u64>,
+) -> FunctionOutput<EthSignedTransaction> {
+  transaction.validate()?;
+
+  let mut key_bytes = [0u8; 32];
+  key_bytes.copy_from_slice(
+    private_key
+      .get(..32)
+      .ok_or_else(|| AppError::Custom("Private key must be 32 bytes".to_string()))?,
+  );
+  let secret_key = secp256k1::SecretKey::from_byte_array(key_bytes)
+    .map_err(|err| AppError::Custom(format!("Invalid private key: {err}")));
+  zeroize::Zeroize::zeroize(&mut key_bytes);
+  let secret_key = secret_key?;
+
+  let secp = keys::secp256k1_context();
+  let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key);
+  let from = keys::generate_address_keccak256(
+    &keys::CryptoPublicKey::Secp256k1(public_key),
+    &[],
+    coin_index,
+    eip1191_chain_id,
+  )?;
+
+  let (recovery_id, compact) = secp
+    .sign_ecdsa_recoverable(
+      secp256k1::Message::from_digest(transaction.signing_hash()),
+      &secret_key,
+    )
+    .serialize_compact();
+
+  let raw_transaction =
+    transaction.encode_signed(i32::from(recovery_id) as u8, &compact[..32], &compact[32..]);
+  let transaction_hash = Keccak256::digest(&raw_transaction);
+
+  Ok(EthSignedTransaction {
+    from,
+    raw_transaction: format!("0x{}", hex::encode(&raw_transaction)),
+    transaction_hash: format!("0x{}", hex::encode(transaction_hash)),
+  })
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
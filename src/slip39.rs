@@ -0,0 +1,730 @@
+// authors = ["Control Owl <qr2m[at]r-o0-t[dot]wtf>"]
+// license = "CC-BY-NC-ND-4.0  [2023-2025]  Control Owl"
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+
+use crate::{AppError, FunctionOutput, d3bug};
+use qr2m_lib::{SecretBytes, SecretString};
+use rand::Rng;
+use zeroize::Zeroize;
+
+const SLIP39_WORDLIST_FILE: &str = "slip39.txt";
+const SLIP39_WORDLIST_LENGTH: usize = 1024;
+const RADIX_BITS: usize = 10;
+const ID_LENGTH_BITS: usize = 15;
+const CHECKSUM_LENGTH_WORDS: usize = 3;
+const METADATA_LENGTH_WORDS: usize = 7;
+const MIN_STRENGTH_BITS: usize = 128;
+const MIN_MNEMONIC_LENGTH_WORDS: usize =
+  METADATA_LENGTH_WORDS + MIN_STRENGTH_BITS.div_ceil(RADIX_BITS);
+const MAX_SHARE_COUNT: u8 = 16;
+const DIGEST_LENGTH_BYTES: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const BASE_ITERATION_COUNT: u32 = 10000;
+const ROUND_COUNT: u8 = 4;
+const CUSTOMIZATION_STRING_ORIGINAL: &[u8] = b"shamir";
+const CUSTOMIZATION_STRING_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+lazy_static::lazy_static! {
+    static ref GF256_TABLES: ([u8; 255], [u8; 256]) = {
+        let mut exp = [0u8; 255];
+        let mut log = [0u8; 256];
+        let mut poly: u16 = 1;
+
+        for (i, value) in exp.iter_mut().enumerate() {
+            *value = poly as u8;
+            log[poly as usize] = i as u8;
+
+            // Multiply by the generator x + 1 modulo the Rijndael polynomial
+            poly = (poly << 1) ^ poly;
+            if poly & 0x100 != 0 {
+                poly ^= 0x11b;
+            }
+        }
+
+        (exp, log)
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slip39Share {
+  pub identifier: u16,
+  pub extendable: bool,
+  pub iteration_exponent: u8,
+  pub group_index: u8,
+  pub group_threshold: u8,
+  pub group_count: u8,
+  pub member_index: u8,
+  pub member_threshold: u8,
+  pub value: SecretBytes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip39Group {
+  pub member_threshold: u8,
+  pub member_count: u8,
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// WORDLIST
+
+fn get_slip39_wordlist() -> FunctionOutput<Vec<String>> {
+  let wordlist_path = std::path::Path::new("wordlists").join(SLIP39_WORDLIST_FILE);
+  let wordlist: Vec<String> = qr2m_lib::get_text_from_resources(wordlist_path.to_str().unwrap())
+    .lines()
+    .map(|word| word.trim().to_string())
+    .filter(|word| !word.is_empty())
+    .collect();
+
+  if wordlist.len() != SLIP39_WORDLIST_LENGTH {
+    return Err(AppError::Custom(format!(
+      "SLIP-39 wordlist must contain {SLIP39_WORDLIST_LENGTH} words, got {}",
+      wordlist.len()
+    )));
+  }
+
+  Ok(wordlist)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// CHECKSUM
+
+fn calculate_rs1024_polymod(values: impl IntoIterator<Item = u32>) -> u32 {
+  const GENERATOR: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+    0x21B1F890, 0x3F3F120,
+  ];
+
+  let mut checksum: u32 = 1;
+
+  for value in values {
+    let top = checksum >> 20;
+    checksum = ((checksum & 0xFFFFF) << 10) ^ value;
+
+    for (i, generator) in GENERATOR.iter().enumerate() {
+      if (top >> i) & 1 == 1 {
+        checksum ^= generator;
+      }
+    }
+  }
+
+  checksum
+}
+
+fn get_customization_string(extendable: bool) -> &'static [u8] {
+  if extendable {
+    CUSTOMIZATION_STRING_EXTENDABLE
+  } else {
+    CUSTOMIZATION_STRING_ORIGINAL
+  }
+}
+
+fn create_rs1024_checksum(data: &[u16], extendable: bool) -> [u16; CHECKSUM_LENGTH_WORDS] {
+  let values = get_customization_string(extendable)
+    .iter()
+    .map(|&byte| byte as u32)
+    .chain(data.iter().map(|&word| word as u32))
+    .chain([0; CHECKSUM_LENGTH_WORDS]);
+  let polymod = calculate_rs1024_polymod(values) ^ 1;
+
+  [
+    ((polymod >> 20) & 1023) as u16,
+    ((polymod >> 10) & 1023) as u16,
+    (polymod & 1023) as u16,
+  ]
+}
+
+fn verify_rs1024_checksum(data: &[u16], extendable: bool) -> bool {
+  let values = get_customization_string(extendable)
+    .iter()
+    .map(|&byte| byte as u32)
+    .chain(data.iter().map(|&word| word as u32));
+
+  calculate_rs1024_polymod(values) == 1
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// SHARE ENCODING
+
+impl Slip39Share {
+  fn decode(mnemonic: &str, wordlist: &[String]) -> FunctionOutput<Self> {
+    let mut indices = mnemonic
+      .split_whitespace()
+      .map(|word| {
+        let word = word.to_lowercase();
+        wordlist
+          .binary_search(&word)
+          .map(|index| index as u16)
+          .map_err(|_| AppError::Custom(format!("Invalid SLIP-39 word {word}")))
+      })
+      .collect::<FunctionOutput<Vec<u16>>>()?;
+
+    if indices.len() < MIN_MNEMONIC_LENGTH_WORDS {
+      return Err(AppError::Custom(format!(
+        "SLIP-39 mnemonic must be at least {MIN_MNEMONIC_LENGTH_WORDS} words"
+      )));
+    }
+
+    let padding_length = (RADIX_BITS * (indices.len() - METADATA_LENGTH_WORDS)) % 16;
+    if padding_length > 8 {
+      return Err(AppError::Custom(
+        "Invalid SLIP-39 mnemonic length".to_string(),
+      ));
+    }
+
+    let id_exponent = ((indices[0] as u32) << RADIX_BITS) | indices[1] as u32;
+    let identifier = (id_exponent >> 5) as u16;
+    let extendable = (id_exponent >> 4) & 1 == 1;
+    let iteration_exponent = (id_exponent & 0x0F) as u8;
+
+    if !verify_rs1024_checksum(&indices, extendable) {
+      return Err(AppError::Custom(format!(
+        "Invalid SLIP-39 checksum for mnemonic starting with {}",
+        mnemonic
+          .split_whitespace()
+          .take(2)
+          .collect::<Vec<_>>()
+          .join(" ")
+      )));
+    }
+
+    let share_parameters = ((indices[2] as u32) << RADIX_BITS) | indices[3] as u32;
+    let group_index = (share_parameters >> 16) as u8;
+    let group_threshold = ((share_parameters >> 12) & 0x0F) as u8 + 1;
+    let group_count = ((share_parameters >> 8) & 0x0F) as u8 + 1;
+    let member_index = ((share_parameters >> 4) & 0x0F) as u8;
+    let member_threshold = (share_parameters & 0x0F) as u8 + 1;
+
+    if group_threshold > group_count {
+      return Err(AppError::Custom(format!(
+        "Invalid SLIP-39 group threshold {group_threshold} of {group_count}"
+      )));
+    }
+
+    let value_words = &indices[4..indices.len() - CHECKSUM_LENGTH_WORDS];
+    let value_length = (RADIX_BITS * value_words.len() - padding_length) / 8;
+    let mut value = Vec::with_capacity(value_length);
+    let mut accumulator: u32 = 0;
+    let mut accumulator_bits = 0;
+    let mut padding_bits = padding_length;
+
+    for &word in value_words {
+      accumulator = (accumulator << RADIX_BITS) | word as u32;
+      accumulator_bits += RADIX_BITS;
+
+      // Padding occupies the most significant bits of the first value word and must be zero
+      if padding_bits > 0 {
+        if accumulator >> (accumulator_bits - padding_bits) != 0 {
+          return Err(AppError::Custom(
+            "Invalid SLIP-39 mnemonic padding".to_string(),
+          ));
+        }
+
+        accumulator_bits -= padding_bits;
+        padding_bits = 0;
+      }
+
+      while accumulator_bits >= 8 {
+        accumulator_bits -= 8;
+        value.push((accumulator >> accumulator_bits) as u8);
+        accumulator &= (1 << accumulator_bits) - 1;
+      }
+    }
+    indices.zeroize();
+
+    Ok(Self {
+      identifier,
+      extendable,
+      iteration_exponent,
+      group_index,
+      group_threshold,
+      group_count,
+      member_index,
+      member_threshold,
+      value: value.into(),
+    })
+  }
+
+  fn encode(&self, wordlist: &[String]) -> SecretString {
+    let id_exponent = ((self.identifier as u32) << 5)
+      | ((self.extendable as u32) << 4)
+      | self.iteration_exponent as u32;
+    let share_parameters = ((self.group_index as u32) << 16)
+      | (((self.group_threshold - 1) as u32) << 12)
+      | (((self.group_count - 1) as u32) << 8)
+      | ((self.member_index as u32) << 4)
+      | (self.member_threshold - 1) as u32;
+
+    let mut indices: Vec<u16> = vec![
+      (id_exponent >> RADIX_BITS) as u16,
+      (id_exponent & 1023) as u16,
+      (share_parameters >> RADIX_BITS) as u16,
+      (share_parameters & 1023) as u16,
+    ];
+
+    let value_word_count = (self.value.len() * 8).div_ceil(RADIX_BITS);
+    let mut accumulator: u32 = 0;
+    let mut accumulator_bits = value_word_count * RADIX_BITS - self.value.len() * 8;
+
+    for &byte in self.value.iter() {
+      accumulator = (accumulator << 8) | byte as u32;
+      accumulator_bits += 8;
+
+      while accumulator_bits >= RADIX_BITS {
+        accumulator_bits -= RADIX_BITS;
+        indices.push(((accumulator >> accumulator_bits) & 1023) as u16);
+        accumulator &= (1 << accumulator_bits) - 1;
+      }
+    }
+
+    let checksum = create_rs1024_checksum(&indices, self.extendable);
+    indices.extend_from_slice(&checksum);
+
+    let mnemonic = indices
+      .iter()
+      .map(|&index| wordlist[index as usize].as_str())
+      .collect::<Vec<_>>()
+      .join(" ");
+    indices.zeroize();
+
+    mnemonic.into()
+  }
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// SHAMIR SECRET SHARING
+
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> FunctionOutput<Vec<u8>> {
+  if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+    return Ok(value.to_vec());
+  }
+
+  let value_length = shares
+    .first()
+    .map(|(_, value)| value.len())
+    .unwrap_or_default();
+  if shares.iter().any(|(_, value)| value.len() != value_length) {
+    return Err(AppError::Custom(
+      "All SLIP-39 share values must have the same length".to_string(),
+    ));
+  }
+
+  let (exp, log) = &*GF256_TABLES;
+  let log_product: u32 = shares
+    .iter()
+    .map(|(index, _)| log[(index ^ x) as usize] as u32)
+    .sum();
+
+  let mut result = vec![0u8; value_length];
+
+  for (index, value) in shares {
+    let log_basis = (log_product + 255 * shares.len() as u32
+      - log[(index ^ x) as usize] as u32
+      - shares
+        .iter()
+        .map(|(other, _)| log[(index ^ other) as usize] as u32)
+        .sum::<u32>())
+      % 255;
+
+    for (output, &byte) in result.iter_mut().zip(value.iter()) {
+      if byte != 0 {
+        *output ^= exp[((log[byte as usize] as u32 + log_basis) % 255) as usize];
+      }
+    }
+  }
+
+  Ok(result)
+}
+
+fn calculate_digest(random_part: &[u8], shared_secret: &[u8]) -> Vec<u8> {
+  let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, random_part);
+  ring::hmac::sign(&key, shared_secret).as_ref()[..DIGEST_LENGTH_BYTES].to_vec()
+}
+
+fn split_secret(
+  threshold: u8,
+  share_count: u8,
+  shared_secret: &[u8],
+) -> FunctionOutput<Vec<(u8, SecretBytes)>> {
+  if threshold == 0 || threshold > share_count || share_count > MAX_SHARE_COUNT {
+    return Err(AppError::Custom(format!(
+      "Invalid SLIP-39 threshold {threshold} of {share_count}"
+    )));
+  }
+
+  if threshold == 1 {
+    return Ok(
+      (0..share_count)
+        .map(|index| (index, shared_secret.into()))
+        .collect(),
+    );
+  }
+
+  let mut rng = rand::rng();
+  let random_share_count = threshold - 2;
+  let mut shares: Vec<(u8, SecretBytes)> = (0..random_share_count)
+    .map(|index| {
+      let mut value = vec![0u8; shared_secret.len()];
+      rng.fill(&mut value[..]);
+      (index, value.into())
+    })
+    .collect();
+
+  let mut random_part = vec![0u8; shared_secret.len() - DIGEST_LENGTH_BYTES];
+  rng.fill(&mut random_part[..]);
+  let digest_share: SecretBytes = [calculate_digest(&random_part, shared_secret), random_part]
+    .concat()
+    .into();
+
+  let mut base_shares: Vec<(u8, &[u8])> = shares
+    .iter()
+    .map(|(index, value)| (*index, value.as_slice()))
+    .collect();
+  base_shares.push((DIGEST_INDEX, &digest_share));
+  base_shares.push((SECRET_INDEX, shared_secret));
+
+  let derived_shares = (random_share_count..share_count)
+    .map(|index| Ok((index, interpolate(&base_shares, index)?.into())))
+    .collect::<FunctionOutput<Vec<(u8, SecretBytes)>>>()?;
+  shares.extend(derived_shares);
+
+  Ok(shares)
+}
+
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> FunctionOutput<SecretBytes> {
+  if threshold == 1 {
+    return shares
+      .first()
+      .map(|(_, value)| SecretBytes::from(*value))
+      .ok_or_else(|| AppError::Custom("No SLIP-39 shares provided".to_string()));
+  }
+
+  let shared_secret: SecretBytes = interpolate(shares, SECRET_INDEX)?.into();
+  let digest_share: SecretBytes = interpolate(shares, DIGEST_INDEX)?.into();
+  let (digest, random_part) = digest_share.split_at(DIGEST_LENGTH_BYTES);
+
+  if calculate_digest(random_part, &shared_secret) != digest {
+    return Err(AppError::Custom(
+      "Invalid SLIP-39 digest of the shared secret".to_string(),
+    ));
+  }
+
+  Ok(shared_secret)
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// ENCRYPTION
+
+fn calculate_round_function(
+  round: u8,
+  passphrase: &[u8],
+  iteration_exponent: u8,
+  salt: &[u8],
+  data: &[u8],
+) -> Vec<u8> {
+  let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+  let mut password = [&[round][..], passphrase].concat();
+  let mut output = vec![0u8; data.len()];
+
+  ring::pbkdf2::derive(
+    ring::pbkdf2::PBKDF2_HMAC_SHA256,
+    std::num::NonZeroU32::new(iterations).unwrap(),
+    &[salt, data].concat(),
+    &password,
+    &mut output,
+  );
+  password.zeroize();
+
+  output
+}
+
+fn get_salt(identifier: u16, extendable: bool) -> Vec<u8> {
+  if extendable {
+    Vec::new()
+  } else {
+    [CUSTOMIZATION_STRING_ORIGINAL, &identifier.to_be_bytes()].concat()
+  }
+}
+
+fn apply_feistel(
+  data: &[u8],
+  passphrase: &[u8],
+  iteration_exponent: u8,
+  identifier: u16,
+  extendable: bool,
+  rounds: impl Iterator<Item = u8>,
+) -> SecretBytes {
+  let (left, right) = data.split_at(data.len() / 2);
+  let mut left = left.to_vec();
+  let mut right = right.to_vec();
+  let salt = get_salt(identifier, extendable);
+
+  for round in rounds {
+    let mut round_output =
+      calculate_round_function(round, passphrase, iteration_exponent, &salt, &right);
+    for (output, byte) in round_output.iter_mut().zip(left.iter()) {
+      *output ^= byte;
+    }
+
+    left.zeroize();
+    left = std::mem::replace(&mut right, round_output);
+  }
+
+  let result = [right.as_slice(), left.as_slice()].concat();
+  left.zeroize();
+  right.zeroize();
+
+  result.into()
+}
+
+fn validate_passphrase(passphrase: &str) -> FunctionOutput<()> {
+  if passphrase.bytes().all(|byte| (32..=126).contains(&byte)) {
+    Ok(())
+  } else {
+    Err(AppError::Custom(
+      "SLIP-39 passphrase must contain only printable ASCII characters".to_string(),
+    ))
+  }
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// SLIP-39
+
+pub fn generate_slip39_shares(
+  master_secret: &[u8],
+  passphrase: &str,
+  group_threshold: u8,
+  groups: &[Slip39Group],
+  iteration_exponent: u8,
+  extendable: bool,
+) -> FunctionOutput<Vec<Vec<SecretString>>> {
+  d3bug(">>> generate_slip39_shares", "debug");
+  d3bug(&format!("group_threshold {group_threshold:?}"), "debug");
+  d3bug(&format!("groups {groups:?}"), "debug");
+
+  if master_secret.len() * 8 < MIN_STRENGTH_BITS || !master_secret.len().is_multiple_of(2) {
+    return Err(AppError::Custom(format!(
+      "SLIP-39 master secret must be at least {} bytes and an even length, got {}",
+      MIN_STRENGTH_BITS / 8,
+      master_secret.len()
+    )));
+  }
+
+  if group_threshold as usize > groups.len() {
+    return Err(AppError::Custom(format!(
+      "SLIP-39 group threshold {group_threshold} exceeds {} groups",
+      groups.len()
+    )));
+  }
+
+  if iteration_exponent > 15 {
+    return Err(AppError::Custom(format!(
+      "SLIP-39 iteration exponent {iteration_exponent} is above 15"
+    )));
+  }
+
+  if let Some(group) = groups
+    .iter()
+    .find(|group| group.member_threshold == 1 && group.member_count > 1)
+  {
+    return Err(AppError::Custom(format!(
+      "SLIP-39 group of {} members with threshold 1 is not allowed, use 1 of 1 instead",
+      group.member_count
+    )));
+  }
+
+  validate_passphrase(passphrase)?;
+
+  let wordlist = get_slip39_wordlist()?;
+  let identifier = rand::rng().random::<u16>() & ((1 << ID_LENGTH_BITS) - 1);
+  let encrypted_master_secret = apply_feistel(
+    master_secret,
+    passphrase.as_bytes(),
+    iteration_exponent,
+    identifier,
+    extendable,
+    0..ROUND_COUNT,
+  );
+
+  let group_shares = split_secret(
+    group_threshold,
+    groups.len() as u8,
+    &encrypted_master_secret,
+  )?;
+
+  group_shares
+    .iter()
+    .zip(groups)
+    .map(|((group_index, group_secret), group)| {
+      Ok(
+        split_secret(group.member_threshold, group.member_count, group_secret)?
+          .into_iter()
+          .map(|(member_index, value)| {
+            Slip39Share {
+              identifier,
+              extendable,
+              iteration_exponent,
+              group_index: *group_index,
+              group_threshold,
+              group_count: groups.len() as u8,
+              member_index,
+              member_threshold: group.member_threshold,
+              value,
+            }
+            .encode(&wordlist)
+          })
+          .collect(),
+      )
+    })
+    .collect()
+}
+
+pub fn recover_slip39_secret(mnemonics: &[&str], passphrase: &str) -> FunctionOutput<SecretBytes> {
+  d3bug(">>> recover_slip39_secret", "debug");
+
+  validate_passphrase(passphrase)?;
+
+  let wordlist = get_slip39_wordlist()?;
+  let shares = mnemonics
+    .iter()
+    .filter(|mnemonic| !mnemonic.trim().is_empty())
+    .map(|mnemonic| Slip39Share::decode(mnemonic, &wordlist))
+    .collect::<FunctionOutput<Vec<_>>>()?;
+
+  let first = shares
+    .first()
+    .ok_or_else(|| AppError::Custom("No SLIP-39 shares provided".to_string()))?;
+
+  if shares.iter().any(|share| {
+    share.identifier != first.identifier
+      || share.extendable != first.extendable
+      || share.iteration_exponent != first.iteration_exponent
+      || share.group_threshold != first.group_threshold
+      || share.group_count != first.group_count
+      || share.value.len() != first.value.len()
+  }) {
+    return Err(AppError::Custom(
+      "SLIP-39 shares do not belong to the same backup".to_string(),
+    ));
+  }
+
+  let mut group_shares: Vec<(u8, SecretBytes)> = Vec::new();
+
+  for group_index in 0..first.group_count {
+    let mut members: Vec<&Slip39Share> = Vec::new();
+
+    for share in shares
+      .iter()
+      .filter(|share| share.group_index == group_index)
+    {
+      if share.member_threshold
+        != members
+          .first()
+          .map_or(share.member_threshold, |m| m.member_threshold)
+      {
+        return Err(AppError::Custom(format!(
+          "SLIP-39 shares of group {} have different thresholds",
+          group_index + 1
+        )));
+      }
+
+      match members
+        .iter()
+        .find(|member| member.member_index == share.member_index)
+      {
+        Some(member) if member.value != share.value => {
+          return Err(AppError::Custom(format!(
+            "SLIP-39 group {} contains conflicting shares with index {}",
+            group_index + 1,
+            share.member_index + 1
+          )));
+        }
+        Some(_) => {}
+        None => members.push(share),
+      }
+    }
+
+    let Some(member_threshold) = members.first().map(|member| member.member_threshold) else {
+      continue;
+    };
+
+    if members.len() < member_threshold as usize {
+      continue;
+    }
+
+    let member_values: Vec<(u8, &[u8])> = members
+      .iter()
+      .take(member_threshold as usize)
+      .map(|member| (member.member_index, member.value.as_slice()))
+      .collect();
+    group_shares.push((
+      group_index,
+      recover_secret(member_threshold, &member_values)?,
+    ));
+  }
+
+  if group_shares.len() < first.group_threshold as usize {
+    return Err(AppError::Custom(format!(
+      "Insufficient SLIP-39 shares, {} of {} required groups are complete",
+      group_shares.len(),
+      first.group_threshold
+    )));
+  }
+
+  let group_values: Vec<(u8, &[u8])> = group_shares
+    .iter()
+    .take(first.group_threshold as usize)
+    .map(|(group_index, value)| (*group_index, value.as_slice()))
+    .collect();
+  let encrypted_master_secret = recover_secret(first.group_threshold, &group_values)?;
+
+  Ok(apply_feistel(
+    &encrypted_master_secret,
+    passphrase.as_bytes(),
+    first.iteration_exponent,
+    first.identifier,
+    first.extendable,
+    (0..ROUND_COUNT).rev(),
+  ))
+}
+
+// -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
+// ENTROPY
+
+pub fn get_master_secret_from_entropy(full_entropy: &str) -> FunctionOutput<SecretBytes> {
+  if !qr2m_lib::is_valid_entropy(full_entropy) {
+    return Err(AppError::Custom("Invalid entropy".to_string()));
+  }
+
+  // The BIP39 checksum adds one bit for every 32 bits of entropy
+  let entropy_length = full_entropy.len() * 32 / 33;
+
+  full_entropy.as_bytes()[..entropy_length]
+    .chunks(8)
+    .map(|chunk| {
+      u8::from_str_radix(std::str::from_utf8(chunk).unwrap_or_default(), 2)
+        .map_err(|err| AppError::Custom(format!("Invalid entropy: {err}")))
+    })
+    .collect::<FunctionOutput<Vec<u8>>>()
+    .map(SecretBytes::from)
+}
+
+pub fn get_entropy_from_master_secret(master_secret: &[u8]) -> FunctionOutput<SecretString> {
+  if !matches!(master_secret.len(), 16 | 20 | 24 | 28 | 32) {
+    return Err(AppError::Custom(format!(
+      "A {}-bit master secret has no BIP39 mnemonic",
+      master_secret.len() * 8
+    )));
+  }
+
+  let entropy = SecretString::new(
+    master_secret
+      .iter()
+      .map(|byte| format!("{byte:08b}"))
+      .collect(),
+  );
+  let checksum = qr2m_lib::calculate_checksum_for_entropy(&entropy);
+
+  Ok(format!("{entropy}{checksum}").into())
+}
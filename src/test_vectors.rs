@@ -3,6 +3,9 @@
 
 // -.-. --- .--. -.-- .-. .. --. .... - / --.- .-. ..--- -- .- - .-. --- ----- - -.. --- - .-- - ..-.
 
+use crate::sign;
+use crate::slip39;
+
 struct _EntropyMnemonicVector {
   entropy: &'static str,
   mnemonic: &'static str,
@@ -33,6 +36,111 @@ struct _MasterChildVector {
   expected_child_public_key_bytes: &'static str,
 }
 
+struct _ExtendedKeyVector {
+  seed: &'static str,
+  derivation_path: &'static str,
+  expected_xprv: &'static str,
+  expected_xpub: &'static str,
+}
+
+struct _PublicDerivationVector {
+  parent_xpub: &'static str,
+  index: u32,
+  expected_xpub: &'static str,
+}
+
+struct _ExtendedKeyImportVector {
+  extended_key: &'static str,
+  expected_depth: u8,
+  expected_parent_fingerprint: &'static str,
+  expected_child_number: u32,
+  expected_xpub: &'static str,
+}
+
+struct _MnemonicAddressVector {
+  mnemonic: &'static str,
+  passphrase: &'static str,
+  coin_index: u32,
+  bip: u32,
+  derivation_path: &'static str,
+  public_key_hash: &'static str,
+  script_hash: &'static str,
+  bech32_hrp: &'static str,
+  wallet_import_format: &'static str,
+  expected_address: &'static str,
+  expected_public_key: &'static str,
+  expected_private_key: &'static str,
+}
+
+struct _TaprootVector {
+  mnemonic: &'static str,
+  derivation_path: &'static str,
+  expected_internal_key: &'static str,
+  expected_output_key: &'static str,
+  expected_address: &'static str,
+}
+
+struct _Eip55Vector {
+  chain_id: Option<u64>,
+  expected_address: &'static str,
+}
+
+struct _Slip10Vector {
+  seed: &'static str,
+  derivation_path: &'static str,
+  expected_chain_code: &'static str,
+  expected_private_key: &'static str,
+  expected_public_key: &'static str,
+}
+
+struct _Blake2bAddressVector {
+  mnemonic: &'static str,
+  passphrase: &'static str,
+  coin_index: u32,
+  key_derivation: &'static str,
+  derivation_path: &'static str,
+  expected_address: &'static str,
+  expected_public_key: &'static str,
+  expected_private_key: &'static str,
+}
+
+struct _CardanoAddressVector {
+  derivation_path: &'static str,
+  expected_address: &'static str,
+  expected_public_key: &'static str,
+  expected_private_key: &'static str,
+}
+
+struct _MoneroAddressVector {
+  derivation_path: &'static str,
+  expected_address: &'static str,
+  expected_private_view_key: &'static str,
+  expected_public_view_key: &'static str,
+  expected_public_key: &'static str,
+  expected_private_key: &'static str,
+}
+
+struct _MessageSignatureVector {
+  address: &'static str,
+  private_key: &'static str,
+  message: &'static str,
+  expected_format: sign::MessageSignatureFormat,
+  expected_signature: &'static str,
+}
+
+struct _Bip85Vector {
+  application: &'static str,
+  dictionary: &'static str,
+  length: u32,
+  index: u32,
+  expected: &'static str,
+}
+
+struct _Slip39Vector {
+  mnemonics: &'static [&'static str],
+  expected_master_secret: &'static str,
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -183,19 +291,31 @@ mod tests {
       let wallet_settings = crate::WALLET_SETTINGS.lock().unwrap();
 
       assert_eq!(
-        wallet_settings.master_private_key,
-        Some(vector.expected_master_xprv.to_string())
+        wallet_settings.master_private_key.as_deref(),
+        Some(vector.expected_master_xprv)
       );
       assert_eq!(
         wallet_settings.master_public_key,
         Some(vector.expected_master_xpub.to_string())
       );
       assert_eq!(
-        hex::encode(wallet_settings.master_private_key_bytes.clone().unwrap()),
+        hex::encode(
+          wallet_settings
+            .master_private_key_bytes
+            .clone()
+            .unwrap()
+            .as_slice()
+        ),
         vector.expected_master_private_key
       );
       assert_eq!(
-        hex::encode(wallet_settings.master_chain_code_bytes.clone().unwrap()),
+        hex::encode(
+          wallet_settings
+            .master_chain_code_bytes
+            .clone()
+            .unwrap()
+            .as_slice()
+        ),
         vector.expected_master_chain_code
       );
       assert_eq!(
@@ -291,8 +411,7 @@ mod tests {
       match keys::derive_child_key_secp256k1(
         &master_private_key_bytes,
         &master_chain_code_bytes,
-        vector.index,
-        vector.hardened,
+        qr2m_lib::ChildNumber::new(vector.index, vector.hardened).expect("invalid child index"),
       ) {
         Ok(Some((child_private_key_bytes, child_chain_code_bytes, child_public_key_bytes))) => {
           assert_eq!(
@@ -311,6 +430,3230 @@ mod tests {
         _ => panic!("Error deriving keys"),
       }
     }
+
+    // The highest hardened index must not overflow and anything above it is rejected
+    let master_key =
+      keys::ExtendedKey::new_master(&[0x11; 32], &[0x22; 32]).expect("can not create master key");
+    let child = master_key
+      .derive_child(qr2m_lib::ChildNumber::new(2147483647, true).expect("invalid child index"))
+      .expect("can not derive highest hardened child");
+    assert_eq!(child.child_number, u32::MAX);
+    assert!(qr2m_lib::ChildNumber::new(2147483648, true).is_err());
+  }
+
+  fn derive_master_keys_from_mnemonic(mnemonic: &str, passphrase: &str) -> (Vec<u8>, Vec<u8>) {
+    let seed = keys::generate_seed_from_mnemonic(mnemonic, passphrase)
+      .expect("can not generate seed from mnemonic");
+    let master_keys = qr2m_lib::calculate_hmac_sha512_hash(b"Bitcoin seed", &seed);
+
+    (master_keys[..32].to_vec(), master_keys[32..].to_vec())
+  }
+
+  fn generate_address_from_mnemonic(vector: &_MnemonicAddressVector) -> keys::Address {
+    let (master_private_key_bytes, master_chain_code_bytes) =
+      derive_master_keys_from_mnemonic(vector.mnemonic, vector.passphrase);
+
+    let magic_ingredients = keys::AddressHocusPokus {
+      coin_index: vector.coin_index,
+      derivation_path: vector.derivation_path.to_string(),
+      master_private_key_bytes: master_private_key_bytes.into(),
+      master_chain_code_bytes: master_chain_code_bytes.into(),
+      master_public_key_bytes: Vec::new(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: vector.public_key_hash.to_string(),
+      script_hash: vector.script_hash.to_string(),
+      bech32_hrp: vector.bech32_hrp.to_string(),
+      key_derivation: "secp256k1".to_string(),
+      wallet_import_format: vector.wallet_import_format.to_string(),
+      hash: "sha256".to_string(),
+      chain_id: None,
+      eip1191: false,
+      bip: vector.bip,
+      compressed: true,
+    };
+
+    match keys::generate_address(magic_ingredients) {
+      Ok(Some(address)) => address,
+      _ => panic!("Error generating address for {}", vector.derivation_path),
+    }
+  }
+
+  #[test]
+  fn test_bip84_native_segwit_addresses() {
+    let test_vectors = vec![
+      _MnemonicAddressVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        passphrase: "",
+        coin_index: 0,
+        bip: 84,
+        derivation_path: "m/84'/0'/0'/0/0",
+        public_key_hash: "0x00",
+        script_hash: "0x05",
+        bech32_hrp: "bc",
+        wallet_import_format: "0x80",
+        expected_address: "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+        expected_public_key: "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+        expected_private_key: "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d",
+      },
+      _MnemonicAddressVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        passphrase: "",
+        coin_index: 0,
+        bip: 84,
+        derivation_path: "m/84'/0'/0'/0/1",
+        public_key_hash: "0x00",
+        script_hash: "0x05",
+        bech32_hrp: "bc",
+        wallet_import_format: "0x80",
+        expected_address: "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+        expected_public_key: "03e775fd51f0dfb8cd865d9ff1cca2a158cf651fe997fdc9fee9c1d3b5e995ea77",
+        expected_private_key: "Kxpf5b8p3qX56DKEe5NqWbNUP9MnqoRFzZwHRtsFqhzuvUJsYZCy",
+      },
+      _MnemonicAddressVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        passphrase: "",
+        coin_index: 0,
+        bip: 84,
+        derivation_path: "m/84'/0'/0'/1/0",
+        public_key_hash: "0x00",
+        script_hash: "0x05",
+        bech32_hrp: "bc",
+        wallet_import_format: "0x80",
+        expected_address: "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+        expected_public_key: "03025324888e429ab8e3dbaf1f7802648b9cd01e9b418485c5fa4c1b9b5700e1a6",
+        expected_private_key: "KxuoxufJL5csa1Wieb2kp29VNdn92Us8CoaUG3aGtPtcF3AzeXvF",
+      },
+    ];
+
+    for vector in test_vectors {
+      let address = generate_address_from_mnemonic(&vector);
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+    }
+  }
+
+  #[test]
+  fn test_bip49_wrapped_segwit_addresses() {
+    let test_vectors = vec![
+      _MnemonicAddressVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        passphrase: "",
+        coin_index: 1,
+        bip: 49,
+        derivation_path: "m/49'/1'/0'/0/0",
+        public_key_hash: "0x6f",
+        script_hash: "0xc4",
+        bech32_hrp: "tb",
+        wallet_import_format: "0xef",
+        expected_address: "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2",
+        expected_public_key: "03a1af804ac108a8a51782198c2d034b28bf90c8803f5a53f76276fa69a4eae77f",
+        expected_private_key: "cULrpoZGXiuC19Uhvykx7NugygA3k86b3hmdCeyvHYQZSxojGyXJ",
+      },
+      _MnemonicAddressVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        passphrase: "",
+        coin_index: 0,
+        bip: 49,
+        derivation_path: "m/49'/0'/0'/0/0",
+        public_key_hash: "0x00",
+        script_hash: "0x05",
+        bech32_hrp: "bc",
+        wallet_import_format: "0x80",
+        expected_address: "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+        expected_public_key: "039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24",
+        expected_private_key: "KyvHbRLNXfXaHuZb3QRaeqA5wovkjg4RuUpFGCxdH5UWc1Foih9o",
+      },
+    ];
+
+    for vector in test_vectors {
+      let address = generate_address_from_mnemonic(&vector);
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+    }
+  }
+
+  #[test]
+  fn test_bip86_taproot_addresses() {
+    let test_vectors = vec![
+      _TaprootVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        derivation_path: "m/86'/0'/0'/0/0",
+        expected_internal_key: "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+        expected_output_key: "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+        expected_address: "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+      },
+      _TaprootVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        derivation_path: "m/86'/0'/0'/0/1",
+        expected_internal_key: "83dfe85a3151d2517290da461fe2815591ef69f2b18a2ce63f01697a8b313145",
+        expected_output_key: "a82f29944d65b86ae6b5e5cc75e294ead6c59391a1edc5e016e3498c67fc7bbb",
+        expected_address: "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+      },
+      _TaprootVector {
+        mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        derivation_path: "m/86'/0'/0'/1/0",
+        expected_internal_key: "399f1b2f4393f29a18c937859c5dd8a77350103157eb880f02e8c08214277cef",
+        expected_output_key: "882d74e5d0572d5a816cef0041a96b6c1de832f6f9676d9605c44d5e9a97d3dc",
+        expected_address: "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7",
+      },
+    ];
+
+    for vector in test_vectors {
+      let (master_private_key_bytes, master_chain_code_bytes) =
+        derive_master_keys_from_mnemonic(vector.mnemonic, "");
+
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: 0,
+        derivation_path: vector.derivation_path.to_string(),
+        master_private_key_bytes: master_private_key_bytes.into(),
+        master_chain_code_bytes: master_chain_code_bytes.into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: "0x00".to_string(),
+        script_hash: "0x05".to_string(),
+        bech32_hrp: "bc".to_string(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: "0x80".to_string(),
+        hash: "sha256".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 86,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating address for {}", vector.derivation_path),
+      };
+
+      let internal_key_bytes: [u8; 32] = hex::decode(&address.public_key)
+        .expect("can not decode internal key")
+        .try_into()
+        .expect("internal key must be 32 bytes");
+      let internal_key = secp256k1::XOnlyPublicKey::from_byte_array(internal_key_bytes)
+        .expect("invalid internal key");
+      let output_key =
+        keys::calculate_taproot_output_key(&internal_key).expect("can not tweak internal key");
+
+      assert_eq!(address.public_key, vector.expected_internal_key);
+      assert_eq!(
+        hex::encode(output_key.serialize()),
+        vector.expected_output_key
+      );
+      assert_eq!(address.address, vector.expected_address);
+    }
+  }
+
+  #[test]
+  fn test_extended_key_serialization() {
+    let test_vectors = vec![
+      _ExtendedKeyVector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m",
+        expected_xprv: "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        expected_xpub: "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+      },
+      _ExtendedKeyVector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'",
+        expected_xprv: "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+        expected_xpub: "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+      },
+      _ExtendedKeyVector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1",
+        expected_xprv: "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+        expected_xpub: "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+      },
+      _ExtendedKeyVector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1/2'",
+        expected_xprv: "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+        expected_xpub: "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+      },
+      _ExtendedKeyVector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1/2'/2/1000000000",
+        expected_xprv: "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+        expected_xpub: "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+      },
+    ];
+
+    for vector in test_vectors {
+      let seed = hex::decode(vector.seed).expect("can not decode seed");
+      let master_keys = qr2m_lib::calculate_hmac_sha512_hash(b"Bitcoin seed", &seed);
+
+      let master_node = keys::ExtendedKey::new_master(&master_keys[..32], &master_keys[32..])
+        .expect("can not create master node");
+      let derivation_path =
+        keys::parse_derivation_path(vector.derivation_path).expect("can not parse path");
+      let node = keys::derive_extended_key_from_path(&master_node, &derivation_path)
+        .expect("can not derive extended key");
+
+      assert_eq!(
+        node
+          .serialize_private(0x0488ADE4)
+          .expect("can not serialize xprv"),
+        vector.expected_xprv
+      );
+      assert_eq!(node.serialize_public(0x0488B21E), vector.expected_xpub);
+    }
+  }
+
+  #[test]
+  fn test_public_child_key_derivation() {
+    let test_vectors = vec![
+      _PublicDerivationVector {
+        parent_xpub: "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+        index: 1,
+        expected_xpub: "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+      },
+      _PublicDerivationVector {
+        parent_xpub: "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+        index: 2,
+        expected_xpub: "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+      },
+      _PublicDerivationVector {
+        parent_xpub: "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+        index: 1000000000,
+        expected_xpub: "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+      },
+    ];
+
+    for vector in test_vectors {
+      let (header, parent) =
+        keys::decode_extended_key(vector.parent_xpub).expect("can not decode extended public key");
+
+      assert_eq!(parent.serialize_public(header), vector.parent_xpub);
+
+      let child = parent
+        .derive_child(qr2m_lib::ChildNumber::new(vector.index, false).expect("invalid child index"))
+        .expect("can not derive public child key");
+
+      assert!(child.private_key.is_none());
+      assert_eq!(child.serialize_public(header), vector.expected_xpub);
+      assert!(
+        parent
+          .derive_child(
+            qr2m_lib::ChildNumber::new(vector.index, true).expect("invalid child index")
+          )
+          .is_err()
+      );
+    }
+  }
+
+  #[test]
+  fn test_watch_only_addresses_from_account_xpub() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let (master_private_key_bytes, master_chain_code_bytes) =
+      derive_master_keys_from_mnemonic(mnemonic, "");
+
+    let master_node =
+      keys::ExtendedKey::new_master(&master_private_key_bytes, &master_chain_code_bytes)
+        .expect("can not create master node");
+    let account_path = keys::parse_derivation_path("m/84'/0'/0'").expect("can not parse path");
+    let account_node = keys::derive_extended_key_from_path(&master_node, &account_path)
+      .expect("can not derive account node")
+      .neuter();
+
+    let (header, account_node) =
+      keys::decode_extended_key(&account_node.serialize_public(0x04B24746))
+        .expect("can not decode account zpub");
+
+    assert_eq!(
+      keys::get_slip132_extended_key_header(header),
+      Some((0x04B2430C, 0x04B24746, 0, Some(84)))
+    );
+    assert_eq!(
+      keys::get_slip132_extended_key_header(0x049D7CB2),
+      Some((0x049D7878, 0x049D7CB2, 0, Some(49)))
+    );
+    assert_eq!(
+      keys::get_slip132_extended_key_header(0x045F18BC),
+      Some((0x045F18BC, 0x045F1CF6, 1, Some(84)))
+    );
+    assert_eq!(
+      keys::get_slip132_extended_key_header(0x0488B21E),
+      Some((0x0488ADE4, 0x0488B21E, 0, None))
+    );
+    assert_eq!(keys::get_slip132_extended_key_header(0x019DA462), None);
+
+    let test_vectors = vec![
+      (
+        "m/84'/0'/0'/0/0",
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+      ),
+      (
+        "m/84'/0'/0'/0/1",
+        "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+      ),
+      (
+        "m/84'/0'/0'/1/0",
+        "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+      ),
+    ];
+
+    for (derivation_path, expected_address) in test_vectors {
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: 0,
+        derivation_path: derivation_path.to_string(),
+        master_private_key_bytes: Default::default(),
+        master_chain_code_bytes: account_node.chain_code.into(),
+        master_public_key_bytes: account_node.public_key.to_vec(),
+        master_depth: account_node.depth,
+        master_child_number: account_node.child_number,
+        public_key_hash: "0x00".to_string(),
+        script_hash: "0x05".to_string(),
+        bech32_hrp: "bc".to_string(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: "0x80".to_string(),
+        hash: "sha256".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 84,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating watch-only address for {derivation_path}"),
+      };
+
+      assert_eq!(address.address, expected_address);
+      assert!(address.private_key.is_empty());
+    }
+
+    for foreign_path in ["m/84'/0'/1'/0/0", "m/84'/0'", "m/84'/0'/0"] {
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: 0,
+        derivation_path: foreign_path.to_string(),
+        master_private_key_bytes: Default::default(),
+        master_chain_code_bytes: account_node.chain_code.into(),
+        master_public_key_bytes: account_node.public_key.to_vec(),
+        master_depth: account_node.depth,
+        master_child_number: account_node.child_number,
+        public_key_hash: "0x00".to_string(),
+        script_hash: "0x05".to_string(),
+        bech32_hrp: "bc".to_string(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: "0x80".to_string(),
+        hash: "sha256".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 84,
+        compressed: true,
+      };
+
+      assert!(
+        keys::generate_address(magic_ingredients).is_err(),
+        "{foreign_path} is not below the imported account key"
+      );
+    }
+  }
+
+  #[test]
+  fn test_extended_key_import() {
+    let test_vectors = vec![
+      _ExtendedKeyImportVector {
+        extended_key: "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+        expected_depth: 2,
+        expected_parent_fingerprint: "5c1bd648",
+        expected_child_number: 1,
+        expected_xpub: "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+      },
+      _ExtendedKeyImportVector {
+        extended_key: "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+        expected_depth: 3,
+        expected_parent_fingerprint: "bef5a2f9",
+        expected_child_number: 0x80000002,
+        expected_xpub: "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+      },
+    ];
+
+    for vector in test_vectors {
+      let (header, node) =
+        keys::decode_extended_key(vector.extended_key).expect("can not decode extended key");
+
+      assert_eq!(node.depth, vector.expected_depth);
+      assert_eq!(
+        hex::encode(node.parent_fingerprint),
+        vector.expected_parent_fingerprint
+      );
+      assert_eq!(node.child_number, vector.expected_child_number);
+      assert_eq!(node.serialize_public(0x0488B21E), vector.expected_xpub);
+
+      match node.private_key {
+        Some(_) => assert_eq!(
+          node
+            .serialize_private(header)
+            .expect("can not serialize xprv"),
+          vector.extended_key
+        ),
+        None => assert_eq!(node.serialize_public(header), vector.extended_key),
+      }
+
+      let mut corrupted = vector.extended_key.to_string();
+      let last = corrupted.pop().unwrap_or_default();
+      corrupted.push(if last == 'a' { 'b' } else { 'a' });
+      assert!(keys::decode_extended_key(&corrupted).is_err());
+    }
+  }
+
+  #[test]
+  fn test_eip55_checksum_addresses() {
+    let mut test_vectors = Vec::new();
+
+    for expected_address in [
+      "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+      "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+      "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+      "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+      test_vectors.push(_Eip55Vector {
+        chain_id: None,
+        expected_address,
+      });
+    }
+
+    for expected_address in [
+      "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+      "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+      "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+      "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+    ] {
+      test_vectors.push(_Eip55Vector {
+        chain_id: Some(30),
+        expected_address,
+      });
+    }
+
+    for expected_address in [
+      "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+      "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359",
+      "0xdbF03B407C01E7cd3cbEa99509D93f8dDDc8C6fB",
+      "0xd1220a0CF47c7B9Be7A2E6Ba89f429762E7b9adB",
+    ] {
+      test_vectors.push(_Eip55Vector {
+        chain_id: Some(31),
+        expected_address,
+      });
+    }
+
+    for vector in test_vectors {
+      let address_bytes =
+        hex::decode(&vector.expected_address[2..]).expect("can not decode address hex");
+
+      assert_eq!(
+        keys::encode_eip55_address(&address_bytes, vector.chain_id),
+        vector.expected_address
+      );
+      assert!(
+        keys::validate_eip55_address(vector.expected_address, vector.chain_id)
+          .expect("can not validate checksum address")
+      );
+      assert!(
+        !keys::validate_eip55_address(&vector.expected_address.to_lowercase(), vector.chain_id)
+          .expect("can not validate lowercase address")
+      );
+
+      let swapped_case: String = vector
+        .expected_address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match i {
+          0 | 1 => c,
+          _ if c.is_ascii_uppercase() => c.to_ascii_lowercase(),
+          _ => c.to_ascii_uppercase(),
+        })
+        .collect();
+      assert!(keys::validate_eip55_address(&swapped_case, vector.chain_id).is_err());
+    }
+
+    // Chain IDs come from ECDB, only chains that adopted EIP-1191 put them into the checksum
+    let coin_database = qr2m_lib::get_text_from_resources("coin/ECDB.csv");
+    let mut rows = coin_database
+      .lines()
+      .map(|line| line.split(',').collect::<Vec<_>>());
+    let header = rows.next().expect("ECDB is empty");
+    assert_eq!(header[12], "evm");
+    assert_eq!(header[15], "chain_id");
+    assert_eq!(header[16], "eip1191");
+
+    let (master_private_key_bytes, master_chain_code_bytes) = derive_master_keys_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    );
+
+    for row in rows {
+      assert_eq!(row.len(), header.len(), "{}", row.join(","));
+
+      let chain_id = row[15].parse::<u64>().ok();
+      let eip1191 = row[16] == "TRUE";
+      let expected = match row[1] {
+        "60" => (Some(1), false),
+        "61" => (Some(61), false),
+        "137" => (Some(30), true),
+        _ => continue,
+      };
+      assert_eq!((chain_id, eip1191), expected);
+      assert_eq!(row[12], "TRUE");
+
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: row[1].parse().expect("invalid coin index"),
+        derivation_path: format!("m/44'/{}'/0'/0/0", row[1]),
+        master_private_key_bytes: master_private_key_bytes.clone().into(),
+        master_chain_code_bytes: master_chain_code_bytes.clone().into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: String::new(),
+        script_hash: String::new(),
+        bech32_hrp: String::new(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: String::new(),
+        hash: "keccak256".to_string(),
+        chain_id,
+        eip1191,
+        bip: 44,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address.address,
+        _ => panic!("Error generating address for coin {}", row[1]),
+      };
+
+      assert!(
+        keys::validate_eip55_address(&address, chain_id.filter(|_| eip1191))
+          .expect("generated address has an invalid checksum")
+      );
+      assert_eq!(
+        keys::validate_eip55_address(&address, None).is_ok(),
+        !eip1191,
+        "{address}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_uncompressed_legacy_addresses() {
+    let test_vectors = vec![_MnemonicAddressVector {
+      mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      passphrase: "",
+      coin_index: 0,
+      bip: 44,
+      derivation_path: "m/44'/0'/0'/0/0",
+      public_key_hash: "0x00",
+      script_hash: "0x05",
+      bech32_hrp: "bc",
+      wallet_import_format: "0x80",
+      expected_address: "18LhnLKXjcTw5xJFiTxntnKit2Gd63eWFm",
+      expected_public_key: "04aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5e9370164133294e5fd1679672fe7866c307daf97281a28f66dca7cbb52919824f",
+      expected_private_key: "5KY3dHRWNnFkBJaTnmUTaR1oqs9tU9goQbG19FSNLSo5oAxLokG",
+    }];
+
+    for vector in test_vectors {
+      let (master_private_key_bytes, master_chain_code_bytes) =
+        derive_master_keys_from_mnemonic(vector.mnemonic, vector.passphrase);
+
+      let magic_ingredients = |bip: u32| keys::AddressHocusPokus {
+        coin_index: vector.coin_index,
+        derivation_path: vector.derivation_path.to_string(),
+        master_private_key_bytes: master_private_key_bytes.clone().into(),
+        master_chain_code_bytes: master_chain_code_bytes.clone().into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: vector.public_key_hash.to_string(),
+        script_hash: vector.script_hash.to_string(),
+        bech32_hrp: vector.bech32_hrp.to_string(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: vector.wallet_import_format.to_string(),
+        hash: "sha256".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip,
+        compressed: false,
+      };
+
+      let address = match keys::generate_address(magic_ingredients(vector.bip)) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating address for {}", vector.derivation_path),
+      };
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+      assert!(keys::generate_address(magic_ingredients(84)).is_err());
+    }
+  }
+
+  #[test]
+  fn test_slip10_ed25519_derivation() {
+    let test_vectors = vec![
+      _Slip10Vector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m",
+        expected_chain_code: "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+        expected_private_key: "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+        expected_public_key: "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+      },
+      _Slip10Vector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'",
+        expected_chain_code: "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+        expected_private_key: "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        expected_public_key: "008c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+      },
+      _Slip10Vector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1'",
+        expected_chain_code: "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+        expected_private_key: "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        expected_public_key: "001932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+      },
+      _Slip10Vector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1'/2'",
+        expected_chain_code: "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+        expected_private_key: "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+        expected_public_key: "00ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+      },
+      _Slip10Vector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1'/2'/2'",
+        expected_chain_code: "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+        expected_private_key: "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+        expected_public_key: "008abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+      },
+      _Slip10Vector {
+        seed: "000102030405060708090a0b0c0d0e0f",
+        derivation_path: "m/0'/1'/2'/2'/1000000000'",
+        expected_chain_code: "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+        expected_private_key: "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+        expected_public_key: "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+      },
+      _Slip10Vector {
+        seed: "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        derivation_path: "m",
+        expected_chain_code: "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b",
+        expected_private_key: "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012",
+        expected_public_key: "008fe9693f8fa62a4305a140b9764c5ee01e455963744fe18204b4fb948249308a",
+      },
+      _Slip10Vector {
+        seed: "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        derivation_path: "m/0'",
+        expected_chain_code: "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d",
+        expected_private_key: "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635",
+        expected_public_key: "0086fab68dcb57aa196c77c5f264f215a112c22a912c10d123b0d03c3c28ef1037",
+      },
+      _Slip10Vector {
+        seed: "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        derivation_path: "m/0'/2147483647'",
+        expected_chain_code: "138f0b2551bcafeca6ff2aa88ba8ed0ed8de070841f0c4ef0165df8181eaad7f",
+        expected_private_key: "ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4",
+        expected_public_key: "005ba3b9ac6e90e83effcd25ac4e58a1365a9e35a3d3ae5eb07b9e4d90bcf7506d",
+      },
+      _Slip10Vector {
+        seed: "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        derivation_path: "m/0'/2147483647'/1'",
+        expected_chain_code: "73bd9fff1cfbde33a1b846c27085f711c0fe2d66fd32e139d3ebc28e5a4a6b90",
+        expected_private_key: "3757c7577170179c7868353ada796c839135b3d30554bbb74a4b1e4a5a58505c",
+        expected_public_key: "002e66aa57069c86cc18249aecf5cb5a9cebbfd6fadeab056254763874a9352b45",
+      },
+      _Slip10Vector {
+        seed: "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        derivation_path: "m/0'/2147483647'/1'/2147483646'",
+        expected_chain_code: "0902fe8a29f9140480a00ef244bd183e8a13288e4412d8389d140aac1794825a",
+        expected_private_key: "5837736c89570de861ebc173b1086da4f505d4adb387c6a1b1342d5e4ac9ec72",
+        expected_public_key: "00e33c0f7d81d843c572275f287498e8d408654fdf0d1e065b84e2e6f157aab09b",
+      },
+      _Slip10Vector {
+        seed: "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        derivation_path: "m/0'/2147483647'/1'/2147483646'/2'",
+        expected_chain_code: "5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4",
+        expected_private_key: "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d",
+        expected_public_key: "0047150c75db263559a70d5778bf36abbab30fb061ad69f69ece61a72b0cfa4fc0",
+      },
+    ];
+
+    for vector in test_vectors {
+      let seed_bytes = hex::decode(vector.seed).expect("can not decode seed");
+      let (master_private_key, master_chain_code, _) =
+        keys::derive_master_key_ed25519(&seed_bytes).expect("can not derive master key");
+
+      let derivation_path =
+        keys::parse_derivation_path(vector.derivation_path).expect("can not parse path");
+      let (private_key, chain_code, public_key) =
+        keys::derive_from_path_ed25519(&master_private_key, &master_chain_code, &derivation_path)
+          .expect("can not derive ed25519 path")
+          .expect("no ed25519 derivation result");
+
+      assert_eq!(hex::encode(chain_code), vector.expected_chain_code);
+      assert_eq!(hex::encode(private_key), vector.expected_private_key);
+      assert_eq!(
+        format!("00{}", hex::encode(public_key)),
+        vector.expected_public_key
+      );
+      assert!(
+        keys::derive_child_key_ed25519(
+          &private_key,
+          &chain_code,
+          qr2m_lib::ChildNumber::new(0, false).expect("invalid child index")
+        )
+        .is_err()
+      );
+
+      let unhardened_path = derivation_path
+        .child(qr2m_lib::ChildNumber::new(0, false).expect("can not create unhardened child"));
+      assert!(
+        keys::derive_from_path_ed25519(&master_private_key, &master_chain_code, &unhardened_path)
+          .is_err()
+      );
+    }
+  }
+
+  #[test]
+  fn test_blake2b_addresses() {
+    let test_vectors = vec![_Blake2bAddressVector {
+      mnemonic: "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur",
+      passphrase: "some password",
+      coin_index: 165,
+      key_derivation: "ed25519-blake2b",
+      derivation_path: "m/44'/165'/0'",
+      expected_address: "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d",
+      expected_public_key: "5B65B0E8173EE0802C2C3E6C9080D1A16B06DE1176C938A924F58670904E82C4",
+      expected_private_key: "3BE4FC2EF3F3B7374E6FC4FB6E7BB153F8A2998B3B3DAB50853EABE128024143",
+    }];
+
+    for vector in test_vectors {
+      let (master_private_key_bytes, master_chain_code_bytes) = match vector.key_derivation {
+        "secp256k1" => derive_master_keys_from_mnemonic(vector.mnemonic, vector.passphrase),
+        _ => {
+          let seed = keys::generate_seed_from_mnemonic(vector.mnemonic, vector.passphrase)
+            .expect("can not generate seed from mnemonic");
+          let (private_key, chain_code, _) =
+            keys::derive_master_key_ed25519(&seed).expect("can not derive master key");
+
+          (private_key.to_vec(), chain_code.to_vec())
+        }
+      };
+
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: vector.coin_index,
+        derivation_path: vector.derivation_path.to_string(),
+        master_private_key_bytes: master_private_key_bytes.into(),
+        master_chain_code_bytes: master_chain_code_bytes.into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: String::new(),
+        script_hash: String::new(),
+        bech32_hrp: String::new(),
+        key_derivation: vector.key_derivation.to_string(),
+        wallet_import_format: String::new(),
+        hash: "blake2b".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 44,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating address for {}", vector.derivation_path),
+      };
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+    }
+
+    // Filecoin secp256k1 vectors from filecoin-project/ref-fvm address_test.rs
+    let filecoin_vectors = [
+      (
+        "049402fac37e6432a416a3a0ca5426b5185ab3b24f6134efa25ce487c82d2e4e13bf452511e0d2245421f8613bc10d72fa216666a96c3bc13920d3ff233fd0bc05",
+        "f15ihq5ibzwki2b4ep2f46avlkrqzhpqgtga7pdrq",
+      ),
+      (
+        "047687b910379bf28cbe3aea674b12000c6b7dba46ffc05f6c94fe2a22bbcc2602ff7f5c76f21ca55d36959152b0e1e887917c393576eef093f61ebd3ad06f7fda",
+        "f12fiakbhe2gwd5cnmrenekasyn6v5tnaxaqizq6a",
+      ),
+      (
+        "04defdd01001efb86e01ded5ce34f847a73a14819ee641bcb60bb92993596f05dc2d605f2985f8d125812dac4163a396349b23c11cc2ff359de54be287ea62319b",
+        "f1wbxhu3ypkuo6eyp6hjx6davuelxaxrvwb2kuwva",
+      ),
+      (
+        "0403ed12c814b6b10d2ee09d95b4688db2d180d0a9a37a6b6a7db63d29811ee9730479d8ef9139e91249cabd393291cfe5d277ba76de45e3e085a37681bf3645d2",
+        "f1xtwapqc6nh4si2hcwpr3656iotzmlwumogqbuaa",
+      ),
+      (
+        "04f796819a8e271631af7c189797b545d60225936147e6010e6562b3ce9efe8b10144161a91ed0b4ec890800253fa6fc20ac90fbf1fbf27130a4ecc3e403b70576",
+        "f1xcbgdhkgkwht3hrrnui3jdopeejsoatkzmoltqy",
+      ),
+      (
+        "0442832bf87cce9ea345b90350de7d3495859ca449059c5e88dde74285dffb9ec01ebabc5fc86268cfeaeba7ae05bfb8d68eb75a5268782cf86fc8702bef8a1fe0",
+        "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy",
+      ),
+    ];
+
+    for (public_key_hex, expected_address) in filecoin_vectors {
+      let public_key_bytes = hex::decode(public_key_hex).expect("can not decode public key");
+      let public_key = keys::CryptoPublicKey::Secp256k1(
+        secp256k1::PublicKey::from_slice(&public_key_bytes).expect("invalid public key"),
+      );
+
+      assert_eq!(
+        keys::generate_address_filecoin(&public_key).expect("can not generate filecoin address"),
+        expected_address
+      );
+    }
+
+    let (master_private_key_bytes, master_chain_code_bytes) = derive_master_keys_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    );
+
+    for coin_index in [309, 595, 1991] {
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index,
+        derivation_path: format!("m/44'/{coin_index}'/0'/0/0"),
+        master_private_key_bytes: master_private_key_bytes.clone().into(),
+        master_chain_code_bytes: master_chain_code_bytes.clone().into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: String::new(),
+        script_hash: String::new(),
+        bech32_hrp: String::new(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: String::new(),
+        hash: "blake2b".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 44,
+        compressed: true,
+      };
+
+      assert!(
+        keys::generate_address(magic_ingredients).is_err(),
+        "blake2b coin {coin_index} must not fall back to another encoder"
+      );
+    }
+
+    assert_eq!(
+      qr2m_lib::encode_base32(b"foobar", b"abcdefghijklmnopqrstuvwxyz234567", 0),
+      "mzxw6ytboi"
+    );
+  }
+
+  #[test]
+  fn test_cardano_icarus_addresses() {
+    let entropy = hex::decode("df9ed25ed146bf43336a5d7cf7395994").expect("can not decode entropy");
+    let root = keys::IcarusKey::new_master(&entropy, "").expect("can not derive icarus root");
+
+    assert_eq!(
+      root
+        .encode_private("root_xsk")
+        .expect("can not encode root key"),
+      "root_xsk1vzrzr76vqyqlavclduhawqvtae2pq8lk0424q7t8rzfjyhhp530zxv2fwq5a3pd4vdzqtu6s2zxdjhww8xg4qwcs7y5dqne5k7mz27p6rcaath83rl20nz0v9nwdaga9fkufjuucza8vmny8qpkzwstk5qh7s88m"
+    );
+
+    let test_vectors = vec![
+      _CardanoAddressVector {
+        derivation_path: "m/1852'/1815'/0'/0/0",
+        expected_address: "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3jcu5d8ps7zex2k2xt3uqxgjqnnj83ws8lhrn648jjxtwqfjkjv7",
+        expected_public_key: "73fea80d424276ad0978d4fe5310e8bc2d485f5f6bb3bf87612989f112ad5a7d",
+        expected_private_key: "addr_xsk1hqf6v2lvhfn5mr3fe6g8ac6n8a3z6s0p24mg6kre8jadxulp530y07wjp2ml0zcz8gk0xc7zy96qp2xxtr0arjq9038k9dhkw3k3cswawhs4fkjp00kwc4wd6fynyaz5zw8ssggs9974apatyhs4ltg4puw0nn7y",
+      },
+      _CardanoAddressVector {
+        derivation_path: "m/1852'/1815'/0'/0/1",
+        expected_address: "addr1qx7svwszky8gcmhrfza7a89z9u0dfzd3l7h23sqlc5yml7ejcu5d8ps7zex2k2xt3uqxgjqnnj83ws8lhrn648jjxtwqmk7v0s",
+        expected_public_key: "f626ab887eb5f40b502463ccf2ec5a7311676ee9e5d55c492059a366c0b4d4a1",
+        expected_private_key: "addr_xsk1up30yhwmkujfkjd2rvy99z7qg7mdpqd8n7vehsyvfq20zulp530weme6mjk3yuqkynms9tr23wqczk94npvnp4r8l2vrq36l8vudk8lh4vfx72yymwg9n7sfe2pmu6ut6qjsgf4wkcser0wesc290w9ujykv4t85",
+      },
+    ];
+
+    for vector in test_vectors {
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: 1815,
+        derivation_path: vector.derivation_path.to_string(),
+        master_private_key_bytes: root.private_key.as_slice().into(),
+        master_chain_code_bytes: root.chain_code.as_slice().into(),
+        master_public_key_bytes: root.public_key.to_vec(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: String::new(),
+        script_hash: String::new(),
+        bech32_hrp: String::new(),
+        key_derivation: "ed25519-bip32".to_string(),
+        wallet_import_format: String::new(),
+        hash: "blake2b".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 1852,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating address for {}", vector.derivation_path),
+      };
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+    }
+  }
+
+  #[test]
+  fn test_monero_primary_addresses() {
+    let (master_private_key_bytes, master_chain_code_bytes) = derive_master_keys_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    );
+
+    let test_vectors = vec![
+      _MoneroAddressVector {
+        derivation_path: "m/44'/128'/0'/0/0",
+        expected_address: "49vDbkSo7eve3J41sBdjvjaBUyz8qHohsQcGtRf63qEUTMBvmA45fpp5pSacMdSg7A3b71RejLzB8EkGbfjp5PELVF2N4Zn",
+        expected_private_view_key: "0f3fe25d0c6d4c94dde0c0bcc214b233e9c72927f813728b0f01f28f9d5e1201",
+        expected_public_view_key: "865cbfab852a1d1ccdfc7328e4dac90f78fc2154257d07522e9b79e637326dfa",
+        expected_public_key: "dae41d6b13568fdd71ec3d20c2f614c65fe819f36ca5da8d24df3bd89b2bad9d",
+        expected_private_key: "3b094ca7218f175e91fa2402b4ae239a2fe8262792a3e718533a1a357a1e4109",
+      },
+      _MoneroAddressVector {
+        derivation_path: "m/44'/128'/0'/0/1",
+        expected_address: "43zMkXAq58qSDPMgrQUmoJ95nZrBC8fxXfLFxzx3VNwUeVBDFAKFthSNYb6SdHunWJGGpDupzi8u4d26oVvLXyGw8wwC8A4",
+        expected_private_view_key: "b03771b866ae43b10fbed2b8cf26e271817233853e0b64b1a352ead596f99403",
+        expected_public_view_key: "1ce849605dcec580ce8debd2f52e575b4f2b8aee04ed1fd757e4540a52a77c46",
+        expected_public_key: "3e71e08018f3e696bdee877b8b36b530527a0c95aa5634e52c1970f1f5b5ebe0",
+        expected_private_key: "41c4e39ebb18019b66fdf75aa7fe5a754e9eac81392c08214384b95b4331bd0b",
+      },
+    ];
+
+    for vector in test_vectors {
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: 128,
+        derivation_path: vector.derivation_path.to_string(),
+        master_private_key_bytes: master_private_key_bytes.clone().into(),
+        master_chain_code_bytes: master_chain_code_bytes.clone().into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: "0x12".to_string(),
+        script_hash: String::new(),
+        bech32_hrp: String::new(),
+        key_derivation: "secp256k1-monero".to_string(),
+        wallet_import_format: String::new(),
+        hash: "cryptonote".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 44,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating address for {}", vector.derivation_path),
+      };
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+      assert_eq!(
+        address.private_view_key.as_deref(),
+        Some(vector.expected_private_view_key)
+      );
+      assert_eq!(
+        address
+          .mnemonic
+          .as_deref()
+          .map(|mnemonic| mnemonic.split(' ').count()),
+        Some(25)
+      );
+
+      let private_spend_key: [u8; 32] = hex::decode(vector.expected_private_key)
+        .expect("can not decode private spend key")
+        .try_into()
+        .expect("private spend key must be 32 bytes");
+      let monero_keys = keys::MoneroKeys::from_private_spend_key(&private_spend_key);
+
+      assert_eq!(
+        hex::encode(monero_keys.private_view_key),
+        vector.expected_private_view_key
+      );
+      assert_eq!(
+        hex::encode(monero_keys.public_view_key),
+        vector.expected_public_view_key
+      );
+      assert_eq!(monero_keys.primary_address(0x12), vector.expected_address);
+    }
+  }
+
+  #[test]
+  fn test_monero_mnemonic_checksum_word() {
+    let mnemonic = "sequence atlas unveil summon pebbles tuesday beer rudely snake rockets different fuselage woven tagged bested dented vegan hover rapid fawns obvious muppet randomly seasons randomly";
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+
+    assert_eq!(
+      keys::get_monero_checksum_word(&words[..24]).expect("can not calculate checksum word"),
+      words[24]
+    );
+    assert!(keys::encode_monero_mnemonic(&[0u8; 32], &words).is_err());
+
+    let private_spend_key: [u8; 32] =
+      hex::decode("b0ef6bd527b9b23b9ceef70dc8b4cd1ee83ca14541964e764ad23f5151204f0f")
+        .expect("can not decode private spend key")
+        .try_into()
+        .expect("private spend key must be 32 bytes");
+
+    assert_eq!(
+      keys::generate_monero_mnemonic(&private_spend_key).expect("can not generate mnemonic"),
+      mnemonic
+    );
+  }
+
+  #[test]
+  fn test_cosmos_bech32_addresses() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let test_vectors = vec![
+      _MnemonicAddressVector {
+        mnemonic,
+        passphrase: "",
+        coin_index: 118,
+        bip: 44,
+        derivation_path: "m/44'/118'/0'/0/0",
+        public_key_hash: "",
+        script_hash: "",
+        bech32_hrp: "cosmos",
+        wallet_import_format: "",
+        expected_address: "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4",
+        expected_public_key: "024f4e2ad99c34d60b9ba6283c9431a8418af8673212961f97a77b6377fcd05b62",
+        expected_private_key: "c4a48e2fce1481cd3294b4490f6678090ea98d3d0e5cd984558ab0968741b104",
+      },
+      _MnemonicAddressVector {
+        mnemonic,
+        passphrase: "",
+        coin_index: 118,
+        bip: 44,
+        derivation_path: "m/44'/118'/0'/0/1",
+        public_key_hash: "",
+        script_hash: "",
+        bech32_hrp: "cosmos",
+        wallet_import_format: "",
+        expected_address: "cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz",
+        expected_public_key: "03a9a0776157f1dee1fe2d65628747059a8796de9a379f3015c4dcf483f64840a6",
+        expected_private_key: "c9ba8e1818baf4ceb063420dcedc7a482056a1580e4dbe797af3484aff7b8651",
+      },
+      _MnemonicAddressVector {
+        mnemonic,
+        passphrase: "",
+        coin_index: 931,
+        bip: 44,
+        derivation_path: "m/44'/931'/0'/0/0",
+        public_key_hash: "",
+        script_hash: "",
+        bech32_hrp: "thor",
+        wallet_import_format: "",
+        expected_address: "thor1gm00vwsfcp48enm4uv9e5dhm37jtd0ye27wrx0",
+        expected_public_key: "02205c476a22d5fe10b74489db9479d0e36e25a32da393a771fcf12380136a451f",
+        expected_private_key: "cd48c8b23a5d619cb67b7a4886d25127acf2e8c023e42a1e9ae14c6194532aa9",
+      },
+    ];
+
+    for vector in test_vectors {
+      let (master_private_key_bytes, master_chain_code_bytes) =
+        derive_master_keys_from_mnemonic(vector.mnemonic, vector.passphrase);
+
+      let magic_ingredients = keys::AddressHocusPokus {
+        coin_index: vector.coin_index,
+        derivation_path: vector.derivation_path.to_string(),
+        master_private_key_bytes: master_private_key_bytes.into(),
+        master_chain_code_bytes: master_chain_code_bytes.into(),
+        master_public_key_bytes: Vec::new(),
+        master_depth: 0,
+        master_child_number: 0,
+        public_key_hash: vector.public_key_hash.to_string(),
+        script_hash: vector.script_hash.to_string(),
+        bech32_hrp: vector.bech32_hrp.to_string(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: vector.wallet_import_format.to_string(),
+        hash: "sha256+ripemd160".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: vector.bip,
+        compressed: true,
+      };
+
+      let address = match keys::generate_address(magic_ingredients) {
+        Ok(Some(address)) => address,
+        _ => panic!("Error generating address for {}", vector.derivation_path),
+      };
+
+      assert_eq!(address.address, vector.expected_address);
+      assert_eq!(address.public_key, vector.expected_public_key);
+      assert_eq!(address.private_key.as_str(), vector.expected_private_key);
+    }
+  }
+
+  #[test]
+  fn test_derivation_path_parser() {
+    let valid_paths = vec![
+      ("m", "m", 0),
+      ("m/44'/0'/0'/0/0", "m/44'/0'/0'/0/0", 5),
+      ("m/44h/60H/0'/0/7", "m/44'/60'/0'/0/7", 5),
+      ("M/0/2147483647'", "m/0/2147483647'", 2),
+      (
+        "m/1852'/1815'/0'/2/0/1/2/3",
+        "m/1852'/1815'/0'/2/0/1/2/3",
+        8,
+      ),
+    ];
+
+    for (input, expected, depth) in valid_paths {
+      let path = keys::parse_derivation_path(input).expect("can not parse valid path");
+
+      assert_eq!(path.to_string(), expected);
+      assert_eq!(path.depth(), depth);
+      assert_eq!(
+        keys::parse_derivation_path(&path.to_string()).expect("can not parse displayed path"),
+        path
+      );
+    }
+
+    let path = keys::parse_derivation_path("m/44'/0'/0'/1/5").expect("can not parse path");
+    assert_eq!(path.children()[0].to_raw(), 0x8000002C);
+    assert_eq!(path.children()[4].to_raw(), 5);
+    assert_eq!(path.parent().to_string(), "m/44'/0'/0'/1");
+    assert_eq!(
+      path
+        .relative_to(3, 0x80000000)
+        .expect("can not strip account prefix")
+        .to_string(),
+      "m/1/5"
+    );
+    assert_eq!(
+      path.relative_to(0, 0).expect("can not keep full path"),
+      path
+    );
+    assert!(path.relative_to(3, 0x80000001).is_err());
+    assert!(path.relative_to(3, 0).is_err());
+    assert!(path.relative_to(6, 5).is_err());
+
+    let invalid_paths = vec![
+      "",
+      "/",
+      "m/",
+      "m//0",
+      "44'/0'",
+      "x/0",
+      "m/0''",
+      "m/-1",
+      "m/+1",
+      "m/0x10",
+      "m/2147483648",
+      "m/0/4294967295",
+      "m/2147483648'",
+      "m/2147483648h",
+      "m/4294967296",
+    ];
+
+    for input in invalid_paths {
+      assert!(
+        keys::parse_derivation_path(input).is_err(),
+        "path {input:?} should be rejected"
+      );
+    }
+  }
+
+  #[test]
+  fn test_cached_parent_node_derivation() {
+    let (master_private_key_bytes, master_chain_code_bytes) = derive_master_keys_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    );
+    let seed = keys::generate_seed_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    )
+    .expect("can not generate seed from mnemonic");
+    let (ed25519_private_key, ed25519_chain_code, ed25519_public_key) =
+      keys::derive_master_key_ed25519(&seed).expect("can not derive ed25519 master key");
+
+    let test_vectors = vec![
+      (
+        "secp256k1",
+        "sha256",
+        0,
+        "m/44'/0'/0'/0",
+        master_private_key_bytes.clone(),
+        master_chain_code_bytes.clone(),
+        Vec::new(),
+      ),
+      (
+        "secp256k1",
+        "sha256+ripemd160",
+        118,
+        "m/44'/118'/0'/0",
+        master_private_key_bytes.clone(),
+        master_chain_code_bytes.clone(),
+        Vec::new(),
+      ),
+      (
+        "ed25519",
+        "ed25519",
+        501,
+        "m/44'/501'/0'",
+        ed25519_private_key.to_vec(),
+        ed25519_chain_code.to_vec(),
+        ed25519_public_key,
+      ),
+    ];
+
+    for (key_derivation, hash, coin_index, parent_path, private_key, chain_code, public_key) in
+      test_vectors
+    {
+      let magic_ingredients =
+        |derivation_path: &str, node: &keys::DerivationNode| keys::AddressHocusPokus {
+          coin_index,
+          derivation_path: derivation_path.to_string(),
+          master_private_key_bytes: node.private_key.clone(),
+          master_chain_code_bytes: node.chain_code.clone(),
+          master_public_key_bytes: node.public_key.clone(),
+          master_depth: node.depth,
+          master_child_number: node.child_number,
+          public_key_hash: "0x00".to_string(),
+          script_hash: "0x05".to_string(),
+          bech32_hrp: if coin_index == 118 {
+            "cosmos".to_string()
+          } else {
+            String::new()
+          },
+          key_derivation: key_derivation.to_string(),
+          wallet_import_format: "0x80".to_string(),
+          hash: hash.to_string(),
+          chain_id: None,
+          eip1191: false,
+          bip: 44,
+          compressed: true,
+        };
+
+      let master_node = keys::DerivationNode::new(private_key, chain_code, public_key, 0, 0);
+      let parent_node = master_node
+        .derive(
+          key_derivation,
+          &keys::parse_derivation_path(parent_path).expect("can not parse path"),
+        )
+        .expect("can not derive parent node");
+
+      assert_eq!(
+        parent_node.depth as usize,
+        parent_path.split('/').count() - 1
+      );
+
+      // SLIP-10 Ed25519 children must be written hardened
+      let hardened = if key_derivation == "ed25519" { "'" } else { "" };
+
+      for index in 0..4 {
+        let derivation_path = format!("{parent_path}/{index}{hardened}");
+
+        let from_master = keys::generate_address(magic_ingredients(&derivation_path, &master_node))
+          .expect("can not generate address from master")
+          .expect("no address from master");
+        let from_parent = keys::generate_address(magic_ingredients(&derivation_path, &parent_node))
+          .expect("can not generate address from parent")
+          .expect("no address from parent");
+
+        assert_eq!(from_parent.address, from_master.address);
+        assert_eq!(from_parent.public_key, from_master.public_key);
+        assert_eq!(
+          from_parent.private_key.as_str(),
+          from_master.private_key.as_str()
+        );
+      }
+
+      if key_derivation == "ed25519" {
+        let unhardened_path = format!("{parent_path}/0");
+
+        assert!(keys::generate_address(magic_ingredients(&unhardened_path, &master_node)).is_err());
+        assert!(keys::generate_address(magic_ingredients(&unhardened_path, &parent_node)).is_err());
+      }
+
+      if key_derivation == "secp256k1" {
+        let mut foreign_public_key = parent_node.public_key.clone();
+        foreign_public_key[0] ^= 0x01;
+
+        assert!(
+          keys::derive_from_path_secp256k1(
+            &parent_node.private_key,
+            &parent_node.chain_code,
+            &foreign_public_key,
+            &keys::parse_derivation_path("m/0").expect("can not parse path"),
+          )
+          .is_err()
+        );
+      }
+    }
+  }
+
+  #[test]
+  #[ignore = "benchmark, run with: cargo test --release -- --ignored --nocapture benchmark"]
+  fn benchmark_secp256k1_address_generation() {
+    const ADDRESS_COUNT: u32 = 20_000;
+
+    let (master_private_key_bytes, master_chain_code_bytes) = derive_master_keys_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    );
+
+    let master_node = keys::DerivationNode::new(
+      master_private_key_bytes,
+      master_chain_code_bytes,
+      Vec::new(),
+      0,
+      0,
+    );
+    let parent_node = master_node
+      .derive(
+        "secp256k1",
+        &keys::parse_derivation_path("m/44'/0'/0'/0").expect("can not parse path"),
+      )
+      .expect("can not derive parent node");
+
+    let start = std::time::Instant::now();
+
+    for index in 0..ADDRESS_COUNT {
+      keys::generate_address(keys::AddressHocusPokus {
+        coin_index: 0,
+        derivation_path: format!("m/44'/0'/0'/0/{index}"),
+        master_private_key_bytes: parent_node.private_key.clone(),
+        master_chain_code_bytes: parent_node.chain_code.clone(),
+        master_public_key_bytes: parent_node.public_key.clone(),
+        master_depth: parent_node.depth,
+        master_child_number: parent_node.child_number,
+        public_key_hash: "0x00".to_string(),
+        script_hash: "0x05".to_string(),
+        bech32_hrp: "bc".to_string(),
+        key_derivation: "secp256k1".to_string(),
+        wallet_import_format: "0x80".to_string(),
+        hash: "sha256".to_string(),
+        chain_id: None,
+        eip1191: false,
+        bip: 44,
+        compressed: true,
+      })
+      .expect("can not generate address")
+      .expect("no address generated");
+    }
+
+    let elapsed = start.elapsed();
+
+    println!(
+      "{ADDRESS_COUNT} secp256k1 addresses in {elapsed:?} ({:.0} addresses per second per thread)",
+      ADDRESS_COUNT as f64 / elapsed.as_secs_f64()
+    );
+  }
+
+  #[test]
+  fn test_message_signatures() {
+    let test_vectors = vec![
+      _MessageSignatureVector {
+        address: "1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV",
+        private_key: "L4rK1yDtCWekvXuE6oXD9jCYfFNV2cWRpVuPLBcCU2z8TrisoyY1",
+        message: "This is an example of a signed message.",
+        expected_format: sign::MessageSignatureFormat::BitcoinLegacy,
+        expected_signature: "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=",
+      },
+      _MessageSignatureVector {
+        address: "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+        private_key: "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k",
+        message: "",
+        expected_format: sign::MessageSignatureFormat::Bip322Simple,
+        expected_signature: "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+      },
+      _MessageSignatureVector {
+        address: "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+        private_key: "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k",
+        message: "Hello World",
+        expected_format: sign::MessageSignatureFormat::Bip322Simple,
+        expected_signature: "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+      },
+      _MessageSignatureVector {
+        address: "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+        private_key: "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        message: "Some data",
+        expected_format: sign::MessageSignatureFormat::Eip191,
+        expected_signature: "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c",
+      },
+    ];
+
+    for vector in &test_vectors {
+      let address = keys::Address {
+        address: vector.address.to_string(),
+        public_key: String::new(),
+        private_key: vector.private_key.into(),
+        private_view_key: None,
+        mnemonic: None,
+      };
+
+      let (format, signature) =
+        sign::sign_message(&address, vector.message).expect("can not sign message");
+
+      assert_eq!(
+        format, vector.expected_format,
+        "Format mismatch for {}",
+        vector.address
+      );
+      assert_eq!(
+        signature, vector.expected_signature,
+        "Signature mismatch for {}",
+        vector.address
+      );
+      assert!(
+        sign::verify_message(vector.address, vector.message, vector.expected_signature)
+          .expect("can not verify message"),
+        "Valid signature rejected for {}",
+        vector.address
+      );
+      assert!(
+        !sign::verify_message(vector.address, "tampered", vector.expected_signature)
+          .expect("can not verify message"),
+        "Tampered message accepted for {}",
+        vector.address
+      );
+    }
+
+    assert!(
+      sign::verify_message(
+        "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3",
+        "Hello World",
+        "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==",
+      )
+      .expect("can not verify message"),
+      "Valid BIP322 taproot signature rejected"
+    );
+
+    let taproot_address = generate_address_from_mnemonic(&_MnemonicAddressVector {
+      mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      passphrase: "",
+      coin_index: 0,
+      bip: 86,
+      derivation_path: "m/86'/0'/0'/0/0",
+      public_key_hash: "0x00",
+      script_hash: "0x05",
+      bech32_hrp: "bc",
+      wallet_import_format: "0x80",
+      expected_address: "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+      expected_public_key: "",
+      expected_private_key: "",
+    });
+
+    let (format, signature) =
+      sign::sign_message(&taproot_address, "Hello World").expect("can not sign message");
+
+    assert_eq!(format, sign::MessageSignatureFormat::Bip322Simple);
+    assert!(
+      sign::verify_message(&taproot_address.address, "Hello World", &signature)
+        .expect("can not verify message")
+    );
+    assert!(
+      !sign::verify_message(&taproot_address.address, "Hello Word", &signature)
+        .expect("can not verify message")
+    );
+
+    let wrong_key = keys::Address {
+      address: "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l".to_string(),
+      public_key: String::new(),
+      private_key: "L4rK1yDtCWekvXuE6oXD9jCYfFNV2cWRpVuPLBcCU2z8TrisoyY1".into(),
+      private_view_key: None,
+      mnemonic: None,
+    };
+
+    assert!(sign::sign_message(&wrong_key, "Hello World").is_err());
+  }
+
+  #[test]
+  fn test_segwit_v0_sighash() {
+    let transaction = sign::Transaction::from_bytes(
+      &hex::decode(
+        "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000",
+      )
+      .expect("invalid transaction hex"),
+    )
+    .expect("can not parse transaction");
+
+    let script_code = hex::decode("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac")
+      .expect("invalid script hex");
+    let sighash = transaction.calculate_segwit_v0_sighash(1, &script_code, 600_000_000);
+
+    assert_eq!(
+      hex::encode(sighash),
+      "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+    );
+
+    let transaction = sign::Transaction::from_bytes(
+      &hex::decode(
+        "0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000",
+      )
+      .expect("invalid transaction hex"),
+    )
+    .expect("can not parse transaction");
+
+    let script_code = hex::decode("76a91479091972186c449eb1ded22b78e40d009bdf008988ac")
+      .expect("invalid script hex");
+    let sighash = transaction.calculate_segwit_v0_sighash(0, &script_code, 1_000_000_000);
+
+    assert_eq!(
+      hex::encode(sighash),
+      "64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6"
+    );
+  }
+
+  #[test]
+  fn test_psbt_signing() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let (master_private_key_bytes, master_chain_code_bytes) =
+      derive_master_keys_from_mnemonic(mnemonic, "");
+    let master_fingerprint = hex::decode("73c5da0a").expect("invalid fingerprint hex");
+
+    let mut signed_inputs = Vec::new();
+
+    for (bip, derivation_path) in [
+      (44, "m/44'/0'/0'/0/0"),
+      (49, "m/49'/0'/0'/0/0"),
+      (84, "m/84'/0'/0'/0/0"),
+      (86, "m/86'/0'/0'/0/0"),
+    ] {
+      let address = generate_address_from_mnemonic(&_MnemonicAddressVector {
+        mnemonic,
+        passphrase: "",
+        coin_index: 0,
+        bip,
+        derivation_path,
+        public_key_hash: "0x00",
+        script_hash: "0x05",
+        bech32_hrp: "bc",
+        wallet_import_format: "0x80",
+        expected_address: "",
+        expected_public_key: "",
+        expected_private_key: "",
+      });
+
+      let public_key = hex::decode(&address.public_key).expect("invalid public key hex");
+      let public_key_hash = qr2m_lib::calculate_sha256_and_ripemd160_hash(&public_key);
+
+      let mut key_origin = master_fingerprint.clone();
+      for child in keys::parse_derivation_path(derivation_path)
+        .expect("can not parse path")
+        .children()
+      {
+        key_origin.extend_from_slice(&child.to_raw().to_le_bytes());
+      }
+
+      let mut redeem_script = None;
+      let (script_pubkey, derivation_entry) = match bip {
+        44 => (
+          [&[0x76, 0xa9, 0x14][..], &public_key_hash, &[0x88, 0xac]].concat(),
+          ([&[0x06][..], &public_key].concat(), key_origin),
+        ),
+        49 => {
+          let script = [&[0x00, 0x14][..], &public_key_hash].concat();
+          let script_hash = qr2m_lib::calculate_sha256_and_ripemd160_hash(&script);
+          redeem_script = Some(script);
+          (
+            [&[0xa9, 0x14][..], &script_hash, &[0x87]].concat(),
+            ([&[0x06][..], &public_key].concat(), key_origin),
+          )
+        }
+        84 => (
+          [&[0x00, 0x14][..], &public_key_hash].concat(),
+          ([&[0x06][..], &public_key].concat(), key_origin),
+        ),
+        _ => {
+          let (_hrp, _version, program) =
+            bech32::segwit::decode(&address.address).expect("invalid taproot address");
+          (
+            [&[0x51, 0x20][..], &program].concat(),
+            (
+              [&[0x16][..], &public_key].concat(),
+              [&[0x00][..], &key_origin].concat(),
+            ),
+          )
+        }
+      };
+
+      signed_inputs.push((
+        sign::TxOutput {
+          value: 10_000 * bip as u64,
+          script_pubkey,
+        },
+        derivation_entry,
+        redeem_script,
+        public_key,
+      ));
+    }
+
+    let previous_transaction = sign::Transaction {
+      version: 2,
+      inputs: vec![sign::TxInput {
+        previous_txid: [0x11; 32],
+        previous_index: 0,
+        script_sig: vec![0x51],
+        sequence: 0xffff_ffff,
+        witness: Vec::new(),
+      }],
+      outputs: vec![signed_inputs[0].0.clone()],
+      lock_time: 0,
+    };
+
+    let transaction = sign::Transaction {
+      version: 2,
+      inputs: (0..signed_inputs.len())
+        .map(|index| sign::TxInput {
+          previous_txid: if index == 0 {
+            previous_transaction.txid()
+          } else {
+            [index as u8; 32]
+          },
+          previous_index: 0,
+          script_sig: Vec::new(),
+          sequence: 0xffff_fffd,
+          witness: Vec::new(),
+        })
+        .collect(),
+      outputs: vec![sign::TxOutput {
+        value: 1_000_000,
+        script_pubkey: signed_inputs[2].0.script_pubkey.clone(),
+      }],
+      lock_time: 0,
+    };
+
+    let mut psbt = sign::Psbt {
+      global: sign::PsbtMap {
+        entries: vec![(vec![0x00], transaction.to_bytes())],
+      },
+      inputs: Vec::new(),
+      outputs: vec![sign::PsbtMap::default()],
+      transaction,
+    };
+
+    for (index, (spent_output, derivation_entry, redeem_script, _)) in
+      signed_inputs.iter().enumerate()
+    {
+      let mut input = sign::PsbtMap::default();
+
+      if index == 0 {
+        input.insert(vec![0x00], previous_transaction.to_bytes());
+      } else {
+        let mut witness_utxo = spent_output.value.to_le_bytes().to_vec();
+        witness_utxo.push(spent_output.script_pubkey.len() as u8);
+        witness_utxo.extend_from_slice(&spent_output.script_pubkey);
+        input.insert(vec![0x01], witness_utxo);
+      }
+
+      if let Some(redeem_script) = redeem_script {
+        input.insert(vec![0x04], redeem_script.clone());
+      }
+
+      input.insert(derivation_entry.0.clone(), derivation_entry.1.clone());
+      input.insert(vec![0xfc, 0x01, 0x02], vec![0xde, 0xad]);
+      psbt.inputs.push(input);
+    }
+
+    let encoded = psbt.to_base64();
+    assert!(encoded.starts_with("cHNidP8B"));
+    assert_eq!(
+      sign::Psbt::from_base64(&encoded).expect("can not parse PSBT"),
+      psbt
+    );
+    assert_eq!(
+      sign::Psbt::from_file_contents(&psbt.to_bytes()).expect("can not parse PSBT file"),
+      psbt
+    );
+
+    let summary = psbt
+      .summarize(&[0x00], &[0x05], "bc")
+      .expect("can not summarize PSBT");
+    assert_eq!(
+      summary.outputs[0].address.as_deref(),
+      Some("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu")
+    );
+    assert_eq!(summary.outputs[0].value, 1_000_000);
+    assert_eq!(summary.fee, Some(1_630_000));
+    assert_eq!(summary.unverified_inputs, 3);
+
+    let mut unproven_psbt = psbt.clone();
+    let mut witness_utxo = signed_inputs[0].0.value.to_le_bytes().to_vec();
+    witness_utxo.push(signed_inputs[0].0.script_pubkey.len() as u8);
+    witness_utxo.extend_from_slice(&signed_inputs[0].0.script_pubkey);
+    unproven_psbt.inputs[0]
+      .entries
+      .retain(|(key, _)| *key != [0x00]);
+    unproven_psbt.inputs[0].insert(vec![0x01], witness_utxo);
+    assert_eq!(
+      sign::sign_psbt(
+        &mut unproven_psbt,
+        &master_private_key_bytes,
+        &master_chain_code_bytes
+      )
+      .expect("can not sign PSBT"),
+      3
+    );
+    assert_eq!(unproven_psbt.inputs[0].get_all(0x02).count(), 0);
+
+    let (other_private_key_bytes, other_chain_code_bytes) =
+      derive_master_keys_from_mnemonic(mnemonic, "TREZOR");
+    let mut foreign_psbt = psbt.clone();
+    assert_eq!(
+      sign::sign_psbt(
+        &mut foreign_psbt,
+        &other_private_key_bytes,
+        &other_chain_code_bytes
+      )
+      .expect("can not sign PSBT"),
+      0
+    );
+    assert_eq!(foreign_psbt, psbt);
+
+    assert_eq!(
+      sign::sign_psbt(
+        &mut psbt,
+        &master_private_key_bytes,
+        &master_chain_code_bytes
+      )
+      .expect("can not sign PSBT"),
+      4
+    );
+
+    let psbt = sign::Psbt::from_base64(&psbt.to_base64()).expect("can not parse signed PSBT");
+    let secp = keys::secp256k1_context();
+    let spent_outputs: Vec<_> = signed_inputs
+      .iter()
+      .map(|(spent_output, ..)| spent_output.clone())
+      .collect();
+
+    for (index, (spent_output, _, _, public_key)) in signed_inputs.iter().enumerate() {
+      let input = &psbt.inputs[index];
+      assert_eq!(input.get(0xfc), None);
+      assert!(
+        input
+          .entries
+          .contains(&(vec![0xfc, 0x01, 0x02], vec![0xde, 0xad]))
+      );
+
+      if index == 3 {
+        let signature = input.get(0x13).expect("missing taproot key signature");
+        let signature = secp256k1::schnorr::Signature::from_byte_array(
+          signature.try_into().expect("signature must be 64 bytes"),
+        );
+        let output_key = secp256k1::XOnlyPublicKey::from_byte_array(
+          spent_output.script_pubkey[2..]
+            .try_into()
+            .expect("output key must be 32 bytes"),
+        )
+        .expect("invalid output key");
+        let sighash = psbt
+          .transaction
+          .calculate_taproot_sighash(index, &spent_outputs, 0x00);
+
+        assert!(
+          secp
+            .verify_schnorr(&signature, &sighash, &output_key)
+            .is_ok()
+        );
+        continue;
+      }
+
+      let signature = input
+        .get_all(0x02)
+        .find(|(key, _)| *key == public_key.as_slice())
+        .map(|(_, value)| value)
+        .expect("missing partial signature");
+      let (&sighash_type, der_signature) = signature.split_last().expect("empty signature");
+      assert_eq!(sighash_type, 0x01);
+
+      let public_key_hash = qr2m_lib::calculate_sha256_and_ripemd160_hash(public_key);
+      let script_code = [&[0x76, 0xa9, 0x14][..], &public_key_hash, &[0x88, 0xac]].concat();
+      let sighash = if index == 0 {
+        psbt
+          .transaction
+          .calculate_legacy_sighash(index, &script_code)
+      } else {
+        psbt
+          .transaction
+          .calculate_segwit_v0_sighash(index, &script_code, spent_output.value)
+      };
+
+      assert!(
+        secp
+          .verify_ecdsa(
+            secp256k1::Message::from_digest(sighash),
+            &secp256k1::ecdsa::Signature::from_der(der_signature).expect("invalid DER"),
+            &secp256k1::PublicKey::from_slice(public_key).expect("invalid public key"),
+          )
+          .is_ok(),
+        "Invalid signature for input {index}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_eth_transaction_signing() {
+    // EIP-155 example transaction
+    let private_key =
+      hex::decode("4646464646464646464646464646464646464646464646464646464646464646")
+        .expect("invalid private key hex");
+    let expected_raw_transaction = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    let from_rlp = sign::EthTransaction::parse(
+      "0xec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080",
+    )
+    .expect("can not parse RLP transaction");
+    let from_json = sign::EthTransaction::parse(
+      r#"{"nonce": 9, "gasPrice": "20000000000", "gas": "0x5208", "to": "0x3535353535353535353535353535353535353535", "value": "0xde0b6b3a7640000", "chainId": 1}"#,
+    )
+    .expect("can not parse JSON transaction");
+
+    assert_eq!(from_rlp, from_json);
+    assert_eq!(
+      hex::encode(from_rlp.signing_hash()),
+      "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+    );
+
+    let signed = sign::sign_eth_transaction_with_key(&from_rlp, &private_key, 60, None)
+      .expect("can not sign transaction");
+    assert_eq!(signed.raw_transaction, expected_raw_transaction);
+    assert_eq!(signed.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+    // Typed transactions round trip through the decoder and recover the signer
+    for json in [
+      r#"{"type": "0x1", "chainId": "0x5", "nonce": "0x0", "gasPrice": "0x3b9aca00", "gas": "0x7530", "to": "0x3535353535353535353535353535353535353535", "value": "0x1", "data": "0xdeadbeef", "accessList": [{"address": "0x3535353535353535353535353535353535353535", "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]}]}"#,
+      r#"{"chainId": 1, "nonce": 3, "maxPriorityFeePerGas": "1500000000", "maxFeePerGas": "30000000000", "gas": 21000, "to": "0x9858EfFD232B4033E47d90003D41EC34EcaEda94", "value": "1000000000000000"}"#,
+    ] {
+      let transaction = sign::EthTransaction::parse(json).expect("can not parse JSON transaction");
+      let signed = sign::sign_eth_transaction_with_key(&transaction, &private_key, 60, None)
+        .expect("can not sign transaction");
+
+      let raw_transaction =
+        hex::decode(&signed.raw_transaction[2..]).expect("invalid raw transaction hex");
+      let sign::RlpItem::List(mut fields) =
+        sign::RlpItem::decode(&raw_transaction[1..]).expect("can not decode signed transaction")
+      else {
+        panic!("signed transaction is not an RLP list");
+      };
+
+      let [s, r, y_parity] = [fields.pop(), fields.pop(), fields.pop()].map(|field| match field {
+        Some(sign::RlpItem::Bytes(bytes)) => bytes,
+        _ => panic!("missing signature field"),
+      });
+
+      let mut unsigned = vec![raw_transaction[0]];
+      unsigned.extend_from_slice(&sign::RlpItem::List(fields).encode());
+      let decoded = sign::EthTransaction::from_rlp_hex(&hex::encode(&unsigned))
+        .expect("can not decode unsigned transaction");
+      assert_eq!(decoded, transaction);
+
+      let mut compact = [0u8; 64];
+      compact[32 - r.len()..32].copy_from_slice(&r);
+      compact[64 - s.len()..].copy_from_slice(&s);
+      let recovery_id =
+        secp256k1::ecdsa::RecoveryId::from_u8_masked(y_parity.first().copied().unwrap_or(0));
+      let signature = secp256k1::ecdsa::RecoverableSignature::from_compact(&compact, recovery_id)
+        .expect("invalid signature");
+      let public_key = keys::secp256k1_context()
+        .recover_ecdsa(
+          secp256k1::Message::from_digest(decoded.signing_hash()),
+          &signature,
+        )
+        .expect("can not recover signer");
+      let recovered = keys::generate_address_keccak256(
+        &keys::CryptoPublicKey::Secp256k1(public_key),
+        &[],
+        60,
+        None,
+      )
+      .expect("can not generate address");
+
+      assert_eq!(recovered, signed.from);
+    }
+
+    // Replay protection and checksum validation
+    assert!(
+      sign::EthTransaction::parse(
+        r#"{"nonce": 0, "gas": 21000, "to": "0x3535353535353535353535353535353535353535"}"#
+      )
+      .is_err()
+    );
+    assert!(
+      sign::EthTransaction::parse(
+        r#"{"chainId": 1, "gas": 21000, "to": "0x9858EfFD232B4033E47d90003D41EC34EcaEda95"}"#
+      )
+      .is_err()
+    );
+    assert!(
+      sign::EthTransaction::parse(
+        r#"{"chainId": 1, "gas": 21000, "to": "0x9858EFFD232B4033E47D90003D41EC34ECAEDA94"}"#
+      )
+      .is_ok()
+    );
+    assert!(sign::EthTransaction::parse(expected_raw_transaction).is_err());
+
+    // A present but non-string recipient or payload must not turn into a contract creation
+    for json in [
+      r#"{"chainId": 1, "gas": 53000, "to": 42}"#,
+      r#"{"chainId": 1, "gas": 53000, "to": ["0x3535353535353535353535353535353535353535"]}"#,
+      r#"{"chainId": 1, "gas": 53000, "to": "0x3535353535353535353535353535353535353535", "data": 1}"#,
+    ] {
+      assert!(sign::EthTransaction::parse(json).is_err());
+    }
+    for json in [
+      r#"{"chainId": 1, "gas": 53000, "to": null, "data": "0x6000"}"#,
+      r#"{"chainId": 1, "gas": 53000, "to": "", "data": "0x6000"}"#,
+      r#"{"chainId": 1, "gas": 53000, "data": "0x6000"}"#,
+    ] {
+      let transaction = sign::EthTransaction::parse(json).expect("can not parse JSON transaction");
+      assert_eq!(transaction.to, None);
+    }
+
+    // Key derived from the wallet master keys
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let (master_private_key_bytes, master_chain_code_bytes) =
+      derive_master_keys_from_mnemonic(mnemonic, "");
+    let magic_ingredients = keys::AddressHocusPokus {
+      coin_index: 60,
+      derivation_path: "m/44'/60'/0'/0/0".to_string(),
+      master_private_key_bytes: master_private_key_bytes.into(),
+      master_chain_code_bytes: master_chain_code_bytes.into(),
+      master_public_key_bytes: Vec::new(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: String::new(),
+      script_hash: String::new(),
+      bech32_hrp: String::new(),
+      key_derivation: "secp256k1".to_string(),
+      wallet_import_format: String::new(),
+      hash: "keccak256".to_string(),
+      chain_id: Some(1),
+      eip1191: false,
+      bip: 44,
+      compressed: true,
+    };
+    let signed = sign::sign_eth_transaction(&from_json, &magic_ingredients)
+      .expect("can not sign transaction with derived key");
+    assert_eq!(signed.from, "0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+
+    let other_chain = keys::AddressHocusPokus {
+      chain_id: Some(61),
+      ..magic_ingredients
+    };
+    assert!(sign::sign_eth_transaction(&from_json, &other_chain).is_err());
+
+    assert_eq!(sign::format_eth_units(1_000_000_000_000_000_000, 18), "1");
+    assert_eq!(sign::format_eth_units(1_500_000_000, 9), "1.5");
+    assert_eq!(sign::format_eth_units(21_000, 18), "0.000000000000021");
+  }
+
+  #[test]
+  fn test_bip85_applications() {
+    let (_, master_key) = keys::decode_extended_key("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb")
+      .expect("can not decode BIP85 master key");
+    let master_private_key = master_key.private_key.expect("missing master private key");
+    let master_chain_code = master_key.chain_code;
+
+    let entropy_vectors = [
+      (
+        "m/83696968'/0'/0'",
+        "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7",
+      ),
+      (
+        "m/83696968'/0'/1'",
+        "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e",
+      ),
+    ];
+
+    for (path, expected_entropy) in entropy_vectors {
+      let path = keys::parse_derivation_path(path).expect("invalid BIP85 path");
+      let entropy = keys::derive_bip85_entropy(&master_private_key, &master_chain_code, &path)
+        .expect("can not derive BIP85 entropy");
+      assert_eq!(hex::encode(&*entropy), expected_entropy);
+    }
+
+    let bip85_vectors = vec![
+      _Bip85Vector {
+        application: "mnemonic",
+        dictionary: "English",
+        length: 12,
+        index: 0,
+        expected: "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+      },
+      _Bip85Vector {
+        application: "mnemonic",
+        dictionary: "English",
+        length: 18,
+        index: 0,
+        expected: "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token",
+      },
+      _Bip85Vector {
+        application: "mnemonic",
+        dictionary: "English",
+        length: 24,
+        index: 0,
+        expected: "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+      },
+      _Bip85Vector {
+        application: "hex",
+        dictionary: "",
+        length: 64,
+        index: 0,
+        expected: "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c",
+      },
+      _Bip85Vector {
+        application: "wif",
+        dictionary: "",
+        length: 0,
+        index: 0,
+        expected: "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp",
+      },
+      _Bip85Vector {
+        application: "password",
+        dictionary: "",
+        length: 21,
+        index: 0,
+        expected: "dKLoepugzdVJvdL56ogNV",
+      },
+    ];
+
+    for vector in bip85_vectors {
+      let derived = match vector.application {
+        "mnemonic" => keys::derive_bip85_mnemonic(
+          &master_private_key,
+          &master_chain_code,
+          vector.dictionary,
+          vector.length,
+          vector.index,
+        ),
+        "hex" => keys::derive_bip85_hex(
+          &master_private_key,
+          &master_chain_code,
+          vector.length,
+          vector.index,
+        ),
+        "wif" => keys::derive_bip85_wif(&master_private_key, &master_chain_code, vector.index),
+        _ => keys::derive_bip85_password(
+          &master_private_key,
+          &master_chain_code,
+          vector.length,
+          vector.index,
+        ),
+      }
+      .expect("can not derive BIP85 application");
+
+      assert_eq!(derived.as_str(), vector.expected);
+    }
+
+    // Every dictionary and word count yields a checksum valid mnemonic in that language,
+    // Portuguese has no BIP85 language code
+    for dictionary in crate::VALID_MNEMONIC_DICTIONARY {
+      if *dictionary == "Portuguese" {
+        let error =
+          keys::derive_bip85_mnemonic(&master_private_key, &master_chain_code, dictionary, 12, 0)
+            .expect_err("Portuguese must not be a BIP85 language");
+        assert!(format!("{error:?}").contains("Unsupported BIP85 language"));
+        continue;
+      }
+
+      for word_count in [12, 15, 18, 21, 24] {
+        let mnemonic = keys::derive_bip85_mnemonic(
+          &master_private_key,
+          &master_chain_code,
+          dictionary,
+          word_count,
+          0,
+        )
+        .expect("can not derive BIP85 mnemonic");
+        assert_eq!(mnemonic.split_whitespace().count(), word_count as usize);
+
+        let validation = keys::validate_mnemonic_words(&mnemonic, Some(dictionary))
+          .expect("can not validate BIP85 mnemonic");
+        assert!(
+          validation.is_valid(),
+          "{dictionary}: {:?}",
+          validation.issues
+        );
+        assert_eq!(validation.dictionary, *dictionary);
+      }
+    }
+
+    assert!(
+      keys::derive_bip85_mnemonic(&master_private_key, &master_chain_code, "English", 13, 0)
+        .is_err()
+    );
+    assert!(keys::derive_bip85_hex(&master_private_key, &master_chain_code, 15, 0).is_err());
+    assert!(keys::derive_bip85_password(&master_private_key, &master_chain_code, 87, 0).is_err());
+  }
+
+  #[test]
+  fn test_slip39_shares() {
+    // Official SLIP-39 vectors, all encrypted with the passphrase "TREZOR"
+    let slip39_vectors = vec![
+      // Valid mnemonic without sharing (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard",
+        ],
+        expected_master_secret: "bb54aac4b89dc868ba37d9cc21b2cece",
+      },
+      // Mnemonic with invalid checksum (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonic with invalid padding (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness",
+        ],
+        expected_master_secret: "",
+      },
+      // Basic sharing 2-of-3 (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+          "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ],
+        expected_master_secret: "b43ceb7e57a0ea8766221624d01b0864",
+      },
+      // Basic sharing 2-of-3 (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with different identifiers (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "adequate smoking academic acid debut wine petition glen cluster slow rhyme slow simple epidemic rumor junk tracks treat olympic tolerate",
+          "adequate stay academic agency agency formal party ting frequent learn upstairs remember smear leaf damage anatomy ladle market hush corner",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with different iteration exponents (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "peasant leaves academic acid desert exact olympic math alive axle trial tackle drug deny decent smear dominant desert bucket remind",
+          "peasant leader academic agency cultural blessing percent network envelope medal junk primary human pumps jacket fragment payroll ticket evoke voice",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with mismatching group thresholds (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "liberty category beard echo animal fawn temple briefing math username various wolf aviation fancy visual holy thunder yelp helpful payment",
+          "liberty category beard email beyond should fancy romp founder easel pink holy hairy romp loyalty material victim owner toxic custody",
+          "liberty category academic easy being hazard crush diminish oral lizard reaction cluster force dilemma deploy force club veteran expect photo",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with mismatching group counts (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "average senior academic leaf broken teacher expect surface hour capture obesity desire negative dynamic dominant pistol mineral mailman iris aide",
+          "average senior academic agency curious pants blimp spew clothes slice script dress wrap firm shaft regular slavery negative theater roster",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with greater group threshold than group counts (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "music husband acrobat acid artist finance center either graduate swimming object bike medical clothes station aspect spider maiden bulb welcome",
+          "music husband acrobat agency advance hunting bike corner density careful material civil evil tactics remind hawk discuss hobo voice rainbow",
+          "music husband beard academic black tricycle clock mayor estimate level photo episode exclude ecology papa source amazing salt verify divorce",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with duplicate member indices (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "device stay academic always dive coal antenna adult black exceed stadium herald advance soldier busy dryer daughter evaluate minister laser",
+          "device stay academic always dwarf afraid robin gravity crunch adjust soul branch walnut coastal dream costume scholar mortgage mountain pumps",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with mismatching member thresholds (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "hour painting academic academic device formal evoke guitar random modern justice filter withdraw trouble identify mailman insect general cover oven",
+          "hour painting academic agency artist again daisy capital beaver fiber much enjoy suitable symbolic identify photo editor romp float echo",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics giving an invalid digest (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "guilt walnut academic acid deliver remove equip listen vampire tactics nylon rhythm failure husband fatigue alive blind enemy teaspoon rebound",
+          "guilt walnut academic agency brave hamster hobo declare herd taste alpha slim criminal mild arcade formal romp branch pink ambition",
+        ],
+        expected_master_secret: "",
+      },
+      // Insufficient number of groups (128 bits, case 1)
+      _Slip39Vector {
+        mnemonics: &[
+          "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+        ],
+        expected_master_secret: "",
+      },
+      // Insufficient number of groups (128 bits, case 2)
+      _Slip39Vector {
+        mnemonics: &[
+          "eraser senior decision scared cargo theory device idea deliver modify curly include pancake both news skin realize vitamins away join",
+          "eraser senior decision roster beard treat identify grumpy salt index fake aviation theater cubic bike cause research dragon emphasis counter",
+        ],
+        expected_master_secret: "",
+      },
+      // Threshold number of groups and members in each group (128 bits, case 1)
+      _Slip39Vector {
+        mnemonics: &[
+          "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+          "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+          "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+          "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+          "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+        ],
+        expected_master_secret: "7c3397a292a5941682d7a4ae2d898d11",
+      },
+      // Threshold number of groups and members in each group (128 bits, case 2)
+      _Slip39Vector {
+        mnemonics: &[
+          "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+          "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+          "eraser senior decision scared cargo theory device idea deliver modify curly include pancake both news skin realize vitamins away join",
+        ],
+        expected_master_secret: "7c3397a292a5941682d7a4ae2d898d11",
+      },
+      // Threshold number of groups and members in each group (128 bits, case 3)
+      _Slip39Vector {
+        mnemonics: &[
+          "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+          "eraser senior acrobat romp bishop medical gesture pumps secret alive ultimate quarter priest subject class dictate spew material endless market",
+        ],
+        expected_master_secret: "7c3397a292a5941682d7a4ae2d898d11",
+      },
+      // Valid mnemonic without sharing (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck",
+        ],
+        expected_master_secret: "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+      },
+      // Mnemonic with invalid checksum (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect lunar",
+        ],
+        expected_master_secret: "",
+      },
+      // Basic sharing 2-of-3 (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap",
+          "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade",
+        ],
+        expected_master_secret: "c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae",
+      },
+      // Basic sharing 2-of-3 (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with different identifiers (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "smear husband academic acid deadline scene venture distance dive overall parking bracelet elevator justice echo burning oven chest duke nylon",
+          "smear isolate academic agency alpha mandate decorate burden recover guard exercise fatal force syndrome fumes thank guest drift dramatic mule",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with different iteration exponents (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "finger trash academic acid average priority dish revenue academic hospital spirit western ocean fact calcium syndrome greatest plan losing dictate",
+          "finger traffic academic agency building lilac deny paces subject threaten diploma eclipse window unknown health slim piece dragon focus smirk",
+        ],
+        expected_master_secret: "",
+      },
+      // Mnemonics with mismatching group thresholds (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "flavor pink beard echo depart forbid retreat become frost helpful juice unwrap reunion credit math burning spine black capital lair",
+          "flavor pink academic easy credit cage raisin crazy closet lobe mobile become drink human tactics valuable hand capture sympathy finger",
+        ],
+        expected_master_secret: "",
+      },
+      // Insufficient number of groups (256 bits, case 1)
+      _Slip39Vector {
+        mnemonics: &[
+          "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+        ],
+        expected_master_secret: "",
+      },
+      // Threshold number of groups, but insufficient number of members in one group (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "wildlife deal decision shadow analysis adjust bulb skunk muscle mandate obesity total guitar coal gravity carve slim jacket ruin rebuild ancestor numerous hour mortgage require herd maiden public ceiling pecan pickup shadow club",
+          "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+        ],
+        expected_master_secret: "",
+      },
+      // Threshold number of groups and members in each group (256 bits, case 1)
+      _Slip39Vector {
+        mnemonics: &[
+          "wildlife deal ceramic round aluminum pitch goat racism employer miracle percent math decision episode dramatic editor lily prospect program scene rebuild display sympathy have single mustang junction relate often chemical society wits estate",
+          "wildlife deal ceramic scatter argue equip vampire together ruin reject literary rival distance aquatic agency teammate rebound false argue miracle stay again blessing peaceful unknown cover beard acid island language debris industry idle",
+          "wildlife deal ceramic snake agree voter main lecture axis kitchen physics arcade velvet spine idea scroll promise platform firm sharp patrol divorce ancestor fantasy forbid goat ajar believe swimming cowboy symbolic plastic spelling",
+          "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+        ],
+        expected_master_secret: "5385577c8cfc6c1a8aa0f7f10ecde0a3318493262591e78b8c14c6686167123b",
+      },
+      // Threshold number of groups and members in each group (256 bits, case 2)
+      _Slip39Vector {
+        mnemonics: &[
+          "wildlife deal acrobat romp anxiety axis starting require metric flexible geology game drove editor edge screw helpful have huge holy making pitch unknown carve holiday numb glasses survive already tenant adapt goat fangs",
+          "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+        ],
+        expected_master_secret: "5385577c8cfc6c1a8aa0f7f10ecde0a3318493262591e78b8c14c6686167123b",
+      },
+      // Valid extendable mnemonic without sharing (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn",
+        ],
+        expected_master_secret: "1679b4516e0ee5954351d288a838f45e",
+      },
+      // Extendable basic sharing 2-of-3 (128 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "enemy favorite academic acid cowboy phrase havoc level response walnut budget painting inside trash adjust froth kitchen learn tidy punish",
+          "enemy favorite academic always academic sniff script carpet romp kind promise scatter center unfair training emphasis evening belong fake enforce",
+        ],
+        expected_master_secret: "48b1a4b80b8c209ad42c33672bdaa428",
+      },
+      // Valid extendable mnemonic without sharing (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "impulse calcium academic academic alcohol sugar lyrics pajamas column facility finance tension extend space birthday rainbow swimming purple syndrome facility trial warn duration snapshot shadow hormone rhyme public spine counter easy hawk album",
+        ],
+        expected_master_secret: "8340611602fe91af634a5f4608377b5235fa2d757c51d720c0c7656249a3035f",
+      },
+      // Extendable basic sharing 2-of-3 (256 bits)
+      _Slip39Vector {
+        mnemonics: &[
+          "western apart academic always artist resident briefing sugar woman oven coding club ajar merit pecan answer prisoner artist fraction amount desktop mild false necklace muscle photo wealthy alpha category unwrap spew losing making",
+          "western apart academic acid answer ancient auction flip image penalty oasis beaver multiple thunder problem switch alive heat inherit superior teaspoon explain blanket pencil numb lend punish endless aunt garlic humidity kidney observe",
+        ],
+        expected_master_secret: "8dc652d6d6cd370d8c963141f6d79ba440300f25c467302c1d966bff8f62300d",
+      },
+    ];
+
+    for vector in slip39_vectors {
+      match slip39::recover_slip39_secret(vector.mnemonics, "TREZOR") {
+        Ok(master_secret) => {
+          assert_eq!(hex::encode(&*master_secret), vector.expected_master_secret)
+        }
+        Err(err) => assert!(
+          vector.expected_master_secret.is_empty(),
+          "recovery failed: {err}"
+        ),
+      }
+    }
+
+    // Shares generated from the abandon ... about entropy recover the same entropy
+    let full_entropy = "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000011";
+    let master_secret =
+      slip39::get_master_secret_from_entropy(full_entropy).expect("can not read entropy");
+    assert_eq!(master_secret.len(), 16);
+
+    let groups = [
+      slip39::Slip39Group {
+        member_threshold: 2,
+        member_count: 3,
+      },
+      slip39::Slip39Group {
+        member_threshold: 1,
+        member_count: 1,
+      },
+      slip39::Slip39Group {
+        member_threshold: 3,
+        member_count: 5,
+      },
+    ];
+
+    for extendable in [false, true] {
+      let shares =
+        slip39::generate_slip39_shares(&master_secret, "TREZOR", 2, &groups, 0, extendable)
+          .expect("can not generate SLIP-39 shares");
+      assert_eq!(
+        shares.iter().map(|group| group.len()).collect::<Vec<_>>(),
+        [3, 1, 5]
+      );
+      assert!(
+        shares
+          .iter()
+          .flatten()
+          .all(|share| share.split_whitespace().count() == 20)
+      );
+
+      let recovered = slip39::recover_slip39_secret(
+        &[
+          &shares[0][2],
+          &shares[2][4],
+          &shares[0][0],
+          &shares[2][1],
+          &shares[2][0],
+        ],
+        "TREZOR",
+      )
+      .expect("can not recover SLIP-39 shares");
+      assert_eq!(
+        slip39::get_entropy_from_master_secret(&recovered)
+          .expect("can not encode entropy")
+          .as_str(),
+        full_entropy
+      );
+
+      let recovered =
+        slip39::recover_slip39_secret(&[&shares[1][0], &shares[0][1], &shares[0][2]], "TREZOR")
+          .expect("can not recover SLIP-39 shares");
+      assert_eq!(recovered, master_secret);
+
+      assert!(slip39::recover_slip39_secret(&[&shares[1][0], &shares[0][1]], "TREZOR").is_err());
+      assert_ne!(
+        slip39::recover_slip39_secret(&[&shares[1][0], &shares[0][1], &shares[0][2]], "")
+          .expect("can not recover SLIP-39 shares"),
+        master_secret
+      );
+    }
+
+    let invalid_groups = [slip39::Slip39Group {
+      member_threshold: 1,
+      member_count: 2,
+    }];
+    assert!(
+      slip39::generate_slip39_shares(&master_secret, "", 1, &invalid_groups, 0, true).is_err()
+    );
+  }
+
+  #[test]
+  fn test_mnemonic_validation() {
+    let abandon = ["abandon"; 11].join(" ");
+
+    let valid =
+      keys::validate_mnemonic_words(&format!("{abandon} about"), Some("English")).unwrap();
+    assert!(valid.is_valid());
+    assert_eq!(
+      valid.entropy.as_deref(),
+      Some(format!("{}0011", "0".repeat(128)).as_str())
+    );
+
+    let checksum = keys::validate_mnemonic_words(&format!("{abandon} abandon"), None).unwrap();
+    assert_eq!(checksum.dictionary, "English");
+    assert_eq!(checksum.issues, vec![keys::MnemonicIssue::InvalidChecksum]);
+    assert!(checksum.entropy.is_none());
+
+    let typo = keys::validate_mnemonic_words(&format!("{abandon} abuot"), None).unwrap();
+    match &typo.issues[..] {
+      [
+        keys::MnemonicIssue::UnknownWord {
+          position,
+          word,
+          suggestions,
+        },
+      ] => {
+        assert_eq!((*position, word.as_str()), (12, "abuot"));
+        assert!(suggestions.contains(&"about".to_string()));
+      }
+      issues => panic!("unexpected issues: {issues:?}"),
+    }
+
+    let short = keys::validate_mnemonic_words(&abandon, None).unwrap();
+    assert_eq!(
+      short.issues,
+      vec![keys::MnemonicIssue::InvalidWordCount(11)]
+    );
+
+    let spanish_entropy = format!(
+      "{}{}",
+      "1".repeat(128),
+      qr2m_lib::calculate_checksum_for_entropy(&"1".repeat(128))
+    );
+    let spanish = keys::encode_mnemonic_words(&spanish_entropy, Some("Spanish")).unwrap();
+    let detected = keys::validate_mnemonic_words(&spanish, None).unwrap();
+    assert_eq!(detected.dictionary, "Spanish");
+    assert!(detected.is_valid());
+
+    // The Spanish wordlist is stored decomposed while typed accents are usually precomposed
+    let spanish_nfc = format!("{} abierto", ["\u{e1}baco"; 11].join(" "));
+    let spanish_nfd = format!("{} abierto", ["a\u{301}baco"; 11].join(" "));
+    for mnemonic in [&spanish_nfc, &spanish_nfd, &spanish_nfc.to_uppercase()] {
+      let validation = keys::validate_mnemonic_words(mnemonic, None).unwrap();
+      assert_eq!(validation.dictionary, "Spanish");
+      assert!(validation.is_valid());
+      assert_eq!(
+        validation.entropy.as_deref(),
+        Some(format!("{}0011", "0".repeat(128)).as_str())
+      );
+    }
+    assert!(
+      keys::find_last_mnemonic_words(&["\u{e1}baco"; 11].join(" "), Some("Spanish"))
+        .unwrap()
+        .contains(&"abierto".to_string())
+    );
+    assert_eq!(
+      keys::generate_seed_from_mnemonic(&spanish_nfc, "\u{e1}").unwrap(),
+      keys::generate_seed_from_mnemonic(&spanish_nfd, "a\u{301}").unwrap()
+    );
+
+    let last_words = keys::find_last_mnemonic_words(&abandon, None).unwrap();
+    assert_eq!(last_words.len(), 128);
+    assert!(last_words.contains(&"about".to_string()));
+    for word in &last_words {
+      assert!(
+        keys::validate_mnemonic_words(&format!("{abandon} {word}"), None)
+          .unwrap()
+          .is_valid()
+      );
+    }
+
+    let last_words =
+      keys::find_last_mnemonic_words(&["abandon"; 23].join(" "), Some("English")).unwrap();
+    assert_eq!(last_words.len(), 8);
+    assert!(last_words.contains(&"art".to_string()));
+
+    assert!(keys::find_last_mnemonic_words(&format!("{abandon} about"), None).is_err());
+    assert!(
+      keys::find_last_mnemonic_words(&format!("{} abuot", ["abandon"; 10].join(" ")), None)
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn test_mnemonic_recovery() {
+    let coin = keys::AddressHocusPokus {
+      coin_index: 0,
+      derivation_path: "m/44'/0'/0'/0/0".to_string(),
+      master_private_key_bytes: Default::default(),
+      master_chain_code_bytes: Default::default(),
+      master_public_key_bytes: Vec::new(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: "0x00".to_string(),
+      script_hash: "0x05".to_string(),
+      bech32_hrp: "bc".to_string(),
+      key_derivation: "secp256k1".to_string(),
+      wallet_import_format: "0x80".to_string(),
+      hash: "sha256".to_string(),
+      chain_id: None,
+      eip1191: false,
+      bip: 44,
+      compressed: true,
+    };
+    let target_address = "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA";
+    let expected = format!("{} about", ["abandon"; 11].join(" "));
+    let cancel_flag = std::sync::Mutex::new(false);
+    let progress = std::sync::Mutex::new(0.0);
+    let report = |value: f64| *progress.lock().unwrap() = value;
+
+    for mnemonic in [
+      format!("{} ? about", ["abandon"; 10].join(" ")),
+      format!("{} abuot", ["abandon"; 11].join(" ")),
+      format!("abandon? {} about", ["abandon"; 10].join(" ")),
+    ] {
+      let recovered = keys::recover_mnemonic_words(
+        &mnemonic,
+        None,
+        "",
+        &coin,
+        target_address,
+        &cancel_flag,
+        &report,
+      )
+      .unwrap();
+      assert_eq!(recovered.as_deref(), Some(expected.as_str()), "{mnemonic}");
+      assert_eq!(*progress.lock().unwrap(), 1.0);
+    }
+
+    let wrong_passphrase = keys::recover_mnemonic_words(
+      &format!("{} ? about", ["abandon"; 10].join(" ")),
+      Some("English"),
+      "TREZOR",
+      &coin,
+      target_address,
+      &cancel_flag,
+      &report,
+    )
+    .unwrap();
+    assert!(wrong_passphrase.is_none());
+
+    // Base58 addresses only match with their exact case
+    let wrong_case = keys::recover_mnemonic_words(
+      &format!("{} ? about", ["abandon"; 10].join(" ")),
+      None,
+      "",
+      &coin,
+      &target_address.to_lowercase(),
+      &cancel_flag,
+      &report,
+    )
+    .unwrap();
+    assert!(wrong_case.is_none());
+
+    let segwit_coin = keys::AddressHocusPokus {
+      coin_index: 0,
+      derivation_path: "m/84'/0'/0'/0/0".to_string(),
+      master_private_key_bytes: Default::default(),
+      master_chain_code_bytes: Default::default(),
+      master_public_key_bytes: Vec::new(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: "0x00".to_string(),
+      script_hash: "0x05".to_string(),
+      bech32_hrp: "bc".to_string(),
+      key_derivation: "secp256k1".to_string(),
+      wallet_import_format: "0x80".to_string(),
+      hash: "sha256".to_string(),
+      chain_id: None,
+      eip1191: false,
+      bip: 84,
+      compressed: true,
+    };
+    let recovered = keys::recover_mnemonic_words(
+      &format!("{} ? about", ["abandon"; 10].join(" ")),
+      None,
+      "",
+      &segwit_coin,
+      "BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU",
+      &cancel_flag,
+      &report,
+    )
+    .unwrap();
+    assert_eq!(recovered.as_deref(), Some(expected.as_str()));
+
+    let evm_coin = keys::AddressHocusPokus {
+      coin_index: 60,
+      derivation_path: "m/44'/60'/0'/0/0".to_string(),
+      master_private_key_bytes: Default::default(),
+      master_chain_code_bytes: Default::default(),
+      master_public_key_bytes: Vec::new(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: String::new(),
+      script_hash: String::new(),
+      bech32_hrp: String::new(),
+      key_derivation: "secp256k1".to_string(),
+      wallet_import_format: String::new(),
+      hash: "keccak256".to_string(),
+      chain_id: Some(1),
+      eip1191: false,
+      bip: 44,
+      compressed: true,
+    };
+    let recovered = keys::recover_mnemonic_words(
+      &format!("{} ? about", ["abandon"; 10].join(" ")),
+      None,
+      "",
+      &evm_coin,
+      "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+      &cancel_flag,
+      &report,
+    )
+    .unwrap();
+    assert_eq!(recovered.as_deref(), Some(expected.as_str()));
+    assert!(
+      keys::recover_mnemonic_words(
+        &format!("{} ? about", ["abandon"; 10].join(" ")),
+        None,
+        "",
+        &evm_coin,
+        "0x9858effd232b4033e47d90003d41ec34ecaEda94",
+        &cancel_flag,
+        &report,
+      )
+      .is_err()
+    );
+
+    *cancel_flag.lock().unwrap() = true;
+    let cancelled = keys::recover_mnemonic_words(
+      &format!("{} ? ?", ["abandon"; 10].join(" ")),
+      None,
+      "",
+      &coin,
+      target_address,
+      &cancel_flag,
+      &report,
+    )
+    .unwrap();
+    assert!(cancelled.is_none());
+
+    for mnemonic in [
+      expected.clone(),
+      format!("{} ? ? ?", ["abandon"; 9].join(" ")),
+      format!("{} ?", ["abandon"; 10].join(" ")),
+    ] {
+      assert!(
+        keys::recover_mnemonic_words(
+          &mnemonic,
+          None,
+          "",
+          &coin,
+          target_address,
+          &cancel_flag,
+          &report
+        )
+        .is_err()
+      );
+    }
+  }
+
+  #[test]
+  fn test_electrum_seeds() {
+    let standard = "cycle rocket west magnet parrot shuffle foot correct salt library feed song";
+    let segwit = "bitter grass shiver impose acquire brush forget axis eager alone wine silver";
+
+    assert_eq!(
+      keys::get_electrum_seed_type(standard),
+      Some(keys::ElectrumSeedType::Standard)
+    );
+    assert_eq!(
+      keys::get_electrum_seed_type(&standard.to_uppercase()),
+      Some(keys::ElectrumSeedType::Standard)
+    );
+    assert_eq!(
+      keys::get_electrum_seed_type(segwit),
+      Some(keys::ElectrumSeedType::Segwit)
+    );
+    assert_eq!(
+      keys::get_electrum_seed_type(
+        "kiss live scene rude gate step hip quarter bunker oxygen motor glove"
+      ),
+      Some(keys::ElectrumSeedType::TwoFactor)
+    );
+    assert_eq!(
+      keys::get_electrum_seed_type(
+        "universe topic remind silver february ranch shine worth innocent cattle enhance wise"
+      ),
+      Some(keys::ElectrumSeedType::TwoFactorSegwit)
+    );
+    assert_eq!(
+      keys::get_electrum_seed_type(&format!("{} about", ["abandon"; 11].join(" "))),
+      None
+    );
+    assert_eq!(keys::get_electrum_seed_type(""), None);
+
+    for (mnemonic, change, expected) in [
+      (
+        standard,
+        false,
+        ("m/0/0", "1NNkttn1YvVGdqBW4PR6zvc3Zx3H5owKRf"),
+      ),
+      (
+        standard,
+        true,
+        ("m/1/0", "1KSezYMhAJMWqFbVFB2JshYg69UpmEXR4D"),
+      ),
+      (
+        segwit,
+        false,
+        ("m/0'/0/0", "bc1q3g5tmkmlvxryhh843v4dz026avatc0zzr6h3af"),
+      ),
+      (
+        segwit,
+        true,
+        ("m/0'/1/0", "bc1qdy94n2q5qcp0kg7v9yzwe6wvfkhnvyzje7nx2p"),
+      ),
+    ] {
+      let addresses = keys::generate_electrum_addresses(mnemonic, "", change, 0, 2).unwrap();
+      assert_eq!(addresses.len(), 2);
+      assert_eq!(
+        (addresses[0].0.as_str(), addresses[0].1.address.as_str()),
+        expected
+      );
+    }
+
+    let with_passphrase = keys::generate_seed_from_electrum_mnemonic(standard, "Secret").unwrap();
+    assert_ne!(
+      with_passphrase,
+      keys::generate_seed_from_electrum_mnemonic(standard, "").unwrap()
+    );
+    assert_eq!(
+      with_passphrase,
+      keys::generate_seed_from_electrum_mnemonic(standard, "secret").unwrap()
+    );
+    assert_ne!(
+      with_passphrase,
+      keys::generate_seed_from_mnemonic(standard, "Secret").unwrap()
+    );
+
+    // Seed vectors from Electrum's test_mnemonic.py
+    let japanese =
+      "なのか ひろい しなん まなぶ つぶす さがす おしゃれ かわく おいかける けさき かいとう さたん";
+    let chinese = "眼 悲 叛 改 节 跃 衡 响 疆 股 遂 冬";
+    let spanish =
+      "almíbar tibio superar vencer hacha peatón príncipe matar consejo polen vehículo odisea";
+    for (mnemonic, passphrase, seed_type, expected_seed) in [
+      (
+        "wild father tree among universe such mobile favorite target dynamic credit identify",
+        "Did you ever hear the tragedy of Darth Plagueis the Wise?",
+        keys::ElectrumSeedType::Segwit,
+        "4aa29f2aeb0127efb55138ab9e7be83b36750358751906f86c662b21a1ea1370f949e6d1a12fa56d3d93cadda93038c76ac8118597364e46f5156fde6183c82f",
+      ),
+      (
+        japanese,
+        "",
+        keys::ElectrumSeedType::Standard,
+        "d3eaf0e44ddae3a5769cb08a26918e8b308258bcb057bb704c6f69713245c0b35cb92c03df9c9ece5eff826091b4e74041e010b701d44d610976ce8bfb66a8ad",
+      ),
+      (
+        chinese,
+        "",
+        keys::ElectrumSeedType::Segwit,
+        "0b9077db7b5a50dbb6f61821e2d35e255068a5847e221138048a20e12d80b673ce306b6fe7ac174ebc6751e11b7037be6ee9f17db8040bb44f8466d519ce2abf",
+      ),
+      (
+        chinese,
+        "给我一些测试向量谷歌",
+        keys::ElectrumSeedType::Segwit,
+        "6c03dd0615cf59963620c0af6840b52e867468cc64f20a1f4c8155705738e87b8edb0fc8a6cee4085776cb3a629ff88bb1a38f37085efdbf11ce9ec5a7fa5f71",
+      ),
+      (
+        spanish,
+        "",
+        keys::ElectrumSeedType::Standard,
+        "18bffd573a960cc775bbd80ed60b7dc00bc8796a186edebe7fc7cf1f316da0fe937852a969c5c79ded8255cdf54409537a16339fbe33fb9161af793ea47faa7a",
+      ),
+    ] {
+      assert_eq!(keys::get_electrum_seed_type(mnemonic), Some(seed_type));
+      assert_eq!(
+        hex::encode(keys::generate_seed_from_electrum_mnemonic(mnemonic, passphrase).unwrap()),
+        expected_seed
+      );
+    }
+
+    // Accents, full width spaces and spaces between CJK characters do not change the seed
+    let spanish_seed = keys::generate_seed_from_electrum_mnemonic(spanish, "").unwrap();
+    for mnemonic in [
+      "almibar tibio superar vencer hacha peaton principe matar consejo polen vehiculo odisea",
+      "ALMÍBAR  tibio superar vencer hacha peato\u{301}n príncipe matar consejo polen vehículo odisea",
+    ] {
+      assert_eq!(
+        keys::generate_seed_from_electrum_mnemonic(mnemonic, "").unwrap(),
+        spanish_seed
+      );
+    }
+    assert_eq!(
+      keys::generate_seed_from_electrum_mnemonic(&japanese.replace(' ', "\u{3000}"), "").unwrap(),
+      keys::generate_seed_from_electrum_mnemonic(japanese, "").unwrap()
+    );
+    assert_eq!(
+      keys::generate_seed_from_electrum_mnemonic(&chinese.replace(' ', ""), "").unwrap(),
+      keys::generate_seed_from_electrum_mnemonic(chinese, "").unwrap()
+    );
+
+    assert!(
+      keys::generate_seed_from_electrum_mnemonic(
+        &format!("{} about", ["abandon"; 11].join(" ")),
+        ""
+      )
+      .is_err()
+    );
+    assert!(
+      keys::get_electrum_derivation_path(keys::ElectrumSeedType::TwoFactor, false, 0).is_err()
+    );
+  }
+
+  #[test]
+  fn test_debug_output_redacts_key_material() {
+    let (master_private_key_bytes, master_chain_code_bytes) = derive_master_keys_from_mnemonic(
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "",
+    );
+    let private_key_hex = hex::encode(&master_private_key_bytes);
+    let chain_code_hex = hex::encode(&master_chain_code_bytes);
+    let private_key_bytes = format!("{:?}", master_private_key_bytes);
+
+    let extended_key =
+      keys::ExtendedKey::new_master(&master_private_key_bytes, &master_chain_code_bytes)
+        .expect("can not create extended key");
+    let icarus_key = keys::IcarusKey::new_master(&master_private_key_bytes[..16], "")
+      .expect("can not derive icarus root");
+    let icarus_private_key_hex = hex::encode(icarus_key.private_key);
+    let monero_keys = keys::MoneroKeys::from_secp256k1_private_key(
+      &master_private_key_bytes
+        .clone()
+        .try_into()
+        .expect("private key must be 32 bytes"),
+    );
+    let monero_secrets = [
+      hex::encode(monero_keys.private_spend_key),
+      hex::encode(monero_keys.private_view_key),
+    ];
+
+    let address = keys::generate_address(keys::AddressHocusPokus {
+      coin_index: 128,
+      derivation_path: "m/44'/128'/0'/0/0".to_string(),
+      master_private_key_bytes: master_private_key_bytes.clone().into(),
+      master_chain_code_bytes: master_chain_code_bytes.clone().into(),
+      master_public_key_bytes: Vec::new(),
+      master_depth: 0,
+      master_child_number: 0,
+      public_key_hash: "0x12".to_string(),
+      script_hash: String::new(),
+      bech32_hrp: String::new(),
+      key_derivation: "secp256k1-monero".to_string(),
+      wallet_import_format: String::new(),
+      hash: "cryptonote".to_string(),
+      chain_id: None,
+      eip1191: false,
+      bip: 44,
+      compressed: true,
+    })
+    .expect("can not generate address")
+    .expect("no address generated");
+    let address_secrets = [
+      address.private_key.to_string(),
+      address
+        .private_view_key
+        .as_deref()
+        .unwrap_or_default()
+        .to_string(),
+      address.mnemonic.as_deref().unwrap_or_default().to_string(),
+    ];
+
+    let outputs = [
+      format!("{extended_key:?}"),
+      format!("{icarus_key:?}"),
+      format!("{monero_keys:?}"),
+      format!("{address:?}"),
+      format!(
+        "{:?}",
+        keys::DerivationNode::new(
+          master_private_key_bytes.clone(),
+          master_chain_code_bytes.clone(),
+          Vec::new(),
+          0,
+          0
+        )
+      ),
+    ];
+
+    for output in &outputs {
+      assert!(output.contains("REDACTED"), "{output}");
+
+      for secret in [
+        &private_key_hex,
+        &chain_code_hex,
+        &private_key_bytes,
+        &icarus_private_key_hex,
+      ]
+      .into_iter()
+      .chain(monero_secrets.iter())
+      .chain(address_secrets.iter())
+      {
+        assert!(!output.contains(secret.as_str()), "{output} leaks {secret}");
+      }
+    }
+  }
+
+  #[test]
+  fn test_psbt_bip174_vectors() {
+    // Serialization vectors from BIP174, the invalid ones have a bad magic, a non empty
+    // scriptSig, signatures in the unsigned transaction and a duplicate unsigned transaction
+    let invalid_psbts = [
+      "0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300",
+      "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000",
+      "70736274ff0100fd0a010200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be4000000006a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa88292feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac00000000000001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000",
+      "70736274ff000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000",
+      "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffffff010000000000000000036a010000000000000000",
+    ];
+
+    for invalid_psbt in invalid_psbts {
+      let data = hex::decode(invalid_psbt).expect("invalid PSBT hex");
+      assert!(sign::Psbt::from_bytes(&data).is_err());
+    }
+
+    let valid_psbts = [
+      "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab300000000000000",
+      "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac000000000001076a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa882920001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000",
+      "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001030401000000000000",
+      "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac00000000000100df0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e13000001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb8230800220202ead596687ca806043edc3de116cdf29d5e9257c196cd055cf698c8d02bf24e9910b4a6ba670000008000000080020000800022020394f62be9df19952c5587768aeb7698061ad2c4a25c894f47d8c162b4d7213d0510b4a6ba6700000080010000800200008000",
+      "70736274ff0100550200000001279a2323a5dfb51fc45f220fa58b0fc13e1e3342792a85d7e36cd6333b5cbc390000000000ffffffff01a05aea0b000000001976a914ffe9c0061097cc3b636f2cb0460fa4fc427d2b4588ac0000000000010120955eea0b0000000017a9146345200f68d189e1adc0df1c4d16ea8f14c0dbeb87220203b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd4646304302200424b58effaaa694e1559ea5c93bbfd4a89064224055cdf070b6771469442d07021f5c8eb0fea6516d60b8acb33ad64ede60e8785bfb3aa94b99bdf86151db9a9a010104220020771fd18ad459666dd49f3d564e3dbc42f4c84774e360ada16816a8ed488d5681010547522103b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd462103de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd52ae220603b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd4610b4a6ba67000000800000008004000080220603de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd10b4a6ba670000008000000080050000800000",
+      "70736274ff01003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffffff010000000000000000036a010000000000000a0f0102030405060708090f0102030405060708090a0b0c0d0e0f0000",
+    ];
+
+    for valid_psbt in valid_psbts {
+      let data = hex::decode(valid_psbt).expect("invalid PSBT hex");
+      let psbt = sign::Psbt::from_bytes(&data).expect("can not parse PSBT");
+      assert_eq!(psbt.to_bytes(), data);
+    }
+  }
+
+  #[test]
+  fn test_taproot_key_path_signing() {
+    // keyPathSpending vectors from BIP341, inputs 3 and 4 use the supported sighash types
+    let transaction = sign::Transaction::from_bytes(
+      &hex::decode(
+        "02000000097de20cbff686da83a54981d2b9bab3586f4ca7e48f57f5b55963115f3b334e9c010000000000000000d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd990000000000fffffffff8e1f583384333689228c5d28eac13366be082dc57441760d957275419a418420000000000fffffffff0689180aa63b30cb162a73c6d2a38b7eeda2a83ece74310fda0843ad604853b0100000000feffffffaa5202bdf6d8ccd2ee0f0202afbbb7461d9264a25e5bfd3c5a52ee1239e0ba6c0000000000feffffff956149bdc66faa968eb2be2d2faa29718acbfe3941215893a2a3446d32acd050000000000000000000e664b9773b88c09c32cb70a2a3e4da0ced63b7ba3b22f848531bbb1d5d5f4c94010000000000000000e9aa6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf0000000000ffffffffa778eb6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af10100000000ffffffff0200ca9a3b000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac807840cb0000000020ac9a87f5594be208f8532db38cff670c450ed2fea8fcdefcc9a663f78bab962b0065cd1d",
+      )
+      .expect("invalid transaction hex"),
+    )
+    .expect("can not parse transaction");
+
+    let spent_outputs: Vec<_> = [
+      (
+        "512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+        420000000,
+      ),
+      (
+        "5120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+        462000000,
+      ),
+      (
+        "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+        294000000,
+      ),
+      (
+        "5120e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e",
+        504000000,
+      ),
+      (
+        "512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+        630000000,
+      ),
+      ("00147dd65592d0ab2fe0d0257d571abf032cd9db93dc", 378000000),
+      (
+        "512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+        672000000,
+      ),
+      (
+        "5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+        546000000,
+      ),
+      (
+        "512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220",
+        588000000,
+      ),
+    ]
+    .iter()
+    .map(|(script_pubkey, value)| sign::TxOutput {
+      value: *value,
+      script_pubkey: hex::decode(script_pubkey).expect("invalid script hex"),
+    })
+    .collect();
+
+    let signed_inputs = [
+      (
+        3,
+        0x01,
+        "d3c7af07da2d54f7a7735d3d0fc4f0a73164db638b2f2f7c43f711f6d4aa7e64",
+        "c525714a7f49c28aedbbba78c005931a81c234b2f6c99a73e4d06082adc8bf2b",
+        "bf013ea93474aa67815b1b6cc441d23b64fa310911d991e713cd34c7f5d46669",
+      ),
+      (
+        4,
+        0x00,
+        "f36bb07a11e469ce941d16b63b11b9b9120a84d9d87cff2c84a8d4affb438f4e",
+        "ccbd66c6f7e8fdab47b3a486f59d28262be857f30d4773f2d5ea47f7761ce0e2",
+        "4f900a0bae3f1446fd48490c2958b5a023228f01661cda3496a11da502a7f7ef",
+      ),
+    ];
+
+    for (index, hash_type, internal_private_key, merkle_root, expected_sighash) in signed_inputs {
+      let sighash = transaction.calculate_taproot_sighash(index, &spent_outputs, hash_type);
+      assert_eq!(hex::encode(sighash), expected_sighash);
+
+      let internal_private_key =
+        hex::decode(internal_private_key).expect("invalid private key hex");
+      let chain_code = vec![0u8; 32];
+      let master_key = keys::ExtendedKey::new_master(&internal_private_key, &chain_code)
+        .expect("can not create master key");
+
+      let mut psbt = sign::Psbt {
+        global: sign::PsbtMap {
+          entries: vec![(vec![0x00], transaction.to_bytes())],
+        },
+        inputs: Vec::new(),
+        outputs: vec![sign::PsbtMap::default(); transaction.outputs.len()],
+        transaction: transaction.clone(),
+      };
+
+      for spent_output in &spent_outputs {
+        let mut witness_utxo = spent_output.value.to_le_bytes().to_vec();
+        witness_utxo.push(spent_output.script_pubkey.len() as u8);
+        witness_utxo.extend_from_slice(&spent_output.script_pubkey);
+
+        let mut input = sign::PsbtMap::default();
+        input.insert(vec![0x01], witness_utxo);
+        psbt.inputs.push(input);
+      }
+
+      // A foreign derivation with an absurd leaf hash count has to be skipped, not fail signing
+      let mut foreign_derivation = vec![0xff];
+      foreign_derivation.extend_from_slice(&u64::MAX.to_le_bytes());
+      let input = &mut psbt.inputs[index];
+      input.insert([&[0x16][..], &[0x02; 32]].concat(), foreign_derivation);
+      input.insert(
+        [&[0x16][..], &master_key.public_key[1..]].concat(),
+        [&[0x00][..], &master_key.fingerprint()].concat(),
+      );
+      input.insert(vec![0x17], master_key.public_key[1..].to_vec());
+      input.insert(
+        vec![0x18],
+        hex::decode(merkle_root).expect("invalid merkle root hex"),
+      );
+      if hash_type != 0x00 {
+        input.insert(vec![0x03], (hash_type as u32).to_le_bytes().to_vec());
+      }
+
+      assert_eq!(
+        sign::sign_psbt(&mut psbt, &internal_private_key, &chain_code).expect("can not sign PSBT"),
+        1
+      );
+
+      let signature = psbt.inputs[index]
+        .get(0x13)
+        .expect("missing taproot key signature");
+      let expected_length = if hash_type == 0x00 { 64 } else { 65 };
+      assert_eq!(signature.len(), expected_length);
+      assert_eq!(signature.get(64).copied().unwrap_or(0x00), hash_type);
+
+      let signature = secp256k1::schnorr::Signature::from_byte_array(
+        signature[..64]
+          .try_into()
+          .expect("signature must be 64 bytes"),
+      );
+      let output_key = secp256k1::XOnlyPublicKey::from_byte_array(
+        spent_outputs[index].script_pubkey[2..]
+          .try_into()
+          .expect("output key must be 32 bytes"),
+      )
+      .expect("invalid output key");
+
+      assert!(
+        keys::secp256k1_context()
+          .verify_schnorr(&signature, &sighash, &output_key)
+          .is_ok()
+      );
+    }
   }
 }
 